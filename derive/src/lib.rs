@@ -0,0 +1,90 @@
+//! Derive macro for `vec_mut_scan`, see the `vec_mut_scan::Scannable` trait.
+//!
+//! Use this through `vec_mut_scan` with the `derive` feature enabled instead of depending on this
+//! crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput, Error, Fields, Index,
+    Member,
+};
+
+/// Derive `Scannable` for a struct wrapping a vector.
+///
+/// The struct must have a single field or mark one of its fields with `#[scannable]`. That field
+/// must be a `Vec<T>` or another type implementing `Scannable`.
+#[proc_macro_derive(Scannable, attributes(scannable))]
+pub fn derive_scannable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "Scannable can only be derived for structs",
+            ))
+        }
+    };
+
+    let marked: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| {
+            field
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("scannable"))
+        })
+        .collect();
+
+    let (index, field) = match (marked.len(), fields.len()) {
+        (1, _) => marked[0],
+        (0, 1) => (0, fields.iter().next().unwrap()),
+        (0, _) => {
+            return Err(Error::new(
+                fields.span(),
+                "mark the wrapped vector with #[scannable]",
+            ))
+        }
+        _ => {
+            return Err(Error::new(
+                marked[1].1.span(),
+                "only one field can be marked with #[scannable]",
+            ))
+        }
+    };
+
+    let member = match (&field.ident, fields) {
+        (Some(ident), _) => Member::Named(ident.clone()),
+        (None, Fields::Unnamed(_)) => Member::Unnamed(Index::from(index)),
+        (None, _) => unreachable!(),
+    };
+    let ty = &field.ty;
+    let name = &input.ident;
+    // The field's type may depend on type parameters, so require it to be scannable.
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#ty: ::vec_mut_scan::Scannable));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::vec_mut_scan::Scannable for #name #ty_generics #where_clause {
+            type Item = <#ty as ::vec_mut_scan::Scannable>::Item;
+
+            fn scannable_vec(&mut self) -> &mut ::vec_mut_scan::__private::Vec<Self::Item> {
+                ::vec_mut_scan::Scannable::scannable_vec(&mut self.#member)
+            }
+        }
+    })
+}
@@ -0,0 +1,211 @@
+use core::{
+    mem,
+    ops::{Deref, DerefMut},
+    ptr, slice,
+};
+
+use crate::VecMutScan;
+
+impl<'a, T: 'a> VecMutScan<'a, T> {
+    /// Advance to the last remaining item of the vector, consuming the remaining items from the
+    /// back.
+    ///
+    /// This mirrors [`DoubleEndedIterator::next_back`]. Calls can be mixed with
+    /// [`next`][VecMutScan::next] calls, until both ends meet. Items visited from the back that are
+    /// kept stay at the end of the vector, directly following the remaining items, so that
+    /// [`slices`][VecMutScan::slices] still covers all items. Removing an item from the back moves
+    /// the kept items visited from the back to close its place, instead of leaving a second gap.
+    ///
+    /// Use [`remaining`][VecMutScan::remaining] and [`visited_back`][VecMutScan::visited_back] to
+    /// tell the items visited from the back apart from the remaining items. After
+    /// [`suspend`][VecMutScan::suspend], they count as remaining items again.
+    ///
+    /// This returns a reference wrapper that enables item removal (see [`VecMutScanBackItem`]).
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// // Remove pairs of items that sum to 10, using two pointers over a sorted vector.
+    /// let mut numbers = vec![1, 2, 4, 5, 6, 8];
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    ///
+    /// loop {
+    ///     let low = match scan.remaining().first() {
+    ///         Some(&low) => low,
+    ///         None => break,
+    ///     };
+    ///     let high = *scan.remaining().last().unwrap();
+    ///     if scan.remaining().len() > 1 && low + high == 10 {
+    ///         scan.next().unwrap().remove();
+    ///         scan.next_back().unwrap().remove();
+    ///     } else if low + high < 10 {
+    ///         scan.next();
+    ///     } else {
+    ///         scan.next_back();
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 5]);
+    /// ```
+    pub fn next_back<'s>(&'s mut self) -> Option<VecMutScanBackItem<'s, 'a, T>> {
+        if self.read != self.end {
+            Some(VecMutScanBackItem { scan: self })
+        } else {
+            None
+        }
+    }
+
+    /// The items that were not visited yet, from either end.
+    ///
+    /// This is the suffix returned by [`slices`][VecMutScan::slices] without the kept items
+    /// visited using [`next_back`][VecMutScan::next_back].
+    pub fn remaining(&self) -> &[T] {
+        // The items `read..end` are valid.
+        unsafe { slice::from_raw_parts(self.base.add(self.read), self.end - self.read) }
+    }
+
+    /// Mutable access to the items that were not visited yet, from either end.
+    ///
+    /// See [`remaining`][VecMutScan::remaining].
+    pub fn remaining_mut(&mut self) -> &mut [T] {
+        // The items `read..end` are valid and borrowed uniquely through `self`.
+        unsafe { slice::from_raw_parts_mut(self.base.add(self.read), self.end - self.read) }
+    }
+
+    /// The items visited using [`next_back`][VecMutScan::next_back] that were kept, in the
+    /// vector's order.
+    ///
+    /// These items follow the [`remaining`][VecMutScan::remaining] items and end the suffix
+    /// returned by [`slices`][VecMutScan::slices].
+    pub fn visited_back(&self) -> &[T] {
+        // The items `end..len` are valid.
+        unsafe { slice::from_raw_parts(self.base.add(self.end), self.len - self.end) }
+    }
+}
+
+/// Reference wrapper that enables item removal for items visited from the back of a
+/// [`VecMutScan`], see [`next_back`][VecMutScan::next_back].
+///
+/// Dropping the wrapper keeps the item.
+#[repr(transparent)]
+pub struct VecMutScanBackItem<'s, 'a, T: 'a> {
+    scan: &'s mut VecMutScan<'a, T>,
+}
+
+// When a `VecMutScanBackItem` is created, there must be valid data at `scan.end - 1`.
+
+impl<'s, 'a, T: 'a> VecMutScanBackItem<'s, 'a, T> {
+    fn into_inner_forget(self) -> &'s mut VecMutScan<'a, T> {
+        // This is safe, as `VecMutScanBackItem` is annotated with #[repr(transparent)], see
+        // `VecGrowScanItem::into_inner_forget`.
+        unsafe { mem::transmute(self) }
+    }
+
+    /// Removes and returns this item from the vector.
+    ///
+    /// The kept items visited from the back are moved to close the item's place.
+    pub fn remove(self) -> T {
+        let scan = self.into_inner_forget();
+        scan.end -= 1;
+        scan.len -= 1;
+        let result = unsafe {
+            // The item at `end` is valid and moved out, then the items visited from the back,
+            // `end + 1..len + 1`, are moved into its place.
+            let result = ptr::read(scan.base.add(scan.end));
+            ptr::copy(
+                scan.base.add(scan.end + 1),
+                scan.base.add(scan.end),
+                scan.len - scan.end,
+            );
+            result
+        };
+        scan.run_check("removing an item", scan.end);
+        result
+    }
+
+    /// Replaces this item with a new value, returns the old value.
+    ///
+    /// This is equivalent to assigning a new value or calling [`mem::replace`] on the mutable
+    /// reference obtained by using [`DerefMut`], but can avoid an intermediate move within the
+    /// vector's buffer.
+    pub fn replace(self, value: T) -> T {
+        let scan = self.into_inner_forget();
+        scan.end -= 1;
+        // The item at `end` is moved out and `value` is written to the same slot.
+        let result = unsafe { ptr::replace(scan.base.add(scan.end), value) };
+        scan.run_check("replacing an item", scan.end);
+        result
+    }
+}
+
+impl<'s, 'a, T: 'a> Deref for VecMutScanBackItem<'s, 'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // The offset `scan.end - 1` contains valid data owned by the scan.
+        unsafe { &*self.scan.base.add(self.scan.end - 1) }
+    }
+}
+
+impl<'s, 'a, T: 'a> DerefMut for VecMutScanBackItem<'s, 'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // The offset `scan.end - 1` contains valid data owned by the scan.
+        unsafe { &mut *self.scan.base.add(self.scan.end - 1) }
+    }
+}
+
+impl<'s, 'a, T: 'a> Drop for VecMutScanBackItem<'s, 'a, T> {
+    fn drop(&mut self) {
+        let scan = &mut *self.scan;
+        // The item stays in place and becomes the first item visited from the back.
+        scan.end -= 1;
+        scan.run_check("keeping an item", scan.end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec::Vec};
+
+    #[test]
+    fn both_ends() {
+        let mut input: Vec<_> = (0..10).map(Rc::new).collect();
+        let input_copy = input.clone();
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next().unwrap().remove();
+        scan.next();
+        scan.next_back().unwrap().remove();
+        assert_eq!(*scan.next_back().unwrap().replace(Rc::new(18)), 8);
+        scan.next_back();
+        scan.next_back().unwrap().remove();
+        *scan.next_back().unwrap() = Rc::new(15);
+        let values = |v: &[Rc<i32>]| v.iter().map(|x| **x).collect::<Vec<_>>();
+        assert_eq!(values(scan.remaining()), [2, 3, 4]);
+        assert_eq!(values(scan.visited_back()), [15, 7, 18]);
+        assert_eq!(values(scan.slices().1), [2, 3, 4, 15, 7, 18]);
+        assert_eq!(scan.gap_len(), 1);
+        assert_eq!(scan.gap_mut().len(), 1);
+        scan.assert_invariants();
+        assert_eq!(values(&scan.snapshot()), [1, 2, 3, 4, 15, 7, 18]);
+        scan.next().unwrap().remove();
+        drop(scan);
+
+        assert_eq!(values(&input), [1, 3, 4, 15, 7, 18]);
+        drop(input_copy);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+
+        let mut scan = VecMutScan::new(&mut input);
+        while let Some(item) = scan.next_back() {
+            if **item % 2 == 1 {
+                item.remove();
+            }
+        }
+        assert!(scan.next().is_none());
+        drop(scan);
+
+        assert_eq!(values(&input), [4, 18]);
+    }
+}
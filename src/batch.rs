@@ -0,0 +1,170 @@
+use alloc::{vec, vec::Vec};
+use core::{
+    ops::{Deref, DerefMut},
+    ptr, slice,
+};
+
+use crate::VecMutScan;
+
+impl<'a, T: 'a> VecMutScan<'a, T> {
+    /// Advance over the next `n` items at once, or over all remaining items if there are fewer.
+    ///
+    /// This returns a handle that dereferences to a slice of these items, allowing to inspect them
+    /// together and to mark any subset of them for removal (see [`VecMutScanBatch`]). Returns
+    /// `None` if there are no remaining items.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut records: Vec<u32> = (0..10).collect();
+    /// let mut scan = VecMutScan::new(&mut records);
+    ///
+    /// while let Some(mut batch) = scan.next_batch(4) {
+    ///     // E.g. validate all records of the batch in a single request.
+    ///     let invalid: Vec<bool> = batch.iter().map(|record| record % 3 == 0).collect();
+    ///     for (index, &invalid) in invalid.iter().enumerate() {
+    ///         if invalid {
+    ///             batch.remove(index);
+    ///         }
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(records, [1, 2, 4, 5, 7, 8]);
+    /// ```
+    pub fn next_batch<'s>(&'s mut self, n: usize) -> Option<VecMutScanBatch<'s, 'a, T>> {
+        let len = n.min(self.end - self.read);
+        if len == 0 {
+            return None;
+        }
+        Some(VecMutScanBatch {
+            scan: self,
+            removed: vec![false; len],
+        })
+    }
+}
+
+/// Handle over a batch of upcoming items of a [`VecMutScan`], see
+/// [`next_batch`][VecMutScan::next_batch].
+///
+/// Dereferences to a slice of the batch's items. Items are only marked for removal until the
+/// batch is committed, which happens when it is dropped. Committing drops the removed items and
+/// moves each run of kept items over the gap at once, after which the scan continues with the item
+/// following the batch.
+pub struct VecMutScanBatch<'s, 'a, T: 'a> {
+    scan: &'s mut VecMutScan<'a, T>,
+    removed: Vec<bool>,
+}
+
+// The batch consists of the `removed.len()` valid items starting at `scan.read`.
+
+impl<'s, 'a, T: 'a> VecMutScanBatch<'s, 'a, T> {
+    /// Mark the item at `index` within the batch for removal.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) {
+        self.removed[index] = true;
+    }
+
+    /// Unmark the item at `index` within the batch, so that it is kept.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn keep(&mut self, index: usize) {
+        self.removed[index] = false;
+    }
+
+    /// Returns `true` if the item at `index` within the batch is marked for removal.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn is_removed(&self, index: usize) -> bool {
+        self.removed[index]
+    }
+
+    /// Commit the batch, removing all marked items.
+    ///
+    /// This is equivalent to dropping the batch.
+    pub fn commit(self) {}
+}
+
+impl<'s, 'a, T: 'a> Deref for VecMutScanBatch<'s, 'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        // The batch's items are valid and owned by the scan.
+        unsafe { slice::from_raw_parts(self.scan.base.add(self.scan.read), self.removed.len()) }
+    }
+}
+
+impl<'s, 'a, T: 'a> DerefMut for VecMutScanBatch<'s, 'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // The batch's items are valid and owned by the scan.
+        unsafe { slice::from_raw_parts_mut(self.scan.base.add(self.scan.read), self.removed.len()) }
+    }
+}
+
+impl<'s, 'a, T: 'a> Drop for VecMutScanBatch<'s, 'a, T> {
+    fn drop(&mut self) {
+        let scan = &mut *self.scan;
+        let start = scan.read;
+        let mut index = 0;
+        while index < self.removed.len() {
+            let run = self.removed[index..]
+                .iter()
+                .take_while(|&&removed| !removed)
+                .count();
+            unsafe {
+                if run > 0 {
+                    // Move the run of kept items over the gap, see `keep_current`.
+                    ptr::copy(scan.base.add(scan.read), scan.base.add(scan.write), run);
+                    scan.read += run;
+                    scan.write += run;
+                    index += run;
+                } else {
+                    // Take ownership before dropping, so that the scan stays consistent should the
+                    // item's drop panic.
+                    let item = ptr::read(scan.base.add(scan.read));
+                    scan.read += 1;
+                    index += 1;
+                    drop(item);
+                }
+            }
+        }
+        scan.run_check("committing a batch", start);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::rc::Rc;
+
+    #[test]
+    fn batch_removal() {
+        let mut input: Vec<_> = (0..11).map(Rc::new).collect();
+        let input_copy = input.clone();
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next().unwrap().remove();
+        let mut seen = vec![];
+        while let Some(mut batch) = scan.next_batch(4) {
+            assert!(batch.len() == 4 || batch.len() == 2);
+            seen.extend(batch.iter().map(|x| **x));
+            for index in 0..batch.len() {
+                if *batch[index] % 3 != 1 {
+                    batch.remove(index);
+                }
+            }
+            batch.keep(0);
+            assert!(!batch.is_removed(0));
+            batch[0] = Rc::new(*batch[0] * 10);
+        }
+        assert!(scan.next_batch(1).is_none());
+        drop(scan);
+
+        assert_eq!(seen, (1..11).collect::<Vec<_>>());
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [10, 4, 50, 7, 90, 10]);
+        drop(input_copy);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+}
@@ -0,0 +1,191 @@
+use bitvec::{order::BitOrder, ptr::Mut, store::BitStore, vec::BitVec};
+
+/// Bit proxy for a single bit of a [`BitVec`], as returned by [`BitVecScanItem::bit_mut`].
+pub type BitMut<'s, T, O> = bitvec::ptr::BitRef<'s, Mut, T, O>;
+
+/// Forward scan over a [`BitVec`] with mutation and bit removal.
+///
+/// This provides the interface of [`VecMutScan`](crate::VecMutScan) for a `bitvec` bit vector.
+/// Kept bits are moved over the gap left by removed bits, compacting the bits in place, so every
+/// bit is moved at most once. Dropping the scan mid-iteration keeps the remaining bits.
+///
+/// Unlike `VecMutScan`, the bit vector keeps its length during the scan and is only truncated when
+/// the scan is dropped. Should the scan be leaked, the bit vector contains the kept bits followed
+/// by unspecified bits.
+///
+/// Requires the `bitvec` feature, which needs Rust 1.56 or later.
+///
+/// ```
+/// # use vec_mut_scan::BitVecScan;
+/// use bitvec::prelude::*;
+///
+/// // Remove every set bit directly following another set bit.
+/// let mut bits = bitvec![1, 1, 0, 1, 1, 1, 0];
+/// let mut scan = BitVecScan::new(&mut bits);
+/// let mut last = false;
+///
+/// while let Some(item) = scan.next() {
+///     let bit = item.get();
+///     if bit && last {
+///         item.remove();
+///     }
+///     last = bit;
+/// }
+/// drop(scan);
+///
+/// assert_eq!(bits, bits![1, 0, 1, 0]);
+/// ```
+pub struct BitVecScan<'a, T: BitStore, O: BitOrder> {
+    vec: &'a mut BitVec<T, O>,
+    write: usize,
+    read: usize,
+}
+
+// invariant: the kept bits are `0..write`, the remaining bits are `read..vec.len()` and the bits
+// in between are unspecified.
+
+impl<'a, T: BitStore, O: BitOrder> BitVecScan<'a, T, O> {
+    /// Begin a scan over a bit vector with mutation and bit removal.
+    pub fn new(vec: &'a mut BitVec<T, O>) -> Self {
+        BitVecScan {
+            vec,
+            write: 0,
+            read: 0,
+        }
+    }
+
+    /// Advance to the next bit of the bit vector.
+    ///
+    /// This returns a reference wrapper that enables bit removal (see [`BitVecScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<BitVecScanItem<'s, 'a, T, O>> {
+        if self.read != self.vec.len() {
+            Some(BitVecScanItem { scan: self })
+        } else {
+            None
+        }
+    }
+
+    /// The number of bits kept so far.
+    pub fn kept(&self) -> usize {
+        self.write
+    }
+
+    /// The number of bits that were not visited yet.
+    pub fn remaining(&self) -> usize {
+        self.vec.len() - self.read
+    }
+}
+
+impl<'a, T: BitStore, O: BitOrder> Drop for BitVecScan<'a, T, O> {
+    fn drop(&mut self) {
+        let len = self.vec.len();
+        if self.write != self.read {
+            // `copy_within` rejects an empty source range at the end.
+            if self.read != len {
+                self.vec.copy_within(self.read..len, self.write);
+            }
+            self.vec.truncate(self.write + len - self.read);
+        }
+    }
+}
+
+/// Reference wrapper that enables bit removal for [`BitVecScan`].
+///
+/// Dropping the wrapper keeps the bit.
+pub struct BitVecScanItem<'s, 'a, T: BitStore, O: BitOrder> {
+    scan: &'s mut BitVecScan<'a, T, O>,
+}
+
+// When a `BitVecScanItem` is created, there must be a bit at `scan.read`.
+
+impl<'s, 'a, T: BitStore, O: BitOrder> BitVecScanItem<'s, 'a, T, O> {
+    /// The value of this bit.
+    pub fn get(&self) -> bool {
+        self.scan.vec[self.scan.read]
+    }
+
+    /// Set the value of this bit.
+    pub fn set(&mut self, value: bool) {
+        let read = self.scan.read;
+        self.scan.vec.set(read, value);
+    }
+
+    /// A proxy for this bit, which dereferences to a mutable `bool`.
+    pub fn bit_mut(&mut self) -> BitMut<'_, T, O> {
+        let read = self.scan.read;
+        self.scan.vec.get_mut(read).unwrap()
+    }
+
+    /// Removes this bit from the bit vector and returns its value.
+    pub fn remove(self) -> bool {
+        let value = self.get();
+        self.scan.read += 1;
+        // Skip the drop, which would keep the bit.
+        core::mem::forget(self);
+        value
+    }
+
+    /// Replaces the value of this bit, returns the old value.
+    pub fn replace(self, value: bool) -> bool {
+        let read = self.scan.read;
+        self.scan.vec.replace(read, value)
+    }
+}
+
+impl<'s, 'a, T: BitStore, O: BitOrder> Drop for BitVecScanItem<'s, 'a, T, O> {
+    fn drop(&mut self) {
+        let scan = &mut *self.scan;
+        if scan.write != scan.read {
+            let value = scan.vec[scan.read];
+            scan.vec.set(scan.write, value);
+        }
+        scan.read += 1;
+        scan.write += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+    use bitvec::order::Msb0;
+
+    #[test]
+    fn compacts_bits() {
+        let pattern: Vec<bool> = (0..200).map(|i| i * 7 % 5 < 2).collect();
+        let mut bits: BitVec<u8, Msb0> = pattern.iter().copied().collect();
+        let mut expected = Vec::new();
+
+        let mut scan = BitVecScan::new(&mut bits);
+        let mut index = 0;
+        while index < 150 {
+            let mut item = scan.next().unwrap();
+            let bit = item.get();
+            match index % 4 {
+                0 => assert_eq!(item.remove(), bit),
+                1 => {
+                    assert_eq!(item.replace(!bit), bit);
+                    expected.push(!bit);
+                }
+                2 => {
+                    *item.bit_mut() = true;
+                    expected.push(true);
+                }
+                _ => {
+                    item.set(false);
+                    item.set(bit);
+                    expected.push(bit);
+                }
+            }
+            index += 1;
+        }
+        assert_eq!(scan.kept(), expected.len());
+        assert_eq!(scan.remaining(), 50);
+        drop(scan);
+        expected.extend_from_slice(&pattern[150..]);
+
+        assert!(bits.iter().by_vals().eq(expected.iter().copied()));
+    }
+}
@@ -0,0 +1,170 @@
+#[cfg(feature = "try-reserve")]
+use alloc::collections::TryReserveError;
+use alloc::{collections::VecDeque, vec::Vec};
+use bytes::BytesMut;
+
+use crate::{GrowStorage, SetLen, VecGrowScan, VecMutScan};
+
+impl SetLen for BytesMut {
+    unsafe fn set_len(&mut self, len: usize) {
+        BytesMut::set_len(self, len)
+    }
+}
+
+impl GrowStorage<u8> for BytesMut {
+    fn splice_queue(&mut self, index: usize, queue: &mut VecDeque<u8>) {
+        let len = self.len();
+        let (front, back) = queue.as_slices();
+        self.resize(len + queue.len(), 0);
+        self.copy_within(index..len, index + queue.len());
+        self[index..index + front.len()].copy_from_slice(front);
+        self[index + front.len()..index + queue.len()].copy_from_slice(back);
+        queue.clear();
+    }
+
+    fn append(&mut self, tail: &mut Vec<u8>) {
+        self.extend_from_slice(tail);
+        tail.clear();
+    }
+
+    fn capacity(&self) -> usize {
+        BytesMut::capacity(self)
+    }
+
+    unsafe fn reserve_items(&mut self, len: usize, additional: usize) -> *mut u8 {
+        // Initializing the additional bytes makes them part of the slice the returned pointer is
+        // derived from.
+        self.set_len(len);
+        self.resize(len + additional, 0);
+        let base = self.as_mut_ptr();
+        self.set_len(0);
+        base
+    }
+
+    #[cfg(feature = "try-reserve")]
+    #[allow(clippy::incompatible_msrv)]
+    unsafe fn try_reserve_items(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<*mut u8, TryReserveError> {
+        // `BytesMut` has no fallible way to reserve capacity.
+        Ok(self.reserve_items(len, additional))
+    }
+}
+
+impl<'a> VecMutScan<'a, u8> {
+    /// Begin a scan over a [`BytesMut`] with mutation and byte removal.
+    ///
+    /// This works in place, within the part of the buffer the `BytesMut` has a view of. No bytes
+    /// are copied into a separate vector and buffers shared with other `BytesMut` or `Bytes` handles
+    /// are left untouched.
+    ///
+    /// Requires the `bytes` feature.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// use bytes::BytesMut;
+    ///
+    /// let mut buf = BytesMut::from(&b"a-b-c"[..]);
+    /// let mut scan = VecMutScan::from_bytes_mut(&mut buf);
+    /// while let Some(byte) = scan.next() {
+    ///     if *byte == b'-' {
+    ///         byte.remove();
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(&buf[..], b"abc");
+    /// ```
+    pub fn from_bytes_mut(bytes: &'a mut BytesMut) -> Self {
+        let base = bytes.as_mut_ptr();
+        let end = bytes.len();
+
+        // The bytes `0..end` are initialized and owned by `bytes`.
+        unsafe { VecMutScan::from_raw_parts(bytes, base, 0, end) }
+    }
+}
+
+impl<'a> VecGrowScan<'a, u8> {
+    /// Begin a scan over a [`BytesMut`] with mutation, insertion and byte removal.
+    ///
+    /// Like [`VecMutScan::from_bytes_mut`], this works in place. When bytes are inserted that do not
+    /// fit into the gap left by removed bytes, the `BytesMut` reserves additional capacity once the
+    /// scan is dropped, following the usual `BytesMut` reallocation rules.
+    ///
+    /// Requires the `bytes` feature.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// use bytes::BytesMut;
+    ///
+    /// let mut buf = BytesMut::from(&b"a\nb\n"[..]);
+    /// let mut scan = VecGrowScan::from_bytes_mut(&mut buf);
+    /// while let Some(mut byte) = scan.next() {
+    ///     if *byte == b'\n' {
+    ///         byte.insert_many_before(b"\r".iter().copied());
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(&buf[..], b"a\r\nb\r\n");
+    /// ```
+    pub fn from_bytes_mut(bytes: &'a mut BytesMut) -> Self {
+        let base = bytes.as_mut_ptr();
+        let end = bytes.len();
+
+        // The bytes `0..end` are initialized and owned by `bytes`.
+        unsafe { VecGrowScan::from_raw_parts(bytes, base, 0, end) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::VecGrowScan;
+    use alloc::vec::Vec;
+    use bytes::BytesMut;
+
+    #[test]
+    fn split_buffer() {
+        let mut buf = BytesMut::from(&b"0123456789"[..]);
+        let mut back = buf.split_off(5);
+        let front = buf.split_to(2);
+
+        let mut scan = VecGrowScan::from_bytes_mut(&mut back);
+        while let Some(byte) = scan.next() {
+            match *byte {
+                b'6' => drop(byte.remove()),
+                b'8' => byte.insert_many_after(b"xyz".iter().copied()),
+                _ => (),
+            }
+        }
+        drop(scan);
+
+        let mut scan = VecGrowScan::from_bytes_mut(&mut buf);
+        scan.next()
+            .unwrap()
+            .insert_many_before((0..100).map(|_| b'_'));
+        drop(scan);
+
+        assert_eq!(&front[..], b"01");
+        assert_eq!(buf.len(), 103);
+        assert_eq!(&buf[100..], b"234");
+        assert_eq!(&back[..], b"578xyz9");
+
+        buf.unsplit(back);
+        let expected: Vec<u8> = (0..100)
+            .map(|_| b'_')
+            .chain(b"234578xyz9".iter().copied())
+            .collect();
+        assert_eq!(&buf[..], &expected[..]);
+
+        let mut scan = VecGrowScan::from_bytes_mut(&mut buf);
+        scan.insert_next(b'<');
+        assert_eq!(*scan.next().unwrap(), b'<');
+        scan.next().unwrap().insert_visit_after(b'>');
+        drop(scan);
+        assert_eq!(&buf[..3], b"<_>");
+        assert_eq!(&buf[102..], b"234578xyz9");
+    }
+}
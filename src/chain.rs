@@ -0,0 +1,262 @@
+use alloc::vec::Vec;
+use core::{
+    mem,
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+use crate::{VecMutScan, VecMutScanItem};
+
+/// Forward scan over a sequence of vectors with mutation, removal and moving items between the
+/// vectors.
+///
+/// The vectors are visited one after another, as if they were a single sequence. Like with
+/// [`VecMutScan`], every kept item is moved at most once. In addition, the current item can be
+/// moved to the end of any of the vectors, e.g. to demote it to an overflow list. Items moved to a
+/// vector that was already scanned are pushed directly. Items moved to the vector being scanned or
+/// to a later vector are held back until that vector was scanned, so moved items are never
+/// visited again.
+///
+/// Dropping the `ChainScan` mid-iteration keeps the remaining items and appends all held back
+/// items to their vectors.
+///
+/// ```
+/// # use vec_mut_scan::ChainScan;
+/// // Demote entries with few hits to the cold tier and promote the others.
+/// let mut hot = vec![("a", 9), ("b", 1)];
+/// let mut cold = vec![("c", 7), ("d", 0)];
+///
+/// let mut scan = ChainScan::new(vec![&mut hot, &mut cold]);
+/// while let Some(entry) = scan.next() {
+///     let tier = if entry.1 >= 5 { 0 } else { 1 };
+///     if entry.vector_index() != tier {
+///         entry.move_to(tier);
+///     }
+/// }
+/// drop(scan);
+///
+/// assert_eq!(hot, [("a", 9), ("c", 7)]);
+/// assert_eq!(cold, [("d", 0), ("b", 1)]);
+/// ```
+pub struct ChainScan<'a, T: 'a> {
+    vecs: Vec<&'a mut Vec<T>>,
+    // Items moved to the vector being scanned or to a later vector, per vector.
+    held: Vec<Vec<T>>,
+    // Scan over `vecs[started - 1]`, if it wasn't finished yet.
+    scan: Option<VecMutScan<'a, T>>,
+    started: usize,
+}
+
+impl<'a, T: 'a> ChainScan<'a, T> {
+    /// Begin a scan over a sequence of vectors with mutation, removal and moving items between the
+    /// vectors.
+    pub fn new(vecs: impl IntoIterator<Item = &'a mut Vec<T>>) -> Self {
+        let vecs: Vec<_> = vecs.into_iter().collect();
+        let held = vecs.iter().map(|_| Vec::new()).collect();
+        ChainScan {
+            vecs,
+            held,
+            scan: None,
+            started: 0,
+        }
+    }
+
+    /// Advance to the next item, continuing with the following vector when the current vector was
+    /// scanned completely.
+    ///
+    /// This returns a reference wrapper that enables item removal and moving items (see
+    /// [`ChainScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<ChainScanItem<'s, 'a, T>> {
+        loop {
+            match &self.scan {
+                Some(scan) if scan.read != scan.end => break,
+                _ => {
+                    if !self.start_next_vector() {
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(ChainScanItem { chain: self })
+    }
+
+    /// The index of the vector being scanned, if the scan started.
+    ///
+    /// Once all vectors were scanned, this is the index of the last vector.
+    pub fn vector_index(&self) -> Option<usize> {
+        self.started.checked_sub(1)
+    }
+
+    /// Finish the current vector, if any, and begin scanning the next one.
+    ///
+    /// Returns `false` if there is no next vector.
+    fn start_next_vector(&mut self) -> bool {
+        if let Some(scan) = self.scan.take() {
+            drop(scan);
+            let index = self.started - 1;
+            self.vecs[index].append(&mut self.held[index]);
+        }
+        if self.started == self.vecs.len() {
+            return false;
+        }
+        let vec: *mut Vec<T> = &mut *self.vecs[self.started];
+        // The scan is the only user of the vector until it is dropped in `start_next_vector` or
+        // in `drop`. The vector itself stays in place, as only the reference to it is stored in
+        // `vecs`.
+        self.scan = Some(VecMutScan::new(unsafe { &mut *vec }));
+        self.started += 1;
+        true
+    }
+
+    /// Move an item to the end of the vector at `target`.
+    fn place(&mut self, target: usize, item: T) {
+        if target + 1 < self.started {
+            self.vecs[target].push(item);
+        } else {
+            self.held[target].push(item);
+        }
+    }
+}
+
+impl<'a, T: 'a> Drop for ChainScan<'a, T> {
+    fn drop(&mut self) {
+        drop(self.scan.take());
+        for (vec, held) in self.vecs.iter_mut().zip(self.held.iter_mut()) {
+            vec.append(held);
+        }
+    }
+}
+
+/// Reference wrapper that enables item removal and moving items for [`ChainScan`].
+///
+/// Dropping the wrapper keeps the item.
+pub struct ChainScanItem<'s, 'a, T: 'a> {
+    chain: &'s mut ChainScan<'a, T>,
+}
+
+// When a `ChainScanItem` is created, `chain.scan` must be present and have remaining items.
+
+impl<'s, 'a, T: 'a> ChainScanItem<'s, 'a, T> {
+    fn into_inner_forget(self) -> &'s mut ChainScan<'a, T> {
+        let item = mem::ManuallyDrop::new(self);
+        // The reference is moved out of `item`, which is never used or dropped afterwards.
+        unsafe { ptr::read(&item.chain) }
+    }
+
+    fn scan(&self) -> &VecMutScan<'a, T> {
+        self.chain.scan.as_ref().unwrap()
+    }
+
+    /// The index of the vector containing this item.
+    pub fn vector_index(&self) -> usize {
+        self.chain.started - 1
+    }
+
+    /// Removes and returns this item.
+    pub fn remove(self) -> T {
+        let chain = self.into_inner_forget();
+        let scan = chain.scan.as_mut().unwrap();
+        VecMutScanItem { scan }.remove()
+    }
+
+    /// Replaces this item with a new value, returns the old value.
+    pub fn replace(mut self, value: T) -> T {
+        mem::replace(&mut *self, value)
+    }
+
+    /// Moves this item to the end of the vector at index `target`.
+    ///
+    /// The item is not visited again, even if it is moved to the current or a later vector.
+    ///
+    /// Panics if `target` is out of bounds.
+    pub fn move_to(self, target: usize) {
+        assert!(
+            target < self.chain.vecs.len(),
+            "target vector index out of bounds"
+        );
+        let chain = self.into_inner_forget();
+        let item = VecMutScanItem {
+            scan: chain.scan.as_mut().unwrap(),
+        }
+        .remove();
+        chain.place(target, item);
+    }
+}
+
+impl<'s, 'a, T: 'a> Deref for ChainScanItem<'s, 'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        let scan = self.scan();
+        // The offset `scan.read` contains valid data owned by the scan.
+        unsafe { &*scan.base.add(scan.read) }
+    }
+}
+
+impl<'s, 'a, T: 'a> DerefMut for ChainScanItem<'s, 'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        let scan = self.chain.scan.as_mut().unwrap();
+        // The offset `scan.read` contains valid data owned by the scan.
+        unsafe { &mut *scan.base.add(scan.read) }
+    }
+}
+
+impl<'s, 'a, T: 'a> Drop for ChainScanItem<'s, 'a, T> {
+    fn drop(&mut self) {
+        let scan = self.chain.scan.as_mut().unwrap();
+        drop(VecMutScanItem { scan });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec};
+
+    #[test]
+    fn move_between_vectors() {
+        let mut a: Vec<_> = (0..6).map(Rc::new).collect();
+        let mut b: Vec<_> = (10..16).map(Rc::new).collect();
+        let mut c: Vec<_> = (20..23).map(Rc::new).collect();
+        let copies: Vec<_> = a.iter().chain(&b).chain(&c).cloned().collect();
+
+        let mut scan = ChainScan::new(vec![&mut a, &mut b, &mut c]);
+        assert_eq!(scan.vector_index(), None);
+        let mut seen = vec![];
+        loop {
+            let mut item = match scan.next() {
+                Some(item) => item,
+                None => break,
+            };
+            let value = **item;
+            let vector_index = item.vector_index();
+            seen.push(value);
+            match value % 5 {
+                0 => drop(item.remove()),
+                1 => item.move_to(2),
+                2 => item.move_to(0),
+                3 => item.move_to(vector_index),
+                _ => *item = Rc::new(value + 100),
+            }
+            if value == 14 {
+                break;
+            }
+        }
+        assert_eq!(scan.vector_index(), Some(1));
+        drop(scan);
+
+        assert_eq!(seen, [0, 1, 2, 3, 4, 5, 10, 11, 12, 13, 14]);
+        let values = |v: &Vec<Rc<i32>>| v.iter().map(|x| **x).collect::<Vec<_>>();
+        assert_eq!(values(&a), [104, 2, 3, 12]);
+        assert_eq!(values(&b), [114, 15, 13]);
+        assert_eq!(values(&c), [20, 21, 22, 1, 11]);
+        drop(copies);
+        assert!(a
+            .iter()
+            .chain(&b)
+            .chain(&c)
+            .all(|x| Rc::strong_count(x) == 1));
+    }
+}
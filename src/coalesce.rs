@@ -0,0 +1,91 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::VecMutScan;
+
+/// Merges consecutive items using `merge`, removing the items that were merged.
+///
+/// The closure is passed the current item and the last kept item. It can merge the current item
+/// into the last kept item and return `true`, in which case the current item is removed. Otherwise
+/// it returns `false` and the current item is kept. This works like [`Vec::dedup_by`], but is
+/// intended for merging the removed items into the kept ones.
+///
+/// ```
+/// # use vec_mut_scan::coalesce;
+/// // Merge runs of text chunks with the same style.
+/// let mut chunks: Vec<_> = vec![("bold", "a"), ("bold", "b"), ("plain", "c")]
+///     .into_iter()
+///     .map(|(style, text)| (style, String::from(text)))
+///     .collect();
+/// coalesce(&mut chunks, |current, last| {
+///     if current.0 == last.0 {
+///         last.1.push_str(&current.1);
+///         true
+///     } else {
+///         false
+///     }
+/// });
+///
+/// assert_eq!(chunks, [("bold", String::from("ab")), ("plain", String::from("c"))]);
+/// ```
+pub fn coalesce<T>(vec: &mut Vec<T>, mut merge: impl FnMut(&mut T, &mut T) -> bool) {
+    let mut scan = VecMutScan::new(vec);
+    while let Some(mut item) = scan.next() {
+        let (visited, remaining) = item.slices_mut();
+        let merged = match visited.last_mut() {
+            Some(last) => merge(&mut remaining[0], last),
+            None => false,
+        };
+        if merged {
+            item.remove();
+        }
+    }
+}
+
+/// Merges overlapping and adjacent ranges of a vector sorted by the ranges' start.
+///
+/// Ranges are merged when a range starts before or at the end of the preceding merged range. Empty
+/// ranges are treated like any other range, so they are merged into a preceding range that
+/// contains or ends at their start.
+///
+/// ```
+/// # use vec_mut_scan::coalesce_ranges;
+/// let mut ranges = vec![0..2, 1..4, 4..5, 7..9, 8..8, 10..12];
+/// coalesce_ranges(&mut ranges);
+///
+/// assert_eq!(ranges, [0..5, 7..9, 10..12]);
+/// ```
+pub fn coalesce_ranges<T: Ord + Clone>(vec: &mut Vec<Range<T>>) {
+    coalesce(vec, |current, last| {
+        if current.start <= last.end {
+            if current.end > last.end {
+                last.end = current.end.clone();
+            }
+            true
+        } else {
+            false
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn ranges() {
+        let mut input = vec![3..5, 4..4, 5..7, 6..7, 7..7, 9..10, 10..20, 12..15, 21..22];
+        coalesce_ranges(&mut input);
+        assert_eq!(input, [3..7, 9..20, 21..22]);
+
+        let mut input: Vec<Range<i32>> = vec![];
+        coalesce_ranges(&mut input);
+        assert!(input.is_empty());
+
+        let mut input = vec![1..1, 2..2, 2..3];
+        coalesce_ranges(&mut input);
+        assert_eq!(input, [1..1, 2..3]);
+    }
+}
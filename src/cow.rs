@@ -0,0 +1,362 @@
+use alloc::{borrow::Cow, rc::Rc, sync::Arc, vec::Vec};
+use core::{mem, ops::Deref};
+
+use crate::{VecMutScan, VecMutScanItem};
+
+/// A clone-on-write container of a sequence of items.
+///
+/// This is implemented for [`Cow<[T]>`](Cow), where the slice might be borrowed, as well as for
+/// `Arc<Vec<T>>` and `Rc<Vec<T>>`, where the vector might be shared. In all cases,
+/// [`to_mut`](CloneOnWrite::to_mut) clones the items when necessary, i.e. when the items are
+/// borrowed or shared.
+pub trait CloneOnWrite {
+    /// The type of the contained items.
+    type Item: Clone;
+
+    /// Access the contained items.
+    fn as_slice(&self) -> &[Self::Item];
+
+    /// Returns `true` if [`to_mut`](CloneOnWrite::to_mut) can return the items without cloning.
+    fn is_owned(&mut self) -> bool;
+
+    /// Mutable access to the items, cloning them if necessary.
+    fn to_mut(&mut self) -> &mut Vec<Self::Item>;
+}
+
+impl<'b, T: Clone> CloneOnWrite for Cow<'b, [T]> {
+    type Item = T;
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn is_owned(&mut self) -> bool {
+        match self {
+            Cow::Borrowed(_) => false,
+            Cow::Owned(_) => true,
+        }
+    }
+
+    fn to_mut(&mut self) -> &mut Vec<T> {
+        Cow::to_mut(self)
+    }
+}
+
+/// Uses [`Arc::make_mut`], so the vector is cloned unless this is the only reference to it.
+impl<T: Clone> CloneOnWrite for Arc<Vec<T>> {
+    type Item = T;
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn is_owned(&mut self) -> bool {
+        Arc::get_mut(self).is_some()
+    }
+
+    fn to_mut(&mut self) -> &mut Vec<T> {
+        Arc::make_mut(self)
+    }
+}
+
+/// Uses [`Rc::make_mut`], so the vector is cloned unless this is the only reference to it.
+impl<T: Clone> CloneOnWrite for Rc<Vec<T>> {
+    type Item = T;
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn is_owned(&mut self) -> bool {
+        Rc::get_mut(self).is_some()
+    }
+
+    fn to_mut(&mut self) -> &mut Vec<T> {
+        Rc::make_mut(self)
+    }
+}
+
+/// Forward scan over a clone-on-write container with mutation and item removal.
+///
+/// This provides the same interface as [`VecMutScan`], but for a container implementing
+/// [`CloneOnWrite`], e.g. a [`Cow<[T]>`](Cow) or an `Arc<Vec<T>>`. As long as all items are kept
+/// unchanged, the items are not cloned. Only when an item is first mutated, removed or replaced,
+/// the container's items are cloned. From then on the scan proceeds like a `VecMutScan` over the
+/// cloned items. If the container is not borrowed or shared to begin with, no clone is necessary.
+///
+/// ```
+/// # use vec_mut_scan::CowScan;
+/// # use std::borrow::Cow;
+/// let numbers = vec![1, 2, 3];
+///
+/// let mut unchanged = Cow::from(&numbers[..]);
+/// let mut scan = CowScan::new(&mut unchanged);
+/// while let Some(item) = scan.next() {
+///     if *item > 3 {
+///         item.remove();
+///     }
+/// }
+/// drop(scan);
+/// // Nothing was changed, so no clone was made.
+/// assert!(match unchanged {
+///     Cow::Borrowed(_) => true,
+///     Cow::Owned(_) => false,
+/// });
+///
+/// let mut changed = Cow::from(&numbers[..]);
+/// let mut scan = CowScan::new(&mut changed);
+/// while let Some(item) = scan.next() {
+///     if *item > 2 {
+///         item.remove();
+///     }
+/// }
+/// drop(scan);
+/// assert_eq!(changed, Cow::<[i32]>::Owned(vec![1, 2]));
+/// ```
+///
+/// With an `Arc<Vec<T>>`, the items are edited in place when there are no other references to
+/// them:
+///
+/// ```
+/// # use vec_mut_scan::CowScan;
+/// # use std::sync::Arc;
+/// let mut numbers = Arc::new(vec![1, 2, 3]);
+/// let snapshot = numbers.clone();
+///
+/// let mut scan = CowScan::new(&mut numbers);
+/// scan.next().unwrap().remove();
+/// drop(scan);
+///
+/// assert_eq!(*numbers, [2, 3]);
+/// assert_eq!(*snapshot, [1, 2, 3]);
+/// ```
+pub struct CowScan<'a, C: CloneOnWrite + ?Sized> {
+    state: State<'a, C>,
+}
+
+enum State<'a, C: CloneOnWrite + ?Sized> {
+    /// All items before the given position were kept unchanged.
+    Borrowed(&'a mut C, usize),
+    Owned(VecMutScan<'a, C::Item>),
+    /// Only present while switching from `Borrowed` to `Owned` or after cloning panicked.
+    Cloning,
+}
+
+impl<'a, C: CloneOnWrite + ?Sized> CowScan<'a, C> {
+    /// Begin a scan over a clone-on-write container with mutation and item removal.
+    pub fn new(container: &'a mut C) -> Self {
+        let state = if container.is_owned() {
+            State::Owned(VecMutScan::new(container.to_mut()))
+        } else {
+            State::Borrowed(container, 0)
+        };
+        CowScan { state }
+    }
+
+    /// Advance to the next item.
+    ///
+    /// This returns a reference wrapper that enables item removal (see [`CowScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<CowScanItem<'s, 'a, C>> {
+        let has_next = match &self.state {
+            State::Borrowed(container, pos) => *pos < container.as_slice().len(),
+            State::Owned(scan) => scan.read != scan.end,
+            State::Cloning => false,
+        };
+        if has_next {
+            Some(CowScanItem { scan: self })
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the items were cloned or did not need to be cloned.
+    pub fn is_owned(&self) -> bool {
+        match self.state {
+            State::Owned(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Access all items.
+    ///
+    /// This returns a prefix of all items already visited and a suffix of the remaining items, like
+    /// [`VecMutScan::slices`].
+    pub fn slices(&self) -> (&[C::Item], &[C::Item]) {
+        match &self.state {
+            State::Borrowed(container, pos) => container.as_slice().split_at(*pos),
+            State::Owned(scan) => scan.slices(),
+            State::Cloning => (&[], &[]),
+        }
+    }
+
+    /// Make sure the items are owned, cloning them if necessary, and return the scan over them.
+    fn owned_scan(&mut self) -> &mut VecMutScan<'a, C::Item> {
+        if let State::Borrowed(..) = self.state {
+            if let State::Borrowed(container, pos) = mem::replace(&mut self.state, State::Cloning) {
+                // The items before `pos` are unchanged and thus already in their final position.
+                self.state = State::Owned(VecMutScan::start_at(container.to_mut(), pos));
+            }
+        }
+        match &mut self.state {
+            State::Owned(scan) => scan,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Reference wrapper that enables item removal for [`CowScan`].
+///
+/// Unlike [`VecMutScanItem`], this does not implement [`DerefMut`](core::ops::DerefMut), as
+/// mutable access might require cloning the items. Use [`to_mut`](CowScanItem::to_mut) instead.
+#[repr(transparent)]
+pub struct CowScanItem<'s, 'a, C: CloneOnWrite + ?Sized> {
+    scan: &'s mut CowScan<'a, C>,
+}
+
+// When a `CowScanItem` is created, there must be a current item, i.e. a `VecMutScanItem` could be
+// created for the owned items.
+
+impl<'s, 'a, C: CloneOnWrite + ?Sized> CowScanItem<'s, 'a, C> {
+    fn into_inner_forget(self) -> &'s mut CowScan<'a, C> {
+        // See `VecGrowScanItem::into_inner_forget`.
+        unsafe {
+            // This is safe, as `CowScanItem` is annotated with #[repr(transparent)]
+            mem::transmute(self)
+        }
+    }
+
+    /// Turn this into an item of the owned scan, cloning the items if necessary.
+    fn into_owned_item(self) -> VecMutScanItem<'s, 'a, C::Item> {
+        let scan = self.into_inner_forget().owned_scan();
+        VecMutScanItem { scan }
+    }
+
+    /// Removes and returns this item, cloning the items if necessary.
+    pub fn remove(self) -> C::Item {
+        self.into_owned_item().remove()
+    }
+
+    /// Replaces this item with a new value, returns the old value, cloning the items if necessary.
+    pub fn replace(self, value: C::Item) -> C::Item {
+        self.into_owned_item().replace(value)
+    }
+
+    /// Mutable access to this item, cloning the items if necessary.
+    pub fn to_mut(&mut self) -> &mut C::Item {
+        let scan = self.scan.owned_scan();
+        // As in `VecMutScanItem::deref_mut`, `scan.read` contains valid data owned by the scan.
+        unsafe { &mut *scan.base.add(scan.read) }
+    }
+
+    /// Access all items.
+    ///
+    /// See [`CowScan::slices`].
+    pub fn slices(&self) -> (&[C::Item], &[C::Item]) {
+        self.scan.slices()
+    }
+}
+
+impl<'s, 'a, C: CloneOnWrite + ?Sized> Deref for CowScanItem<'s, 'a, C> {
+    type Target = C::Item;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.scan.state {
+            State::Borrowed(container, pos) => &container.as_slice()[*pos],
+            // As in `VecMutScanItem::deref`, `scan.read` contains valid data owned by the scan.
+            State::Owned(scan) => unsafe { &*scan.base.add(scan.read) },
+            State::Cloning => unreachable!(),
+        }
+    }
+}
+
+impl<'s, 'a, C: CloneOnWrite + ?Sized> Drop for CowScanItem<'s, 'a, C> {
+    fn drop(&mut self) {
+        match &mut self.scan.state {
+            State::Borrowed(_, pos) => *pos += 1,
+            State::Owned(scan) => unsafe { scan.keep_current() },
+            State::Cloning => (),
+        }
+    }
+}
+
+impl<'a, C: CloneOnWrite + ?Sized> From<&'a mut C> for CowScan<'a, C> {
+    fn from(container: &'a mut C) -> Self {
+        CowScan::new(container)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn clone_on_first_change() {
+        let input: Vec<_> = (0..6).map(Rc::new).collect();
+        let mut cow = Cow::from(&input[..]);
+        let mut scan = CowScan::new(&mut cow);
+
+        for i in 0..3 {
+            assert_eq!(scan.slices().0.len(), i);
+            assert_eq!(**scan.next().unwrap(), i);
+        }
+        assert!(!scan.is_owned());
+
+        let mut item = scan.next().unwrap();
+        *item.to_mut() = Rc::new(30);
+        drop(item);
+        assert!(scan.is_owned());
+
+        scan.next().unwrap().remove();
+        scan.next().unwrap().replace(Rc::new(50));
+        assert!(scan.next().is_none());
+        drop(scan);
+
+        let values: Vec<_> = cow.iter().map(|rc| **rc).collect();
+        assert_eq!(values, [0, 1, 2, 30, 50]);
+
+        let ref_counts: Vec<_> = input.iter().map(Rc::strong_count).collect();
+        assert_eq!(ref_counts, [2, 2, 2, 1, 1, 1]);
+    }
+
+    #[test]
+    fn already_owned() {
+        let mut cow: Cow<[i32]> = Cow::Owned(vec![1, 2, 3]);
+        let mut scan = CowScan::new(&mut cow);
+        assert!(scan.is_owned());
+
+        scan.next().unwrap().remove();
+        drop(scan);
+
+        assert_eq!(&cow[..], [2, 3]);
+    }
+
+    #[test]
+    fn shared_arc() {
+        let mut arc = Arc::new(vec![1, 2, 3, 4]);
+        let shared = arc.clone();
+
+        let mut scan = CowScan::new(&mut arc);
+        scan.next();
+        scan.next();
+        assert!(!scan.is_owned());
+        scan.next().unwrap().remove();
+        assert!(scan.is_owned());
+        drop(scan);
+
+        assert_eq!(*arc, [1, 2, 4]);
+        assert_eq!(*shared, [1, 2, 3, 4]);
+
+        drop(shared);
+        let ptr = arc.as_ptr();
+        let mut scan = CowScan::new(&mut arc);
+        assert!(scan.is_owned());
+        scan.next().unwrap().remove();
+        drop(scan);
+
+        assert_eq!(*arc, [2, 4]);
+        assert_eq!(arc.as_ptr(), ptr);
+    }
+}
@@ -0,0 +1,160 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use core::mem;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+#[cfg(feature = "std")]
+use crate::Sink;
+use crate::VecMutScan;
+
+/// Removes consecutive repeated items, keeping the last item of each run.
+///
+/// Unlike [`Vec::dedup`], which keeps the first item of each run of equal items, this keeps the
+/// last one. This is useful when later items supersede earlier ones.
+///
+/// ```
+/// # use vec_mut_scan::dedup_keep_last;
+/// let mut ids = vec![1, 1, 2, 1, 1];
+/// dedup_keep_last(&mut ids);
+///
+/// assert_eq!(ids, [1, 2, 1]);
+/// ```
+pub fn dedup_keep_last<T: PartialEq>(vec: &mut Vec<T>) {
+    dedup_by_keep_last(vec, |a, b| a == b)
+}
+
+/// Removes consecutive items considered equal by `same_run`, keeping the last item of each run.
+///
+/// The closure is passed the current item and the last kept item, which is the latest item of the
+/// preceding run. If it returns `true`, the current item replaces that item.
+///
+/// ```
+/// # use vec_mut_scan::dedup_by_keep_last;
+/// let mut events = vec![(1, "a"), (1, "b"), (2, "c"), (1, "d"), (1, "e")];
+/// dedup_by_keep_last(&mut events, |a, b| a.0 == b.0);
+///
+/// assert_eq!(events, [(1, "b"), (2, "c"), (1, "e")]);
+/// ```
+pub fn dedup_by_keep_last<T>(vec: &mut Vec<T>, mut same_run: impl FnMut(&T, &T) -> bool) {
+    let mut scan = VecMutScan::new(vec);
+    while let Some(mut item) = scan.next() {
+        let superseded = match item.slices().0.last() {
+            Some(last) => same_run(&item, last),
+            None => false,
+        };
+        if superseded {
+            // The previous item of the run is the last kept one, so instead of deferring the
+            // decision to keep it, we move the current item into its place and remove it.
+            let (visited, remaining) = item.slices_mut();
+            mem::swap(visited.last_mut().unwrap(), &mut remaining[0]);
+            item.remove();
+        }
+    }
+}
+
+/// Removes all items with a key equal to that of an earlier item, keeping the first occurrence.
+///
+/// Unlike [`Vec::dedup_by_key`], this does not only remove consecutive duplicates. The keys of all
+/// kept items are tracked in a [`HashSet`](std::collections::HashSet), so only a single scan is
+/// needed and every item is moved at most once.
+///
+/// Requires the `std` feature.
+///
+/// ```
+/// # use vec_mut_scan::dedup_unsorted;
+/// let mut words = vec!["apple", "Banana", "APPLE", "cherry", "banana"];
+/// dedup_unsorted(&mut words, |word| word.to_lowercase());
+///
+/// assert_eq!(words, ["apple", "Banana", "cherry"]);
+/// ```
+#[cfg(feature = "std")]
+pub fn dedup_unsorted<T, K: Hash + Eq>(vec: &mut Vec<T>, key: impl FnMut(&T) -> K) {
+    dedup_unsorted_into(vec, key, &mut |_: (K, T)| ());
+}
+
+/// Removes all items with a key equal to that of an earlier item, passing them to `sink`.
+///
+/// This works like [`dedup_unsorted`], but instead of dropping the removed duplicates, each one is
+/// passed to the sink in order, together with its key, which matches the key of an earlier kept
+/// item.
+///
+/// Requires the `std` feature.
+///
+/// ```
+/// # use vec_mut_scan::dedup_unsorted_into;
+/// let mut records = vec![(1, "a"), (2, "b"), (1, "c"), (3, "d"), (2, "e")];
+/// let mut discarded = vec![];
+/// dedup_unsorted_into(&mut records, |record| record.0, &mut discarded);
+///
+/// assert_eq!(records, [(1, "a"), (2, "b"), (3, "d")]);
+/// assert_eq!(discarded, [(1, (1, "c")), (2, (2, "e"))]);
+/// ```
+#[cfg(feature = "std")]
+pub fn dedup_unsorted_into<T, K: Hash + Eq, S: Sink<(K, T)> + ?Sized>(
+    vec: &mut Vec<T>,
+    mut key: impl FnMut(&T) -> K,
+    sink: &mut S,
+) {
+    let mut seen = HashSet::new();
+    let mut scan = VecMutScan::new(vec);
+    while let Some(item) = scan.next() {
+        let item_key = key(&item);
+        if seen.contains(&item_key) {
+            sink.accept((item_key, item.remove()));
+        } else {
+            seen.insert(item_key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec};
+
+    #[test]
+    fn keep_last() {
+        let items: Vec<_> = [1, 1, 2, 3, 3, 3, 1, 2, 2]
+            .iter()
+            .enumerate()
+            .map(|(index, &key)| Rc::new((key, index)))
+            .collect();
+        let mut input = items.clone();
+
+        dedup_by_keep_last(&mut input, |a, b| a.0 == b.0);
+
+        let output: Vec<_> = input.iter().map(|item| **item).collect();
+        assert_eq!(output, [(1, 1), (2, 2), (3, 5), (1, 6), (2, 8)]);
+        drop(input);
+        assert!(items.iter().all(|item| Rc::strong_count(item) == 1));
+
+        let mut empty: Vec<i32> = vec![];
+        dedup_keep_last(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn unsorted() {
+        let mut input: Vec<_> = (0..20).map(|x| x * 7 % 10).collect();
+
+        dedup_unsorted(&mut input, |&x| x / 2);
+
+        assert_eq!(input, [0, 7, 4, 8, 2]);
+
+        let mut input: Vec<_> = (0..10).collect();
+        let mut removed = vec![];
+        dedup_unsorted_into(&mut input, |&x| x % 3, &mut |(key, x)| {
+            removed.push((key, x))
+        });
+
+        assert_eq!(input, [0, 1, 2]);
+        assert_eq!(
+            removed,
+            [(0, 3), (1, 4), (2, 5), (0, 6), (1, 7), (2, 8), (0, 9)]
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{
+    cmp::min,
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut},
+    ptr, slice,
+};
+
+/// Forward scan over a [`VecDeque`] with mutation and item removal.
+///
+/// This works like [`VecMutScan`][crate::VecMutScan], visiting the items from the front to the
+/// back. Items are kept in order and every item is moved at most once by the scan, even when items
+/// are removed.
+///
+/// The scan works on the deque's buffer in place, following the ring buffer's wrap-around. When
+/// items were removed, their vacated slots end up at the back of the deque and are split off with
+/// [`VecDeque::split_off`] when the scan is dropped, which allocates a buffer for those slots.
+///
+/// Dropping the `VecDequeMutScan` mid-iteration keeps the remaining items in the deque.
+///
+/// ```
+/// # use vec_mut_scan::VecDequeMutScan;
+/// # use std::collections::VecDeque;
+/// let mut jobs: VecDeque<_> = vec![1, 2, 3, 4].into();
+/// jobs.push_front(0);
+///
+/// // Cancel the odd jobs.
+/// let mut scan = VecDequeMutScan::new(&mut jobs);
+/// while let Some(job) = scan.next() {
+///     if *job % 2 == 1 {
+///         job.remove();
+///     }
+/// }
+/// drop(scan);
+///
+/// assert_eq!(jobs, [0, 2, 4]);
+/// ```
+pub struct VecDequeMutScan<'a, T: 'a> {
+    deque: &'a mut VecDeque<T>,
+    // The deque's items, which are moved back into `deque` when the scan is dropped. Should the
+    // scan be leaked, the items are leaked with it and `deque` is left empty.
+    items: ManuallyDrop<VecDeque<T>>,
+    // The two halves of the ring buffer, see `VecDeque::as_mut_slices`.
+    front: *mut T,
+    front_len: usize,
+    back: *mut T,
+    // Logical positions within the deque, like the fields of `VecMutScan`.
+    write: usize,
+    read: usize,
+    end: usize,
+}
+
+// The scan owns the deque's items, see `VecMutScan`.
+unsafe impl<'a, T: Send> Send for VecDequeMutScan<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for VecDequeMutScan<'a, T> {}
+
+impl<'a, T: 'a> VecDequeMutScan<'a, T> {
+    /// Begin a scan over a deque with mutation and item removal.
+    pub fn new(deque: &'a mut VecDeque<T>) -> Self {
+        let mut items = ManuallyDrop::new(mem::replace(deque, VecDeque::new()));
+        let end = items.len();
+        let (front, back) = items.as_mut_slices();
+        let front_len = front.len();
+        let front = front.as_mut_ptr();
+        let back = back.as_mut_ptr();
+
+        VecDequeMutScan {
+            deque,
+            items,
+            front,
+            front_len,
+            back,
+            write: 0,
+            read: 0,
+            end,
+        }
+    }
+
+    /// Advance to the next item of the deque.
+    ///
+    /// This returns a reference wrapper that enables item removal (see [`VecDequeMutScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<VecDequeMutScanItem<'s, 'a, T>> {
+        if self.read != self.end {
+            Some(VecDequeMutScanItem { scan: self })
+        } else {
+            None
+        }
+    }
+
+    /// Access the whole deque.
+    ///
+    /// This returns the visited items followed by the remaining items. As the ring buffer can wrap
+    /// around within either of them, each is returned as two slices, like
+    /// [`VecDeque::as_slices`]. The slots of removed items are not part of either.
+    pub fn slices(&self) -> (&[T], &[T], &[T], &[T]) {
+        unsafe {
+            let (a, a_len, b, b_len) = self.parts(0, self.write);
+            let (c, c_len, d, d_len) = self.parts(self.read, self.end);
+            (
+                slice::from_raw_parts(a, a_len),
+                slice::from_raw_parts(b, b_len),
+                slice::from_raw_parts(c, c_len),
+                slice::from_raw_parts(d, d_len),
+            )
+        }
+    }
+
+    /// Access and mutate the whole deque.
+    ///
+    /// See [`slices`][VecDequeMutScan::slices].
+    pub fn slices_mut(&mut self) -> (&mut [T], &mut [T], &mut [T], &mut [T]) {
+        unsafe {
+            let (a, a_len, b, b_len) = self.parts(0, self.write);
+            let (c, c_len, d, d_len) = self.parts(self.read, self.end);
+            (
+                slice::from_raw_parts_mut(a, a_len),
+                slice::from_raw_parts_mut(b, b_len),
+                slice::from_raw_parts_mut(c, c_len),
+                slice::from_raw_parts_mut(d, d_len),
+            )
+        }
+    }
+
+    /// The slot at the logical position `index` of the deque.
+    ///
+    /// Safety: `index` must be at most the deque's initial length.
+    unsafe fn slot(&self, index: usize) -> *mut T {
+        if index < self.front_len {
+            self.front.add(index)
+        } else {
+            self.back.add(index - self.front_len)
+        }
+    }
+
+    /// The number of slots that directly follow the slot at `index` within the same half, up to
+    /// the deque's initial length.
+    fn run_len(&self, index: usize) -> usize {
+        if index < self.front_len {
+            self.front_len - index
+        } else {
+            self.end - index
+        }
+    }
+
+    /// Split the logical range `start..end` into the ring buffer's two halves.
+    ///
+    /// Safety: `start..end` must be within the deque's initial length.
+    unsafe fn parts(&self, start: usize, end: usize) -> (*mut T, usize, *mut T, usize) {
+        if start >= self.front_len || end <= self.front_len {
+            (self.slot(start), end - start, self.back, 0)
+        } else {
+            (
+                self.slot(start),
+                self.front_len - start,
+                self.back,
+                end - self.front_len,
+            )
+        }
+    }
+}
+
+impl<'a, T: 'a> Drop for VecDequeMutScan<'a, T> {
+    fn drop(&mut self) {
+        // Move the remaining items over the gap, one contiguous run at a time.
+        let len = self.end - (self.read - self.write);
+        while self.read != self.end {
+            let run = min(self.run_len(self.read), self.run_len(self.write));
+            unsafe { ptr::copy(self.slot(self.read), self.slot(self.write), run) };
+            self.read += run;
+            self.write += run;
+        }
+        if len != self.end {
+            // The slots `len..end` were moved from or removed, so they are split off and their
+            // buffer is freed without dropping them.
+            let mut vacated = Vec::from(self.items.split_off(len));
+            unsafe { vacated.set_len(0) };
+        }
+        // The items are not used after this.
+        *self.deque = unsafe { ptr::read(&*self.items) };
+    }
+}
+
+/// Reference wrapper that enables item removal for [`VecDequeMutScan`].
+#[repr(transparent)]
+pub struct VecDequeMutScanItem<'s, 'a, T: 'a> {
+    scan: &'s mut VecDequeMutScan<'a, T>,
+}
+
+// When a `VecDequeMutScanItem` is created, there must be valid data at `scan.read`, see
+// `VecMutScanItem`.
+
+impl<'s, 'a, T: 'a> VecDequeMutScanItem<'s, 'a, T> {
+    fn into_inner_forget(self) -> &'s mut VecDequeMutScan<'a, T> {
+        // This is safe, as `VecDequeMutScanItem` is annotated with #[repr(transparent)], see
+        // `VecMutScanItem::into_inner_forget`.
+        unsafe { mem::transmute(self) }
+    }
+
+    /// Removes and returns this item from the deque.
+    pub fn remove(self) -> T {
+        // Do not run the item's drop, which would keep the item.
+        let scan = self.into_inner_forget();
+        unsafe {
+            let result = ptr::read(scan.slot(scan.read));
+            scan.read += 1;
+            result
+        }
+    }
+
+    /// Replaces this item with a new value, returns the old value.
+    ///
+    /// This is equivalent to assigning a new value or calling [`mem::replace`] on the mutable
+    /// reference obtained by using [`DerefMut`], but can avoid an intermediate move within the
+    /// deque's buffer.
+    pub fn replace(self, value: T) -> T {
+        let scan = self.into_inner_forget();
+        unsafe {
+            let result = ptr::read(scan.slot(scan.read));
+            ptr::write(scan.slot(scan.write), value);
+            scan.read += 1;
+            scan.write += 1;
+            result
+        }
+    }
+}
+
+impl<'s, 'a, T: 'a> Deref for VecDequeMutScanItem<'s, 'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // Within a `VecDequeMutScanItem` the slot at `scan.read` contains valid data owned by the
+        // scan on which we have a mutable borrow.
+        unsafe { &*self.scan.slot(self.scan.read) }
+    }
+}
+
+impl<'s, 'a, T: 'a> DerefMut for VecDequeMutScanItem<'s, 'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // See `deref`.
+        unsafe { &mut *self.scan.slot(self.scan.read) }
+    }
+}
+
+impl<'s, 'a, T: 'a> Drop for VecDequeMutScanItem<'s, 'a, T> {
+    fn drop(&mut self) {
+        let scan = &mut *self.scan;
+        // Move the item over the gap, see `VecMutScan::keep_current`.
+        unsafe { ptr::copy(scan.slot(scan.read), scan.slot(scan.write), 1) };
+        scan.read += 1;
+        scan.write += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec};
+
+    #[test]
+    fn wrapped_deque() {
+        let mut deque: VecDeque<_> = VecDeque::with_capacity(8);
+        deque.extend((4..8).map(Rc::new));
+        for i in (0..4).rev() {
+            deque.push_front(Rc::new(i));
+        }
+        assert!(!deque.as_slices().1.is_empty());
+        let copies: Vec<_> = deque.iter().cloned().collect();
+
+        let mut scan = VecDequeMutScan::new(&mut deque);
+        let mut seen = vec![];
+        loop {
+            let mut item = match scan.next() {
+                Some(item) => item,
+                None => break,
+            };
+            let value = **item;
+            seen.push(value);
+            match value % 3 {
+                0 => drop(item.remove()),
+                1 => *item = Rc::new(value + 10),
+                _ => (),
+            }
+            if value == 5 {
+                break;
+            }
+        }
+        let (a, b, c, d) = scan.slices();
+        assert_eq!(a.len() + b.len(), 4);
+        assert_eq!(c.len() + d.len(), 2);
+        drop(scan);
+
+        assert_eq!(seen, [0, 1, 2, 3, 4, 5]);
+        let values: Vec<_> = deque.iter().map(|x| **x).collect();
+        assert_eq!(values, [11, 2, 14, 5, 6, 7]);
+        drop(copies);
+        assert!(deque.iter().all(|x| Rc::strong_count(x) == 1));
+        deque.push_back(Rc::new(8));
+        assert_eq!(deque.len(), 7);
+    }
+
+    #[test]
+    fn in_place() {
+        let mut deque: VecDeque<_> = VecDeque::with_capacity(8);
+        deque.extend(4..8);
+        for i in (0..4).rev() {
+            deque.push_front(i);
+        }
+        let front = deque.as_slices().0.as_ptr();
+
+        let mut scan = VecDequeMutScan::new(&mut deque);
+        while let Some(item) = scan.next() {
+            if *item == 2 {
+                assert_eq!(item.replace(12), 2);
+            }
+        }
+        drop(scan);
+
+        assert_eq!(deque.as_slices().0.as_ptr(), front);
+        assert_eq!(deque, [0, 1, 12, 3, 4, 5, 6, 7]);
+    }
+}
@@ -0,0 +1,155 @@
+use alloc::vec::{self, Vec};
+use core::{
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+/// Forward scan that moves the kept items of a source vector into a destination vector.
+///
+/// Unlike [`VecMutScan`](crate::VecMutScan), which compacts the kept items in place, this moves
+/// every kept or replaced item into `dest`, appending it after any items `dest` already contains.
+/// When most items are removed, moving the few kept items out is cheaper than compacting in place,
+/// and reusing a destination vector avoids allocating. Afterwards, the source vector is empty but
+/// keeps its capacity. Items that were not visited when the scan is dropped are kept.
+///
+/// ```
+/// # use vec_mut_scan::DoubleBufferScan;
+/// let mut current: Vec<i32> = (0..10).collect();
+/// let mut next = Vec::with_capacity(10);
+///
+/// let mut scan = DoubleBufferScan::new(&mut current, &mut next);
+/// while let Some(mut item) = scan.next() {
+///     if *item % 4 != 0 {
+///         item.remove();
+///     } else {
+///         *item /= 4;
+///     }
+/// }
+/// drop(scan);
+///
+/// assert!(current.is_empty());
+/// assert_eq!(next, [0, 1, 2]);
+/// ```
+pub struct DoubleBufferScan<'a, T: 'a> {
+    source: vec::Drain<'a, T>,
+    dest: &'a mut Vec<T>,
+}
+
+impl<'a, T: 'a> DoubleBufferScan<'a, T> {
+    /// Begin a scan over `source`, moving kept items to the end of `dest`.
+    pub fn new(source: &'a mut Vec<T>, dest: &'a mut Vec<T>) -> Self {
+        DoubleBufferScan {
+            source: source.drain(..),
+            dest,
+        }
+    }
+
+    /// Advance to the next item of the source vector.
+    ///
+    /// This returns a reference wrapper that enables item removal (see
+    /// [`DoubleBufferScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<DoubleBufferScanItem<'s, 'a, T>> {
+        let item = self.source.next()?;
+        Some(DoubleBufferScanItem {
+            scan: self,
+            item: Some(item),
+        })
+    }
+
+    /// The items kept so far, including the items `dest` contained before the scan.
+    pub fn kept(&self) -> &[T] {
+        self.dest
+    }
+}
+
+impl<'a, T: 'a> Drop for DoubleBufferScan<'a, T> {
+    fn drop(&mut self) {
+        self.dest.extend(self.source.by_ref());
+    }
+}
+
+/// Reference wrapper that enables item removal for [`DoubleBufferScan`].
+///
+/// Dropping the wrapper keeps the item, moving it to the destination vector.
+pub struct DoubleBufferScanItem<'s, 'a, T: 'a> {
+    scan: &'s mut DoubleBufferScan<'a, T>,
+    // Always `Some` until the wrapper is consumed.
+    item: Option<T>,
+}
+
+impl<'s, 'a, T: 'a> DoubleBufferScanItem<'s, 'a, T> {
+    /// Removes and returns this item.
+    pub fn remove(mut self) -> T {
+        self.item.take().unwrap()
+    }
+
+    /// Replaces this item with a new value, returns the old value.
+    pub fn replace(mut self, value: T) -> T {
+        mem::replace(&mut *self, value)
+    }
+
+    /// Keep this item, returning a reference to it in the destination vector.
+    pub fn keep(self) -> &'s mut T {
+        let mut this = ManuallyDrop::new(self);
+        let item = this.item.take().unwrap();
+        // `this` is not dropped, so the reference is moved out exactly once.
+        let scan = unsafe { ptr::read(&this.scan) };
+        scan.dest.push(item);
+        scan.dest.last_mut().unwrap()
+    }
+}
+
+impl<'s, 'a, T: 'a> Deref for DoubleBufferScanItem<'s, 'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.item.as_ref().unwrap()
+    }
+}
+
+impl<'s, 'a, T: 'a> DerefMut for DoubleBufferScanItem<'s, 'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.item.as_mut().unwrap()
+    }
+}
+
+impl<'s, 'a, T: 'a> Drop for DoubleBufferScanItem<'s, 'a, T> {
+    fn drop(&mut self) {
+        if let Some(item) = self.item.take() {
+            self.scan.dest.push(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec};
+
+    #[test]
+    fn moves_kept_items() {
+        let mut source: Vec<_> = (0..8).map(Rc::new).collect();
+        let copy = source.clone();
+        let mut dest = vec![Rc::new(100)];
+
+        let mut scan = DoubleBufferScan::new(&mut source, &mut dest);
+        scan.next().unwrap().remove();
+        scan.next();
+        assert_eq!(*scan.next().unwrap().replace(Rc::new(20)), 2);
+        let kept = scan.next().unwrap().keep();
+        assert_eq!(**kept, 3);
+        scan.next().unwrap().remove();
+        assert_eq!(scan.kept().len(), 4);
+        drop(scan);
+
+        let values: Vec<_> = dest.iter().map(|x| **x).collect();
+        assert_eq!(values, [100, 1, 20, 3, 5, 6, 7]);
+        assert!(source.is_empty());
+        assert_eq!(source.capacity(), 8);
+        drop(dest);
+        assert!(copy.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+}
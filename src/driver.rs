@@ -0,0 +1,140 @@
+use alloc::vec::Vec;
+
+use crate::VecGrowScan;
+
+/// The action to take for an item visited by a [`ScanVisitor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanAction<T> {
+    /// Keep the item.
+    Keep,
+    /// Remove the item, passing it to [`ScanVisitor::removed`].
+    Remove,
+    /// Replace the item with a new value, passing the old value to [`ScanVisitor::removed`].
+    Replace(T),
+    /// Keep the item and insert a new item before it.
+    InsertBefore(T),
+    /// Keep the item and insert a new item after it.
+    InsertAfter(T),
+    /// Keep the item and end the scan, keeping all remaining items unvisited.
+    Stop,
+}
+
+/// Object-safe visitor for scanning a vector using [`drive`].
+///
+/// Unlike the methods of [`VecMutScan`](crate::VecMutScan) and [`VecGrowScan`], this can be used
+/// as a `dyn ScanVisitor<T>`, e.g. across a plugin boundary where generic closures are not
+/// available. Closures taking a `&mut T` and returning a [`ScanAction<T>`] implement this trait.
+pub trait ScanVisitor<T> {
+    /// Visit an item, returning what to do with it.
+    ///
+    /// The item can be mutated in place, which also takes effect when the item is kept.
+    fn visit(&mut self, item: &mut T) -> ScanAction<T>;
+
+    /// Accept an item that was removed or replaced.
+    ///
+    /// The default implementation drops the item.
+    fn removed(&mut self, item: T) {
+        drop(item);
+    }
+}
+
+impl<T, F: FnMut(&mut T) -> ScanAction<T>> ScanVisitor<T> for F {
+    fn visit(&mut self, item: &mut T) -> ScanAction<T> {
+        self(item)
+    }
+}
+
+/// Scan a vector, applying the actions returned by `visitor` to each item.
+///
+/// Items inserted by the visitor are not visited themselves. This is not generic over the visitor,
+/// so only a single instance per item type is compiled.
+///
+/// ```
+/// # use vec_mut_scan::{drive, ScanAction, ScanVisitor};
+/// struct Uppercase;
+///
+/// impl ScanVisitor<String> for Uppercase {
+///     fn visit(&mut self, item: &mut String) -> ScanAction<String> {
+///         match item.as_str() {
+///             "" => ScanAction::Remove,
+///             "stop" => ScanAction::Stop,
+///             _ => {
+///                 item.make_ascii_uppercase();
+///                 ScanAction::Keep
+///             }
+///         }
+///     }
+/// }
+///
+/// let mut words: Vec<String> = vec!["a".into(), "".into(), "b".into(), "stop".into(), "c".into()];
+/// let visitor: &mut dyn ScanVisitor<String> = &mut Uppercase;
+/// drive(&mut words, visitor);
+///
+/// assert_eq!(words, ["A", "B", "stop", "c"]);
+/// ```
+pub fn drive<T>(vec: &mut Vec<T>, visitor: &mut dyn ScanVisitor<T>) {
+    let mut scan = VecGrowScan::new(vec);
+    while let Some(mut item) = scan.next() {
+        match visitor.visit(&mut item) {
+            ScanAction::Keep => (),
+            ScanAction::Remove => visitor.removed(item.remove()),
+            ScanAction::Replace(value) => visitor.removed(item.replace(value)),
+            ScanAction::InsertBefore(value) => {
+                item.insert_before(value);
+            }
+            ScanAction::InsertAfter(value) => {
+                item.insert_after(value);
+            }
+            ScanAction::Stop => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn closure_visitor() {
+        let mut input: Vec<_> = (0..8).collect();
+
+        drive(&mut input, &mut |x: &mut i32| match *x % 4 {
+            0 => ScanAction::Remove,
+            1 => ScanAction::Replace(-*x),
+            2 => ScanAction::InsertBefore(100),
+            _ => ScanAction::InsertAfter(200),
+        });
+
+        assert_eq!(input, [-1, 100, 2, 3, 200, -5, 100, 6, 7, 200]);
+    }
+
+    #[test]
+    fn removed_items() {
+        struct Collect(Vec<i32>);
+
+        impl ScanVisitor<i32> for Collect {
+            fn visit(&mut self, item: &mut i32) -> ScanAction<i32> {
+                if *item > 4 {
+                    ScanAction::Stop
+                } else if *item % 2 == 0 {
+                    ScanAction::Remove
+                } else {
+                    ScanAction::Replace(*item * 10)
+                }
+            }
+
+            fn removed(&mut self, item: i32) {
+                self.0.push(item);
+            }
+        }
+
+        let mut input = vec![1, 2, 3, 4, 5, 6];
+        let mut visitor = Collect(vec![]);
+        drive(&mut input, &mut visitor);
+
+        assert_eq!(input, [10, 30, 5, 6]);
+        assert_eq!(visitor.0, [1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{VecGrowScan, VecGrowScanItem};
+
+/// The next item of a [`VecGrowScan`] or the scan itself, if all items were visited.
+///
+/// Returned by [`VecGrowScan::entry`].
+pub enum Entry<'s, 'a, T: 'a> {
+    /// The next item of the vector.
+    Occupied(VecGrowScanItem<'s, 'a, T>),
+    /// All items were visited, contains the scan.
+    End(&'s mut VecGrowScan<'a, T>),
+}
+
+impl<'a, T: 'a> VecGrowScan<'a, T> {
+    /// Advance to the next item of the vector, or return the scan if all items were visited.
+    ///
+    /// This is an alternative to [`next`][VecGrowScan::next] for match based control flow. Unlike
+    /// with `next`, the scan remains usable after the last item, e.g. to append further items,
+    /// while the borrow of the returned value is still active.
+    ///
+    /// ```
+    /// # use vec_mut_scan::{Entry, VecGrowScan};
+    /// let mut numbers = vec![1, 2, 3];
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    ///
+    /// let mut sum = 0;
+    /// loop {
+    ///     match scan.entry() {
+    ///         Entry::Occupied(item) => {
+    ///             sum += *item;
+    ///             if *item == 2 {
+    ///                 item.remove();
+    ///             }
+    ///         }
+    ///         Entry::End(scan) => {
+    ///             scan.append(sum);
+    ///             break;
+    ///         }
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 3, 6]);
+    /// ```
+    pub fn entry<'s>(&'s mut self) -> Entry<'s, 'a, T> {
+        // This can't use `next`, as the borrow of `self` would extend to the `End` case.
+        if self.read != self.end {
+            Entry::Occupied(VecGrowScanItem { scan: self })
+        } else {
+            Entry::End(self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn entries() {
+        let mut input = vec![1, 2, 3];
+        let mut scan = VecGrowScan::new(&mut input);
+
+        let mut ends = 0;
+        while ends < 2 {
+            match scan.entry() {
+                Entry::Occupied(item) => {
+                    let value = *item;
+                    item.replace_with_many(vec![value; value]);
+                }
+                Entry::End(scan) => {
+                    ends += 1;
+                    scan.insert_next(0);
+                }
+            }
+        }
+        drop(scan);
+
+        assert_eq!(input, [1, 2, 2, 3, 3, 3, 0]);
+    }
+}
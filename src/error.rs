@@ -0,0 +1,32 @@
+use core::fmt;
+
+/// The error returned by the fallible operations of this crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScanError {
+    /// The required capacity exceeds the maximal capacity of the storage.
+    CapacityExceeded,
+    /// The allocator failed to provide the required capacity.
+    AllocationFailed,
+    /// A stored position does not match the vector it is used with.
+    InvalidPosition,
+    /// An operation did not complete within its budget.
+    BudgetExhausted,
+    /// A suspended scan has appended items, which a [`VecMutScan`](crate::VecMutScan) cannot keep
+    /// at the end of the vector.
+    AppendedItems,
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ScanError::CapacityExceeded => "capacity exceeded",
+            ScanError::AllocationFailed => "memory allocation failed",
+            ScanError::InvalidPosition => "position does not match the vector",
+            ScanError::BudgetExhausted => "budget exhausted",
+            ScanError::AppendedItems => "scan has appended items",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ScanError {}
@@ -0,0 +1,103 @@
+use alloc::vec::Vec;
+
+use crate::{ScanError, VecGrowScan, VecMutScan};
+
+impl<'a, T: 'a> VecMutScan<'a, T> {
+    /// Like [`start_at`][VecMutScan::start_at], but returns [`ScanError::InvalidPosition`] instead
+    /// of panicking if `start` is larger than the vector's length.
+    ///
+    /// ```
+    /// # use vec_mut_scan::{ScanError, VecMutScan};
+    /// let mut numbers = vec![1, 2, 3];
+    ///
+    /// assert!(VecMutScan::try_start_at(&mut numbers, 4).is_err());
+    /// let mut scan = VecMutScan::try_start_at(&mut numbers, 1)?;
+    /// scan.next().unwrap().remove();
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 3]);
+    /// # Ok::<(), ScanError>(())
+    /// ```
+    pub fn try_start_at(vec: &mut Vec<T>, start: usize) -> Result<VecMutScan<'_, T>, ScanError> {
+        if start <= vec.len() {
+            Ok(VecMutScan::start_at(vec, start))
+        } else {
+            Err(ScanError::InvalidPosition)
+        }
+    }
+
+    /// Like [`swap_remaining`][VecMutScan::swap_remaining], but returns
+    /// [`ScanError::InvalidPosition`] instead of panicking if `i` or `j` is out of bounds.
+    pub fn try_swap_remaining(&mut self, i: usize, j: usize) -> Result<(), ScanError> {
+        let remaining = self.remaining_mut();
+        if i < remaining.len() && j < remaining.len() {
+            remaining.swap(i, j);
+            Ok(())
+        } else {
+            Err(ScanError::InvalidPosition)
+        }
+    }
+}
+
+impl<'a, T: 'a> VecGrowScan<'a, T> {
+    /// Like [`start_at`][VecGrowScan::start_at], but returns [`ScanError::InvalidPosition`]
+    /// instead of panicking if `start` is larger than the vector's length.
+    pub fn try_start_at(vec: &mut Vec<T>, start: usize) -> Result<VecGrowScan<'_, T>, ScanError> {
+        if start <= vec.len() {
+            Ok(VecGrowScan::start_at(vec, start))
+        } else {
+            Err(ScanError::InvalidPosition)
+        }
+    }
+
+    /// Like [`swap_remaining`][VecGrowScan::swap_remaining], but returns
+    /// [`ScanError::InvalidPosition`] instead of panicking if `i` or `j` is out of bounds.
+    pub fn try_swap_remaining(&mut self, i: usize, j: usize) -> Result<(), ScanError> {
+        let remaining = self.slices_mut().3;
+        if i < remaining.len() && j < remaining.len() {
+            remaining.swap(i, j);
+            Ok(())
+        } else {
+            Err(ScanError::InvalidPosition)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn out_of_bounds() {
+        let mut input = vec![1, 2, 3, 4];
+
+        assert_eq!(
+            VecGrowScan::try_start_at(&mut input, 5).err(),
+            Some(ScanError::InvalidPosition)
+        );
+        let mut scan = VecGrowScan::try_start_at(&mut input, 1).unwrap();
+        assert_eq!(
+            scan.try_swap_remaining(0, 3),
+            Err(ScanError::InvalidPosition)
+        );
+        scan.try_swap_remaining(0, 2).unwrap();
+        assert_eq!(*scan.next().unwrap(), 4);
+        drop(scan);
+
+        assert_eq!(
+            VecMutScan::try_start_at(&mut input, 5).err(),
+            Some(ScanError::InvalidPosition)
+        );
+        let mut scan = VecMutScan::try_start_at(&mut input, 4).unwrap();
+        assert!(scan.next().is_none());
+        assert_eq!(
+            scan.try_swap_remaining(0, 0),
+            Err(ScanError::InvalidPosition)
+        );
+        drop(scan);
+
+        assert_eq!(input, [1, 4, 3, 2]);
+    }
+}
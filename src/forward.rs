@@ -0,0 +1,166 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    fmt,
+};
+
+use crate::{VecGrowScanItem, VecMutScanItem};
+
+// The item wrappers behave like references to the current item, so they forward the standard
+// reference conversions, comparisons with items and formatting to the current item.
+
+impl<'s, 'a, T: 'a> AsRef<T> for VecMutScanItem<'s, 'a, T> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<'s, 'a, T: 'a> AsMut<T> for VecMutScanItem<'s, 'a, T> {
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<'s, 'a, T: 'a> Borrow<T> for VecMutScanItem<'s, 'a, T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<'s, 'a, T: 'a> BorrowMut<T> for VecMutScanItem<'s, 'a, T> {
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<'s, 'a, T: 'a + PartialEq> PartialEq<T> for VecMutScanItem<'s, 'a, T> {
+    fn eq(&self, other: &T) -> bool {
+        **self == *other
+    }
+}
+
+impl<'s, 'a, T: 'a + PartialOrd> PartialOrd<T> for VecMutScanItem<'s, 'a, T> {
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        (**self).partial_cmp(other)
+    }
+}
+
+impl<'s, 'a, T: 'a + fmt::Debug> fmt::Debug for VecMutScanItem<'s, 'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<'s, 'a, T: 'a + fmt::Display> fmt::Display for VecMutScanItem<'s, 'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<'s, 'a, T: 'a> AsRef<T> for VecGrowScanItem<'s, 'a, T> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<'s, 'a, T: 'a> AsMut<T> for VecGrowScanItem<'s, 'a, T> {
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<'s, 'a, T: 'a> Borrow<T> for VecGrowScanItem<'s, 'a, T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<'s, 'a, T: 'a> BorrowMut<T> for VecGrowScanItem<'s, 'a, T> {
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<'s, 'a, T: 'a + PartialEq> PartialEq<T> for VecGrowScanItem<'s, 'a, T> {
+    fn eq(&self, other: &T) -> bool {
+        **self == *other
+    }
+}
+
+impl<'s, 'a, T: 'a + PartialOrd> PartialOrd<T> for VecGrowScanItem<'s, 'a, T> {
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        (**self).partial_cmp(other)
+    }
+}
+
+impl<'s, 'a, T: 'a + fmt::Debug> fmt::Debug for VecGrowScanItem<'s, 'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<'s, 'a, T: 'a + fmt::Display> fmt::Display for VecGrowScanItem<'s, 'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{VecGrowScan, VecMutScan};
+    use alloc::{format, string::String, vec};
+    use core::borrow::BorrowMut;
+
+    fn push_bang(mut s: impl BorrowMut<String>) {
+        s.borrow_mut().push('!');
+    }
+
+    fn len(s: impl AsRef<str>) -> usize {
+        s.as_ref().len()
+    }
+
+    #[test]
+    fn generic_access() {
+        let mut input = vec![String::from("a"), String::from("bc")];
+
+        let mut scan = VecMutScan::new(&mut input);
+        while let Some(item) = scan.next() {
+            push_bang(item);
+        }
+        drop(scan);
+
+        let mut scan = VecGrowScan::new(&mut input);
+        let mut lens = vec![];
+        while let Some(item) = scan.next() {
+            lens.push(len(item.as_ref()));
+        }
+        drop(scan);
+
+        assert_eq!(input, ["a!", "bc!"]);
+        assert_eq!(lens, [2, 3]);
+    }
+
+    #[test]
+    fn comparisons() {
+        let mut input = vec![3, 1, 4];
+
+        let mut scan = VecGrowScan::new(&mut input);
+        let item = scan.next().unwrap();
+        assert!(item == 3);
+        assert!(item > 2 && item < 4);
+        assert_eq!(format!("{} {:?}", item, item), "3 3");
+        drop(item);
+        drop(scan);
+
+        let mut scan = VecMutScan::new(&mut input);
+        while let Some(item) = scan.next() {
+            if item <= 1 {
+                assert_eq!(format!("{:?}", item), "1");
+                item.remove();
+            }
+        }
+        drop(scan);
+
+        assert_eq!(input, [3, 4]);
+    }
+}
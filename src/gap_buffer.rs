@@ -0,0 +1,300 @@
+use alloc::vec::Vec;
+use core::{fmt, mem::ManuallyDrop, ptr, slice};
+
+/// A sequence with a cursor, supporting insertion and removal at the cursor in amortized constant
+/// time.
+///
+/// The items are stored in a single buffer, with the items before the cursor at its start and the
+/// items after the cursor at its end. The unused capacity in between forms a gap, like the gap
+/// left by removed items during a scan. Moving the cursor moves the items it passes over the gap.
+///
+/// ```
+/// # use vec_mut_scan::GapBuffer;
+/// let mut buffer = GapBuffer::from(vec![1, 2, 3, 4]);
+/// buffer.set_cursor(1);
+/// buffer.insert(10);
+/// buffer.insert(11);
+/// assert_eq!(buffer.remove_after(), Some(2));
+/// buffer.set_cursor(4);
+/// assert_eq!(buffer.remove_before(), Some(3));
+///
+/// assert_eq!(buffer.slices(), (&[1, 10, 11][..], &[4][..]));
+/// assert_eq!(buffer.into_vec(), [1, 10, 11, 4]);
+/// ```
+pub struct GapBuffer<T> {
+    // The items are `0..before` and `capacity - after..capacity`, while the length of `buf` is
+    // kept at zero. This way a leaked buffer also leaks the items.
+    buf: Vec<T>,
+    before: usize,
+    after: usize,
+}
+
+impl<T> GapBuffer<T> {
+    /// Create an empty gap buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty gap buffer with room for `capacity` items.
+    pub fn with_capacity(capacity: usize) -> Self {
+        GapBuffer::from(Vec::with_capacity(capacity))
+    }
+
+    /// The number of items.
+    pub fn len(&self) -> usize {
+        self.before + self.after
+    }
+
+    /// Returns `true` if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The position of the cursor, i.e. the number of items before it.
+    pub fn cursor(&self) -> usize {
+        self.before
+    }
+
+    /// Move the cursor to the given position, moving all items in between over the gap.
+    ///
+    /// Panics if `position` is larger than the number of items.
+    pub fn set_cursor(&mut self, position: usize) {
+        assert!(position <= self.len(), "cursor position out of bounds");
+        let base = self.buf.as_mut_ptr();
+        let back = self.back();
+        unsafe {
+            if position < self.before {
+                let count = self.before - position;
+                // The items `position..before` are moved to just before the items after the
+                // cursor. `ptr::copy` handles a gap smaller than the moved items.
+                ptr::copy(base.add(position), base.add(back - count), count);
+            } else {
+                let count = position - self.before;
+                ptr::copy(base.add(back), base.add(self.before), count);
+            }
+        }
+        self.after = self.len() - position;
+        self.before = position;
+    }
+
+    /// Insert an item at the cursor, placing the cursor after it.
+    pub fn insert(&mut self, item: T) {
+        self.reserve(1);
+        unsafe { ptr::write(self.buf.as_mut_ptr().add(self.before), item) };
+        self.before += 1;
+    }
+
+    /// Insert a sequence of items at the cursor, placing the cursor after them.
+    ///
+    /// Equivalent to repeatedly calling [`insert`][GapBuffer::insert], except that reallocations
+    /// will be minimized with iterator size hints.
+    pub fn insert_many(&mut self, iter: impl IntoIterator<Item = T>) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for item in iter {
+            self.insert(item);
+        }
+    }
+
+    /// Remove and return the item before the cursor.
+    pub fn remove_before(&mut self) -> Option<T> {
+        if self.before == 0 {
+            return None;
+        }
+        self.before -= 1;
+        Some(unsafe { ptr::read(self.buf.as_ptr().add(self.before)) })
+    }
+
+    /// Remove and return the item after the cursor.
+    pub fn remove_after(&mut self) -> Option<T> {
+        if self.after == 0 {
+            return None;
+        }
+        let item = unsafe { ptr::read(self.buf.as_ptr().add(self.back())) };
+        self.after -= 1;
+        Some(item)
+    }
+
+    /// Make sure that at least `additional` items can be inserted without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        if self.buf.capacity() - self.len() >= additional {
+            return;
+        }
+        let after = self.after;
+        self.make_contiguous_len();
+        // Should `reserve` panic, the buffer is in a consistent state with all items before the
+        // cursor.
+        self.buf.reserve(additional);
+        unsafe {
+            self.buf.set_len(0);
+            self.before -= after;
+            self.after = after;
+            let base = self.buf.as_mut_ptr();
+            ptr::copy(base.add(self.before), base.add(self.back()), after);
+        }
+    }
+
+    /// The items before and after the cursor.
+    pub fn slices(&self) -> (&[T], &[T]) {
+        let base = self.buf.as_ptr();
+        unsafe {
+            (
+                slice::from_raw_parts(base, self.before),
+                slice::from_raw_parts(base.add(self.back()), self.after),
+            )
+        }
+    }
+
+    /// The items before and after the cursor, for mutation.
+    pub fn slices_mut(&mut self) -> (&mut [T], &mut [T]) {
+        let back = self.back();
+        let base = self.buf.as_mut_ptr();
+        unsafe {
+            (
+                slice::from_raw_parts_mut(base, self.before),
+                slice::from_raw_parts_mut(base.add(back), self.after),
+            )
+        }
+    }
+
+    /// Return a reference to the item at the given index, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let (before, after) = self.slices();
+        before
+            .get(index)
+            .or_else(|| after.get(index - before.len()))
+    }
+
+    /// Move the cursor to the end, returning all items as a single slice.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        let len = self.len();
+        self.set_cursor(len);
+        self.slices_mut().0
+    }
+
+    /// Convert into a vector containing all items.
+    pub fn into_vec(self) -> Vec<T> {
+        let mut this = ManuallyDrop::new(self);
+        this.make_contiguous_len();
+        // `this` is not dropped, so the vector is moved out exactly once.
+        unsafe { ptr::read(&this.buf) }
+    }
+
+    /// Index of the first item after the cursor.
+    fn back(&self) -> usize {
+        self.buf.capacity() - self.after
+    }
+
+    /// Move the items after the cursor right after the items before the cursor and set the length
+    /// of `buf` to include all items, which are now considered before the cursor.
+    fn make_contiguous_len(&mut self) {
+        let len = self.len();
+        let back = self.back();
+        unsafe {
+            let base = self.buf.as_mut_ptr();
+            ptr::copy(base.add(back), base.add(self.before), self.after);
+            self.buf.set_len(len);
+        }
+        self.before = len;
+        self.after = 0;
+    }
+}
+
+impl<T> Default for GapBuffer<T> {
+    fn default() -> Self {
+        GapBuffer::from(Vec::new())
+    }
+}
+
+/// Creates a gap buffer with the cursor at the end.
+impl<T> From<Vec<T>> for GapBuffer<T> {
+    fn from(mut vec: Vec<T>) -> Self {
+        let before = vec.len();
+        // Leak amplification, the items are owned by the gap buffer from here on.
+        unsafe { vec.set_len(0) };
+        GapBuffer {
+            buf: vec,
+            before,
+            after: 0,
+        }
+    }
+}
+
+impl<T> Drop for GapBuffer<T> {
+    fn drop(&mut self) {
+        // The vector takes ownership of the items and drops them.
+        self.make_contiguous_len();
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for GapBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (before, after) = self.slices();
+        f.debug_list().entries(before).entries(after).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{format, rc::Rc, vec};
+
+    #[test]
+    fn cursor_edits() {
+        let item = Rc::new(0);
+        let mut buffer = GapBuffer::new();
+        let mut model = vec![];
+        let mut cursor = 0;
+
+        for step in 0..200usize {
+            match step * 7 % 5 {
+                0 | 1 => {
+                    buffer.insert(item.clone());
+                    model.insert(cursor, item.clone());
+                    cursor += 1;
+                }
+                2 => {
+                    cursor = step * 13 % (model.len() + 1);
+                    buffer.set_cursor(cursor);
+                }
+                3 => {
+                    assert_eq!(buffer.remove_before().is_some(), cursor > 0);
+                    if cursor > 0 {
+                        cursor -= 1;
+                        model.remove(cursor);
+                    }
+                }
+                _ => {
+                    assert_eq!(buffer.remove_after().is_some(), cursor < model.len());
+                    if cursor < model.len() {
+                        model.remove(cursor);
+                    }
+                }
+            }
+            assert_eq!(buffer.cursor(), cursor);
+            assert_eq!(buffer.len(), model.len());
+            assert_eq!(Rc::strong_count(&item), 1 + 2 * model.len());
+        }
+
+        drop(model);
+        let len = buffer.len();
+        buffer.insert_many(vec![item.clone(); 20]);
+        assert_eq!(buffer.make_contiguous().len(), len + 20);
+        assert!(buffer.get(len + 19).is_some());
+        assert!(buffer.get(len + 20).is_none());
+        let vec = buffer.into_vec();
+        assert_eq!(Rc::strong_count(&item), 1 + vec.len());
+        drop(vec);
+
+        let mut buffer = GapBuffer::from(vec![1, 2, 3]);
+        buffer.set_cursor(0);
+        buffer.insert(0);
+        assert_eq!(format!("{:?}", buffer), "[0, 1, 2, 3]");
+        buffer.set_cursor(4);
+        buffer.set_cursor(2);
+        buffer.reserve(100);
+        assert_eq!(buffer.slices(), (&[0, 1][..], &[2, 3][..]));
+        drop(buffer);
+    }
+}
@@ -0,0 +1,210 @@
+use alloc::string::String;
+use core::{fmt, str};
+
+use crate::GapBuffer;
+
+/// A string with a cursor, supporting text insertion and deletion at the cursor in amortized
+/// constant time per byte.
+///
+/// This is a [`GapBuffer`] of UTF-8 encoded text, e.g. for the line buffer of an editor or a REPL.
+/// The cursor is a byte offset, which always lies on a char boundary.
+///
+/// ```
+/// # use vec_mut_scan::GapString;
+/// let mut text = GapString::from(String::from("hello wörld"));
+/// text.set_cursor(5);
+/// text.insert_str(",");
+/// text.move_right();
+/// assert_eq!(text.delete_after(), Some('w'));
+/// text.insert_char('W');
+/// text.move_right();
+/// assert_eq!(text.delete_before(), Some('ö'));
+/// text.insert_char('o');
+///
+/// assert_eq!(text.as_strs(), ("hello, Wo", "rld"));
+/// assert_eq!(text.to_string(), "hello, World");
+/// ```
+#[derive(Default)]
+pub struct GapString {
+    // Only ever contains valid UTF-8 before and after the cursor.
+    buf: GapBuffer<u8>,
+}
+
+impl GapString {
+    /// Create an empty string.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The length of the text in bytes.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns `true` if the text is empty.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// The byte offset of the cursor.
+    pub fn cursor(&self) -> usize {
+        self.buf.cursor()
+    }
+
+    /// Move the cursor to the given byte offset.
+    ///
+    /// Panics if `position` is out of bounds or not on a char boundary.
+    pub fn set_cursor(&mut self, position: usize) {
+        assert!(position <= self.len(), "cursor position out of bounds");
+        let byte = self.buf.get(position);
+        assert!(
+            byte.map_or(true, |&byte| !is_continuation(byte)),
+            "cursor position is not a char boundary"
+        );
+        self.buf.set_cursor(position);
+    }
+
+    /// Move the cursor one char to the left, returning `false` if it is at the start.
+    pub fn move_left(&mut self) -> bool {
+        match self.as_strs().0.chars().next_back() {
+            Some(c) => {
+                let position = self.cursor() - c.len_utf8();
+                self.buf.set_cursor(position);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Move the cursor one char to the right, returning `false` if it is at the end.
+    pub fn move_right(&mut self) -> bool {
+        match self.as_strs().1.chars().next() {
+            Some(c) => {
+                let position = self.cursor() + c.len_utf8();
+                self.buf.set_cursor(position);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Insert text at the cursor, placing the cursor after it.
+    pub fn insert_str(&mut self, text: &str) {
+        self.buf.insert_many(text.bytes());
+    }
+
+    /// Insert a char at the cursor, placing the cursor after it.
+    pub fn insert_char(&mut self, c: char) {
+        self.insert_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Delete and return the char before the cursor.
+    pub fn delete_before(&mut self) -> Option<char> {
+        let c = self.as_strs().0.chars().next_back()?;
+        for _ in 0..c.len_utf8() {
+            self.buf.remove_before();
+        }
+        Some(c)
+    }
+
+    /// Delete and return the char after the cursor.
+    pub fn delete_after(&mut self) -> Option<char> {
+        let c = self.as_strs().1.chars().next()?;
+        for _ in 0..c.len_utf8() {
+            self.buf.remove_after();
+        }
+        Some(c)
+    }
+
+    /// The text before and after the cursor.
+    pub fn as_strs(&self) -> (&str, &str) {
+        let (before, after) = self.buf.slices();
+        // Only whole chars are inserted and deleted and the cursor is on a char boundary.
+        unsafe {
+            (
+                str::from_utf8_unchecked(before),
+                str::from_utf8_unchecked(after),
+            )
+        }
+    }
+
+    /// Move the cursor to the end, returning the whole text as a single string slice.
+    pub fn make_contiguous(&mut self) -> &str {
+        let bytes = self.buf.make_contiguous();
+        unsafe { str::from_utf8_unchecked(bytes) }
+    }
+
+    /// Convert into a `String` containing the whole text.
+    pub fn into_string(self) -> String {
+        unsafe { String::from_utf8_unchecked(self.buf.into_vec()) }
+    }
+}
+
+/// Creates a string with the cursor at the end.
+impl From<String> for GapString {
+    fn from(text: String) -> Self {
+        GapString {
+            buf: GapBuffer::from(text.into_bytes()),
+        }
+    }
+}
+
+impl fmt::Display for GapString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (before, after) = self.as_strs();
+        f.write_str(before)?;
+        f.write_str(after)
+    }
+}
+
+impl fmt::Debug for GapString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (before, after) = self.as_strs();
+        f.debug_tuple("GapString")
+            .field(&before)
+            .field(&after)
+            .finish()
+    }
+}
+
+/// Returns `true` for bytes that continue a multi-byte UTF-8 sequence.
+fn is_continuation(byte: u8) -> bool {
+    byte & 0xc0 == 0x80
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::ToString;
+
+    #[test]
+    fn text_edits() {
+        let mut text = GapString::new();
+        text.insert_str("aé🦀b");
+        assert!(!text.move_right());
+        assert!(text.move_left());
+        assert!(text.move_left());
+        assert_eq!(text.cursor(), 3);
+        assert_eq!(text.delete_before(), Some('é'));
+        text.insert_char('ü');
+        assert_eq!(text.delete_after(), Some('🦀'));
+        assert_eq!(text.as_strs(), ("aü", "b"));
+
+        text.set_cursor(0);
+        assert_eq!(text.delete_before(), None);
+        assert!(!text.move_left());
+        text.insert_str("x");
+        assert_eq!(text.make_contiguous(), "xaüb");
+        assert_eq!(text.len(), 5);
+        assert_eq!(text.to_string(), "xaüb");
+        assert_eq!(text.into_string(), "xaüb");
+    }
+
+    #[test]
+    #[should_panic(expected = "cursor position is not a char boundary")]
+    fn cursor_within_char() {
+        let mut text = GapString::from("aü".to_string());
+        text.set_cursor(2);
+    }
+}
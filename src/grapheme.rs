@@ -0,0 +1,156 @@
+use alloc::{string::String, vec::Vec};
+use core::{mem::ManuallyDrop, ops::Deref, ptr, str};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::VecGrowScan;
+
+/// Forward scan over the grapheme clusters of a string with removal and replacement.
+///
+/// This splits the string into extended grapheme clusters, as defined by [Unicode Standard Annex
+/// #29](https://www.unicode.org/reports/tr29/), so that removing or replacing an item never breaks
+/// up emoji sequences or characters with combining marks. The string is edited in place and is
+/// valid UTF-8 again when the scan is dropped.
+///
+/// Requires the `unicode-segmentation` feature.
+///
+/// ```
+/// # use vec_mut_scan::GraphemeScan;
+/// let mut text = String::from("cafe\u{301} 👍🏽!");
+/// let mut scan = GraphemeScan::new(&mut text);
+/// while let Some(grapheme) = scan.next() {
+///     match &*grapheme {
+///         "e\u{301}" => drop(grapheme.replace("é")),
+///         "👍🏽" => drop(grapheme.remove()),
+///         _ => (),
+///     }
+/// }
+/// drop(scan);
+///
+/// assert_eq!(text, "café !");
+/// ```
+pub struct GraphemeScan<'a> {
+    scan: VecGrowScan<'a, u8>,
+}
+
+impl<'a> GraphemeScan<'a> {
+    /// Begin a scan over the grapheme clusters of a string with removal and replacement.
+    pub fn new(string: &'a mut String) -> Self {
+        // The scan only ever removes complete grapheme clusters and inserts complete strings, so
+        // the bytes are valid UTF-8 whenever the scan is dropped. If the scan is leaked, the string
+        // is left empty.
+        GraphemeScan {
+            scan: VecGrowScan::new(unsafe { string.as_mut_vec() }),
+        }
+    }
+
+    /// Advance to the next grapheme cluster of the string.
+    ///
+    /// This returns a reference wrapper that enables removal and replacement (see
+    /// [`GraphemeScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<GraphemeScanItem<'s, 'a>> {
+        let len = self.remaining().graphemes(true).next()?.len();
+        Some(GraphemeScanItem { scan: self, len })
+    }
+
+    /// The part of the string that was not visited yet.
+    pub fn remaining(&self) -> &str {
+        // The remaining bytes were not modified and start at a grapheme cluster boundary.
+        unsafe { str::from_utf8_unchecked(self.scan.slices().3) }
+    }
+
+    /// Moves past the next `len` bytes, keeping them or, if `removed` is given, moving them there.
+    fn advance(&mut self, len: usize, removed: Option<&mut Vec<u8>>) {
+        match removed {
+            Some(removed) => {
+                for _ in 0..len {
+                    removed.push(self.scan.next().unwrap().remove());
+                }
+            }
+            None => {
+                for _ in 0..len {
+                    self.scan.next();
+                }
+            }
+        }
+    }
+}
+
+/// Reference wrapper that enables removal and replacement of grapheme clusters for
+/// [`GraphemeScan`].
+///
+/// Dereferences to the grapheme cluster as a `str`. Dropping the wrapper keeps the grapheme
+/// cluster.
+pub struct GraphemeScanItem<'s, 'a> {
+    scan: &'s mut GraphemeScan<'a>,
+    len: usize,
+}
+
+impl<'s, 'a> GraphemeScanItem<'s, 'a> {
+    fn into_inner_forget(self) -> (&'s mut GraphemeScan<'a>, usize) {
+        let item = ManuallyDrop::new(self);
+        // The reference is moved out of `item`, which is never used or dropped afterwards.
+        (unsafe { ptr::read(&item.scan) }, item.len)
+    }
+
+    /// Removes and returns this grapheme cluster from the string.
+    pub fn remove(self) -> String {
+        let (scan, len) = self.into_inner_forget();
+        let mut removed = Vec::with_capacity(len);
+        scan.advance(len, Some(&mut removed));
+        // The removed bytes form a complete grapheme cluster.
+        unsafe { String::from_utf8_unchecked(removed) }
+    }
+
+    /// Replaces this grapheme cluster with a new string, returns the old grapheme cluster.
+    ///
+    /// The new string is not split into grapheme clusters and not visited by the scan.
+    pub fn replace(self, value: &str) -> String {
+        let (scan, len) = self.into_inner_forget();
+        let mut removed = Vec::with_capacity(len);
+        scan.advance(len, Some(&mut removed));
+        scan.scan.insert_many(value.bytes());
+        // The removed bytes form a complete grapheme cluster.
+        unsafe { String::from_utf8_unchecked(removed) }
+    }
+}
+
+impl<'s, 'a> Deref for GraphemeScanItem<'s, 'a> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.scan.remaining()[..self.len]
+    }
+}
+
+impl<'s, 'a> Drop for GraphemeScanItem<'s, 'a> {
+    fn drop(&mut self) {
+        self.scan.advance(self.len, None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grapheme_items() {
+        let mut text = String::from("a\u{308}b🇩🇪c\r\nd");
+        let mut seen = Vec::new();
+
+        let mut scan = GraphemeScan::new(&mut text);
+        while let Some(grapheme) = scan.next() {
+            seen.push(String::from(&*grapheme));
+            match &*grapheme {
+                "b" => assert_eq!(grapheme.replace("xyz"), "b"),
+                "\r\n" => assert_eq!(grapheme.replace("\n"), "\r\n"),
+                "🇩🇪" => assert_eq!(grapheme.remove(), "🇩🇪"),
+                _ => (),
+            }
+        }
+        drop(scan);
+
+        assert_eq!(seen, ["a\u{308}", "b", "🇩🇪", "c", "\r\n", "d"]);
+        assert_eq!(text, "a\u{308}xyzc\nd");
+    }
+}
@@ -0,0 +1,103 @@
+use alloc::{collections::BinaryHeap, vec::Vec};
+use core::mem;
+
+use crate::VecMutScan;
+
+/// Scan the items of a binary heap in arbitrary order, restoring the heap property afterwards.
+///
+/// The closure is passed a [`VecMutScan`] over the heap's backing vector, which can be used to
+/// mutate or remove items in any way. Afterwards, the heap is rebuilt in `O(n)` once, instead of
+/// sifting after every removal. Returns the result of the closure.
+///
+/// If the closure panics, the heap is rebuilt from the items left in the vector at that point.
+///
+/// ```
+/// # use vec_mut_scan::scan_heap;
+/// use std::collections::BinaryHeap;
+///
+/// let mut timers: BinaryHeap<_> = vec![(5, "a"), (3, "stale"), (8, "b"), (1, "stale")].into();
+/// let pruned = scan_heap(&mut timers, |scan| {
+///     let mut pruned = 0;
+///     while let Some(item) = scan.next() {
+///         if item.1 == "stale" {
+///             item.remove();
+///             pruned += 1;
+///         }
+///     }
+///     pruned
+/// });
+///
+/// assert_eq!(pruned, 2);
+/// assert_eq!(timers.into_sorted_vec(), [(5, "a"), (8, "b")]);
+/// ```
+pub fn scan_heap<T: Ord, R>(
+    heap: &mut BinaryHeap<T>,
+    f: impl FnOnce(&mut VecMutScan<T>) -> R,
+) -> R {
+    let vec = mem::replace(heap, BinaryHeap::new()).into_vec();
+    let mut guard = RebuildOnDrop { heap, vec };
+    let mut scan = VecMutScan::new(&mut guard.vec);
+    f(&mut scan)
+}
+
+/// Moves the vector back into the heap when dropped, also when unwinding.
+struct RebuildOnDrop<'a, T: Ord> {
+    heap: &'a mut BinaryHeap<T>,
+    vec: Vec<T>,
+}
+
+impl<'a, T: Ord> Drop for RebuildOnDrop<'a, T> {
+    fn drop(&mut self) {
+        *self.heap = BinaryHeap::from(mem::replace(&mut self.vec, Vec::new()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prune_and_update() {
+        let mut heap: BinaryHeap<_> = (0..20).collect();
+
+        scan_heap(&mut heap, |scan| {
+            while let Some(mut item) = scan.next() {
+                if *item % 3 == 0 {
+                    item.remove();
+                } else if *item % 3 == 1 {
+                    *item += 100;
+                }
+            }
+        });
+
+        let mut expected: Vec<_> = (0..20)
+            .filter(|x| x % 3 != 0)
+            .map(|x| if x % 3 == 1 { x + 100 } else { x })
+            .collect();
+        expected.sort();
+        assert_eq!(heap.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn panic_keeps_items() {
+        let mut heap: BinaryHeap<_> = (0..10).collect();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            scan_heap(&mut heap, |scan| {
+                while let Some(mut item) = scan.next() {
+                    if *item == 5 {
+                        panic!("stop");
+                    }
+                    *item += 10;
+                    item.remove();
+                }
+            })
+        }));
+        assert!(result.is_err());
+
+        assert_eq!(heap.peek(), heap.iter().max());
+        assert!(heap.iter().all(|&x| x < 10));
+        assert!(heap.iter().any(|&x| x == 5));
+    }
+}
@@ -0,0 +1,241 @@
+use alloc::vec::Vec;
+
+use crate::{VecMutScan, VecMutScanItem};
+
+/// Resumable scan over a vector that processes a bounded number of items per call.
+///
+/// This keeps track of how far a scan over a vector has progressed, so that the scan can be
+/// performed in several steps, each visiting at most a given number of items. Between steps the
+/// vector is in a consistent state and can be used as usual. This is useful when a full pass over a
+/// large vector would take too long, e.g. within a single frame of a game or UI loop.
+///
+/// Only the position of the scan is stored, so the vector may be modified between steps. Items
+/// pushed to the vector in between will be visited by later steps. Any other modification shifts
+/// which items are considered visited.
+///
+/// ```
+/// # use vec_mut_scan::IncrementalScan;
+/// let mut numbers: Vec<_> = (0..10).collect();
+/// let mut incremental = IncrementalScan::new();
+///
+/// while !incremental.step(&mut numbers, 3, |item| {
+///     if *item % 2 == 0 {
+///         item.remove();
+///     }
+/// }) {
+///     // Between steps, `numbers` can be used as usual.
+///     assert!(numbers.len() <= 10);
+/// }
+///
+/// assert_eq!(numbers, [1, 3, 5, 7, 9]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct IncrementalScan {
+    position: usize,
+}
+
+impl IncrementalScan {
+    /// Create a new incremental scan, positioned at the start of the vector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The index of the next item to visit.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns `true` when all items of the given vector were visited.
+    pub fn is_done<T>(&self, vec: &[T]) -> bool {
+        self.position >= vec.len()
+    }
+
+    /// Restart the scan from the beginning of the vector.
+    pub fn reset(&mut self) {
+        self.position = 0;
+    }
+
+    /// Visit at most `budget` items of the vector, continuing where the previous step stopped.
+    ///
+    /// Each visited item is passed to `f` as a [`VecMutScanItem`], which can be used to mutate,
+    /// replace or remove it. Items that are neither removed nor replaced by `f` are kept.
+    ///
+    /// Returns `true` when all items of the vector were visited.
+    ///
+    /// Apart from visiting the items, a step that removes items has to move all remaining items of
+    /// the vector once to close the gap. Thus the cost of a step is not strictly bounded by the
+    /// budget, but the additional work consists of a single bulk memory move.
+    pub fn step<T>(
+        &mut self,
+        vec: &mut Vec<T>,
+        budget: usize,
+        mut f: impl FnMut(VecMutScanItem<T>),
+    ) -> bool {
+        let start = self.position.min(vec.len());
+        let mut scan = VecMutScan::start_at(vec, start);
+
+        for _ in 0..budget {
+            match scan.next() {
+                Some(item) => f(item),
+                None => break,
+            }
+        }
+
+        // After dropping the scan, the first unvisited item will be located right after the kept
+        // prefix.
+        self.position = scan.write;
+        let done = scan.read == scan.end;
+        drop(scan);
+        done
+    }
+}
+
+/// Owned vector with a stored predicate, that is cleaned up incrementally.
+///
+/// Each call to [`tick`][IncrementalCleaner::tick] visits at most a given number of items, removing
+/// those for which the predicate returns `false`, and continues where the previous tick stopped.
+/// After the last item, the next tick starts a new pass from the front. Thus every item is visited
+/// regularly, while the work of a single tick stays bounded, which makes this usable as an
+/// amortized garbage collection for soft real-time systems.
+///
+/// The vector can be accessed between ticks. The same caveats as for [`IncrementalScan`] apply:
+/// pushed items will be visited by the current pass, other modifications shift which items are
+/// considered visited in it.
+///
+/// ```
+/// # use vec_mut_scan::IncrementalCleaner;
+/// let mut cleaner = IncrementalCleaner::new((0..10).collect(), |item: &mut i32| *item % 3 != 0);
+///
+/// assert!(!cleaner.tick(4));
+/// assert_eq!(*cleaner.vec(), [1, 2, 4, 5, 6, 7, 8, 9]);
+///
+/// cleaner.vec_mut().push(12);
+/// while !cleaner.tick(4) {}
+/// assert_eq!(cleaner.into_inner(), [1, 2, 4, 5, 7, 8]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IncrementalCleaner<T, F> {
+    vec: Vec<T>,
+    keep: F,
+    scan: IncrementalScan,
+}
+
+impl<T, F: FnMut(&mut T) -> bool> IncrementalCleaner<T, F> {
+    /// Wrap a vector, retaining only the items for which `keep` returns `true`.
+    ///
+    /// The predicate can also mutate the items it keeps.
+    pub fn new(vec: Vec<T>, keep: F) -> Self {
+        IncrementalCleaner {
+            vec,
+            keep,
+            scan: IncrementalScan::new(),
+        }
+    }
+
+    /// Visit at most `budget` items, continuing the current pass.
+    ///
+    /// Returns `true` when this tick completed a pass over all items. The next tick then starts a
+    /// new pass from the front.
+    pub fn tick(&mut self, budget: usize) -> bool {
+        let keep = &mut self.keep;
+        let done = self.scan.step(&mut self.vec, budget, |mut item| {
+            if !keep(&mut item) {
+                item.remove();
+            }
+        });
+        if done {
+            self.scan.reset();
+        }
+        done
+    }
+
+    /// The index of the next item to visit in the current pass.
+    pub fn position(&self) -> usize {
+        self.scan.position()
+    }
+
+    /// The wrapped vector.
+    pub fn vec(&self) -> &Vec<T> {
+        &self.vec
+    }
+
+    /// The wrapped vector, for modification between ticks.
+    pub fn vec_mut(&mut self) -> &mut Vec<T> {
+        &mut self.vec
+    }
+
+    /// Unwraps the vector, without visiting any further items.
+    pub fn into_inner(self) -> Vec<T> {
+        self.vec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn steps_respect_budget() {
+        let mut input: Vec<_> = (0..10).collect();
+        let mut incremental = IncrementalScan::new();
+        let mut visited = vec![];
+
+        let mut steps = 0;
+        while !incremental.step(&mut input, 4, |item| {
+            visited.push(*item);
+            if *item % 3 == 0 {
+                item.remove();
+            }
+        }) {
+            steps += 1;
+            assert_eq!(visited.len(), steps * 4);
+        }
+
+        assert_eq!(visited, (0..10).collect::<Vec<_>>());
+        assert_eq!(input, [1, 2, 4, 5, 7, 8]);
+        assert!(incremental.is_done(&input));
+    }
+
+    #[test]
+    fn modification_between_steps() {
+        let mut input = vec![1, 2, 3, 4];
+        let mut incremental = IncrementalScan::new();
+
+        assert!(!incremental.step(&mut input, 2, |item| {
+            item.remove();
+        }));
+        assert_eq!(input, [3, 4]);
+        assert_eq!(incremental.position(), 0);
+
+        input.push(5);
+        input.truncate(0);
+        input.push(6);
+
+        assert!(incremental.step(&mut input, 2, |mut item| *item *= 2));
+        assert_eq!(input, [12]);
+    }
+
+    #[test]
+    fn cleaner_passes() {
+        let mut visits = 0;
+        let mut cleaner = IncrementalCleaner::new((0..10).collect(), |item: &mut i32| {
+            visits += 1;
+            *item -= 1;
+            *item > 0
+        });
+
+        let mut passes = 0;
+        for _ in 0..10 {
+            if cleaner.tick(3) {
+                passes += 1;
+                assert_eq!(cleaner.position(), 0);
+            }
+        }
+        // The passes take 4, 3 and 3 ticks, as each pass removes the items that reach zero.
+        assert_eq!(passes, 3);
+        assert_eq!(cleaner.into_inner(), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(visits, 10 + 8 + 7);
+    }
+}
@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+
+use crate::{VecMutScan, VecMutScanItem};
+
+/// Begin a scan visiting only the items at the given indices, with mutation and removal.
+///
+/// The indices must be strictly increasing and refer to positions within the vector as it was
+/// before the scan. The items in between are kept and are never visited, each run of them is moved
+/// over the gap left by removed items with a single move.
+///
+/// Items are visited by position, so an item moved by [`defer`][VecMutScanItem::defer] or
+/// [`swap_with_next`][VecMutScanItem::swap_with_next] is visited if it ends up at a given index.
+///
+/// This is useful when a cheaper pass already determined the few candidate items to edit.
+///
+/// ```
+/// # use vec_mut_scan::scan_by_indices;
+/// let mut values: Vec<u32> = (0..10).collect();
+/// let candidates = [1, 4, 7, 8];
+///
+/// let mut scan = scan_by_indices(&mut values, &candidates);
+/// while let Some((index, item)) = scan.next() {
+///     if index % 2 == 0 {
+///         item.remove();
+///     }
+/// }
+/// drop(scan);
+///
+/// assert_eq!(values, [0, 1, 2, 3, 5, 6, 7, 9]);
+/// ```
+pub fn scan_by_indices<'a, 'i, T: 'a>(
+    vec: &'a mut Vec<T>,
+    indices: &'i [usize],
+) -> IndexScan<'a, 'i, T> {
+    IndexScan {
+        scan: VecMutScan::new(vec),
+        indices,
+        min_index: 0,
+    }
+}
+
+/// Scan visiting only the items at a precomputed set of indices, see [`scan_by_indices`].
+///
+/// Dropping the scan keeps all items that were not removed.
+pub struct IndexScan<'a, 'i, T: 'a> {
+    scan: VecMutScan<'a, T>,
+    indices: &'i [usize],
+    min_index: usize,
+}
+
+impl<'a, 'i, T: 'a> IndexScan<'a, 'i, T> {
+    /// Advance to the item at the next index, keeping all items before it.
+    ///
+    /// Returns the item's original index together with a reference wrapper that enables item
+    /// removal (see [`VecMutScanItem`]).
+    ///
+    /// Panics if the next index is out of bounds or not larger than the previous one.
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<(usize, VecMutScanItem<'s, 'a, T>)> {
+        let (&index, rest) = self.indices.split_first()?;
+        self.indices = rest;
+        assert!(
+            index >= self.min_index,
+            "indices must be strictly increasing"
+        );
+        assert!(index < self.scan.end, "index out of bounds");
+        self.min_index = index + 1;
+        // The scan started at the vector's start and never inserts, so `read` is the position of
+        // the next item, which is at most the previous index plus one.
+        self.scan.keep_many(index - self.scan.read);
+        Some((index, self.scan.next().unwrap()))
+    }
+
+    /// The indices that were not visited yet.
+    pub fn remaining_indices(&self) -> &'i [usize] {
+        self.indices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec};
+
+    #[test]
+    fn visit_indices() {
+        let mut input: Vec<_> = (0..12).map(Rc::new).collect();
+        let input_copy = input.clone();
+        let indices = [0, 1, 5, 6, 9];
+
+        let mut scan = scan_by_indices(&mut input, &indices);
+        let mut seen = vec![];
+        while let Some((index, mut item)) = scan.next() {
+            seen.push(**item);
+            assert_eq!(index, **item);
+            match index {
+                0 | 5 | 6 => drop(item.remove()),
+                _ => *item = Rc::new(index * 10),
+            }
+            if index == 6 {
+                break;
+            }
+        }
+        assert_eq!(scan.remaining_indices(), [9]);
+        drop(scan);
+
+        assert_eq!(seen, [0, 1, 5, 6]);
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [10, 2, 3, 4, 7, 8, 9, 10, 11]);
+        drop(input_copy);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "indices must be strictly increasing")]
+    fn unsorted_indices() {
+        let mut input = vec![1, 2, 3];
+        let mut scan = scan_by_indices(&mut input, &[1, 1]);
+        scan.next();
+        scan.next();
+    }
+}
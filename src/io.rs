@@ -0,0 +1,91 @@
+use std::io;
+
+use crate::VecGrowScan;
+
+impl<'a> VecGrowScan<'a, u8> {
+    /// Returns a writer inserting all written bytes at the scan's insertion point.
+    ///
+    /// Written bytes are inserted like with [`insert_many`][VecGrowScan::insert_many], between the
+    /// visited bytes and the bytes that haven't been visited yet, so encoders and formatters can
+    /// write directly into the scanned buffer.
+    ///
+    /// Requires the `std` feature.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// use std::io::Write;
+    ///
+    /// // Expand every `%` into the current byte offset.
+    /// let mut bytes = b"a%b%".to_vec();
+    /// let mut scan = VecGrowScan::new(&mut bytes);
+    /// let mut offset = 0;
+    ///
+    /// loop {
+    ///     let byte = match scan.next() {
+    ///         Some(byte) if *byte == b'%' => byte.remove(),
+    ///         Some(_) => {
+    ///             offset += 1;
+    ///             continue;
+    ///         }
+    ///         None => break,
+    ///     };
+    ///     write!(scan.writer(), "<{}>", offset).unwrap();
+    ///     offset += 1;
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(bytes, b"a<1>b<3>");
+    /// ```
+    pub fn writer<'s>(&'s mut self) -> InsertWriter<'s, 'a> {
+        InsertWriter { scan: self }
+    }
+}
+
+/// Writer inserting bytes at the insertion point of a [`VecGrowScan`], see
+/// [`writer`][VecGrowScan::writer].
+///
+/// Writes never fail, except for running out of memory, which aborts like any other insertion.
+pub struct InsertWriter<'s, 'a> {
+    scan: &'s mut VecGrowScan<'a, u8>,
+}
+
+impl<'s, 'a> io::Write for InsertWriter<'s, 'a> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.scan.insert_many(buf.iter().copied());
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.scan.insert_many(buf.iter().copied());
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+    use io::Write;
+
+    #[test]
+    fn write_at_cursor() {
+        let mut bytes: Vec<u8> = (0..8).collect();
+        let mut scan = VecGrowScan::new(&mut bytes);
+
+        scan.next();
+        scan.writer().write_all(&[10, 11, 12]).unwrap();
+        scan.next().unwrap().remove();
+        let mut writer = scan.writer();
+        assert_eq!(writer.write(&[20]).unwrap(), 1);
+        writer.flush().unwrap();
+        scan.next();
+        drop(scan);
+
+        assert_eq!(bytes, [0, 10, 11, 12, 20, 2, 3, 4, 5, 6, 7]);
+    }
+}
@@ -0,0 +1,103 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{VecGrowScan, VecGrowScanItem};
+
+/// An item of a sorted join, passed to the callback of [`join_sorted`].
+pub enum JoinItem<'s, 'a, T: 'a, U> {
+    /// An item of the vector without an equal value in the iterator.
+    LeftOnly(VecGrowScanItem<'s, 'a, T>),
+    /// A value of the iterator without an equal item in the vector.
+    ///
+    /// Contains the scan, positioned where the value belongs, so it can be inserted using
+    /// [`VecGrowScan::insert`].
+    RightOnly(&'s mut VecGrowScan<'a, T>, U),
+    /// An item of the vector and an equal value of the iterator.
+    Both(VecGrowScanItem<'s, 'a, T>, U),
+}
+
+/// Walk a sorted vector and a sorted iterator together, passing matching items to `f`.
+///
+/// Both the vector and the iterator must be sorted consistently with `cmp`, which compares an item
+/// of the vector with a value of the iterator. Equal items are paired up one-to-one in order. The
+/// callback can keep, update or remove the vector items and insert the iterator values, which keeps
+/// the vector sorted. This covers merging, intersection and difference of sorted sequences.
+///
+/// ```
+/// # use vec_mut_scan::{join_sorted, JoinItem};
+/// // Reconcile a snapshot of `(id, version)` pairs with an updated snapshot.
+/// let mut snapshot = vec![(1, 1), (2, 4), (4, 1)];
+/// let update = vec![(2, 5), (3, 1), (4, 1)];
+///
+/// join_sorted(&mut snapshot, update, |x, y| x.0.cmp(&y.0), |item| match item {
+///     JoinItem::LeftOnly(item) => drop(item.remove()),
+///     JoinItem::RightOnly(scan, value) => drop(scan.insert(value)),
+///     JoinItem::Both(mut item, value) => *item = value,
+/// });
+///
+/// assert_eq!(snapshot, [(2, 5), (3, 1), (4, 1)]);
+/// ```
+pub fn join_sorted<'a, T: 'a, U>(
+    vec: &'a mut Vec<T>,
+    iter: impl IntoIterator<Item = U>,
+    mut cmp: impl FnMut(&T, &U) -> Ordering,
+    mut f: impl for<'s> FnMut(JoinItem<'s, 'a, T, U>),
+) {
+    let mut iter = iter.into_iter().peekable();
+    let mut scan = VecGrowScan::new(vec);
+    loop {
+        let order = match (scan.slices().3.first(), iter.peek()) {
+            (Some(item), Some(value)) => cmp(item, value),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => break,
+        };
+        match order {
+            Ordering::Less => f(JoinItem::LeftOnly(scan.next().unwrap())),
+            Ordering::Greater => f(JoinItem::RightOnly(&mut scan, iter.next().unwrap())),
+            Ordering::Equal => {
+                let value = iter.next().unwrap();
+                f(JoinItem::Both(scan.next().unwrap(), value))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn set_operations() {
+        let left = vec![1, 2, 2, 4, 6, 7];
+        let right = vec![0, 2, 3, 4, 4, 7, 9];
+
+        let mut union = left.clone();
+        join_sorted(&mut union, right.clone(), Ord::cmp, |item| {
+            if let JoinItem::RightOnly(scan, value) = item {
+                scan.insert(value);
+            }
+        });
+        assert_eq!(union, [0, 1, 2, 2, 3, 4, 4, 6, 7, 9]);
+
+        let mut intersection = left.clone();
+        join_sorted(&mut intersection, right.clone(), Ord::cmp, |item| {
+            if let JoinItem::LeftOnly(item) = item {
+                item.remove();
+            }
+        });
+        assert_eq!(intersection, [2, 4, 7]);
+
+        let mut difference = left;
+        let mut extra = vec![];
+        join_sorted(&mut difference, right, Ord::cmp, |item| match item {
+            JoinItem::LeftOnly(_) => (),
+            JoinItem::RightOnly(_, value) => extra.push(value),
+            JoinItem::Both(item, _) => drop(item.remove()),
+        });
+        assert_eq!(difference, [1, 2, 6]);
+        assert_eq!(extra, [0, 3, 4, 9]);
+    }
+}
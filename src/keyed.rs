@@ -0,0 +1,181 @@
+use alloc::vec::Vec;
+use core::mem;
+
+use crate::{VecGrowScan, VecGrowScanItem};
+
+/// Forward scan over a vector of key-value pairs sorted by key, with lookup, insertion and removal
+/// by key.
+///
+/// This is useful for flat sorted maps. All operations keep the vector sorted by key. As the scan
+/// only moves forward, operations on a key first skip all pairs with a smaller key, which are then
+/// considered visited. Keys smaller than the key of the last visited pair can't be accessed
+/// anymore.
+///
+/// ```
+/// # use vec_mut_scan::KeyedScan;
+/// let mut map = vec![(1, "a"), (3, "c"), (4, "d"), (7, "g")];
+/// let mut scan = KeyedScan::new(&mut map);
+///
+/// assert_eq!(scan.insert_pair_sorted(2, "b"), None);
+/// assert_eq!(scan.remove_key(&4), Some("d"));
+/// assert_eq!(scan.insert_pair_sorted(7, "G"), Some("g"));
+/// assert_eq!(scan.remove_key(&8), None);
+/// drop(scan);
+///
+/// assert_eq!(map, [(1, "a"), (2, "b"), (3, "c"), (7, "G")]);
+/// ```
+pub struct KeyedScan<'a, K: 'a, V: 'a> {
+    scan: VecGrowScan<'a, (K, V)>,
+}
+
+impl<'a, K: Ord + 'a, V: 'a> KeyedScan<'a, K, V> {
+    /// Begin a scan over a vector of key-value pairs sorted by key.
+    ///
+    /// The vector must be sorted by key. Otherwise the results of the key based operations are
+    /// unspecified.
+    pub fn new(vec: &'a mut Vec<(K, V)>) -> Self {
+        KeyedScan {
+            scan: VecGrowScan::new(vec),
+        }
+    }
+
+    /// Advance to the next pair.
+    ///
+    /// This returns a reference wrapper that enables mutation of the value and removal of the pair
+    /// (see [`KeyedScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<KeyedScanItem<'s, 'a, K, V>> {
+        self.scan.next().map(|item| KeyedScanItem { item })
+    }
+
+    /// Skip all remaining pairs with a key smaller than `key`.
+    ///
+    /// Returns a mutable reference to the value of the next pair, if it has the given key.
+    pub fn seek_key(&mut self, key: &K) -> Option<&mut V> {
+        let remaining = self.scan.slices().3;
+        let skip = match remaining.binary_search_by(|pair| pair.0.cmp(key)) {
+            Ok(index) | Err(index) => index,
+        };
+        for _ in 0..skip {
+            self.scan.next();
+        }
+        match self.scan.slices_mut().3.first_mut() {
+            Some(pair) if pair.0 == *key => Some(&mut pair.1),
+            _ => None,
+        }
+    }
+
+    /// Remove the pair with the given key, returning its value.
+    ///
+    /// Skips all remaining pairs with a smaller key, see [`seek_key`][KeyedScan::seek_key].
+    pub fn remove_key(&mut self, key: &K) -> Option<V> {
+        self.seek_key(key)?;
+        Some(self.scan.next().unwrap().remove().1)
+    }
+
+    /// Insert a pair at the position given by its key, returning the previous value for that key.
+    ///
+    /// Skips all remaining pairs with a smaller key, see [`seek_key`][KeyedScan::seek_key].
+    ///
+    /// Panics if `key` is not larger than the key of the last visited pair, as the pair can't be
+    /// inserted in sorted order then.
+    pub fn insert_pair_sorted(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.seek_key(&key) {
+            return Some(mem::replace(old, value));
+        }
+        let (visited, queued_front, queued_back, _) = self.scan.slices();
+        let last = queued_back
+            .last()
+            .or_else(|| queued_front.last())
+            .or_else(|| visited.last());
+        if let Some(last) = last {
+            assert!(last.0 < key, "key is not larger than the last visited key");
+        }
+        self.scan.insert((key, value));
+        None
+    }
+}
+
+/// Reference wrapper that enables value mutation and pair removal for [`KeyedScan`].
+///
+/// Dropping the wrapper keeps the pair.
+pub struct KeyedScanItem<'s, 'a, K: 'a, V: 'a> {
+    item: VecGrowScanItem<'s, 'a, (K, V)>,
+}
+
+impl<'s, 'a, K: 'a, V: 'a> KeyedScanItem<'s, 'a, K, V> {
+    /// The key of this pair.
+    pub fn key(&self) -> &K {
+        &self.item.0
+    }
+
+    /// The value of this pair.
+    pub fn value(&self) -> &V {
+        &self.item.1
+    }
+
+    /// The value of this pair, for mutation.
+    pub fn value_mut(&mut self) -> &mut V {
+        &mut self.item.1
+    }
+
+    /// Removes and returns this pair from the vector.
+    pub fn remove(self) -> (K, V) {
+        self.item.remove()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn keyed_operations() {
+        let mut input: Vec<_> = (0..10).map(|key| (key * 2, key)).collect();
+        let mut scan = KeyedScan::new(&mut input);
+
+        assert_eq!(scan.insert_pair_sorted(-1, 100), None);
+        *scan.seek_key(&4).unwrap() += 10;
+        assert_eq!(scan.seek_key(&5), None);
+        let mut item = scan.next().unwrap();
+        assert_eq!((*item.key(), *item.value()), (6, 3));
+        *item.value_mut() = 30;
+        drop(item);
+        assert_eq!(scan.remove_key(&8), Some(4));
+        assert_eq!(scan.remove_key(&8), None);
+        assert_eq!(scan.insert_pair_sorted(9, 101), None);
+        assert_eq!(scan.next().unwrap().remove(), (10, 5));
+        assert_eq!(scan.insert_pair_sorted(19, 102), None);
+        assert_eq!(scan.insert_pair_sorted(30, 103), None);
+        drop(scan);
+
+        assert_eq!(
+            input,
+            [
+                (-1, 100),
+                (0, 0),
+                (2, 1),
+                (4, 12),
+                (6, 30),
+                (9, 101),
+                (12, 6),
+                (14, 7),
+                (16, 8),
+                (18, 9),
+                (19, 102),
+                (30, 103)
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "key is not larger than the last visited key")]
+    fn insert_before_visited() {
+        let mut input = vec![(1, ()), (2, ()), (3, ())];
+        let mut scan = KeyedScan::new(&mut input);
+        scan.seek_key(&3);
+        scan.insert_pair_sorted(0, ());
+    }
+}
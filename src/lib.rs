@@ -1,11 +1,167 @@
 //! Forward scan over a vector with mutation and item removal.
 #![no_std]
+#![cfg_attr(feature = "nightly", feature(specialization))]
+#![cfg_attr(feature = "nightly", allow(incomplete_features))]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+// Allows the derive macro's output to refer to this crate within its own tests.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as vec_mut_scan;
 
-use alloc::{collections::VecDeque, vec::Vec};
+mod back;
+mod batch;
+#[cfg(feature = "bitvec")]
+mod bit_vec;
+#[cfg(feature = "bytes")]
+mod bytes_mut;
+mod chain;
+mod coalesce;
+mod cow;
+mod dedup;
+mod deque;
+mod double_buffer;
+mod driver;
+mod entry;
+mod error;
+mod fallible;
+mod forward;
+mod gap_buffer;
+mod gap_string;
+#[cfg(feature = "unicode-segmentation")]
+mod grapheme;
+mod heap;
+mod incremental;
+mod indices;
+#[cfg(feature = "std")]
+mod io;
+mod join;
+mod keyed;
+mod limit;
+mod logical;
+mod partition;
+mod peephole;
+mod pipeline;
+#[cfg(feature = "bytemuck")]
+mod record;
+mod retain;
+mod rev;
+mod rewrite;
+mod rle;
+#[cfg(feature = "rand")]
+mod sample;
+mod scannable;
+mod scratch;
+mod segments;
+mod sink;
+#[cfg(feature = "nightly")]
+mod specialize;
+mod split;
+mod string;
+mod suspend;
+mod sync;
+#[cfg(feature = "test-util")]
+pub mod testing;
+#[cfg(feature = "bytemuck")]
+mod transparent;
+#[cfg(feature = "try-reserve")]
+#[allow(clippy::incompatible_msrv)] // the `try-reserve` feature requires Rust 1.57
+mod try_reserve;
+mod uninit;
+mod window;
+
+pub use back::VecMutScanBackItem;
+pub use batch::VecMutScanBatch;
+#[cfg(feature = "bitvec")]
+pub use bit_vec::{BitMut, BitVecScan, BitVecScanItem};
+pub use chain::{ChainScan, ChainScanItem};
+pub use coalesce::{coalesce, coalesce_ranges};
+pub use cow::{CloneOnWrite, CowScan, CowScanItem};
+pub use dedup::{dedup_by_keep_last, dedup_keep_last};
+#[cfg(feature = "std")]
+pub use dedup::{dedup_unsorted, dedup_unsorted_into};
+pub use deque::{VecDequeMutScan, VecDequeMutScanItem};
+pub use double_buffer::{DoubleBufferScan, DoubleBufferScanItem};
+pub use driver::{drive, ScanAction, ScanVisitor};
+pub use entry::Entry;
+pub use error::ScanError;
+pub use gap_buffer::GapBuffer;
+pub use gap_string::GapString;
+#[cfg(feature = "unicode-segmentation")]
+pub use grapheme::{GraphemeScan, GraphemeScanItem};
+pub use heap::scan_heap;
+pub use incremental::{IncrementalCleaner, IncrementalScan};
+pub use indices::{scan_by_indices, IndexScan};
+#[cfg(feature = "std")]
+pub use io::InsertWriter;
+pub use join::{join_sorted, JoinItem};
+pub use keyed::{KeyedScan, KeyedScanItem};
+pub use limit::{VecMutScanStepBy, VecMutScanTake};
+pub use partition::partition_n;
+pub use peephole::peephole;
+#[cfg(feature = "const-generics")]
+pub use peephole::peephole_array;
+pub use pipeline::Pipeline;
+#[cfg(feature = "bytemuck")]
+pub use record::{RecordScan, RecordScanItem};
+pub use retain::{
+    retain_adaptive, retain_enumerated, retain_enumerated_with_output, retain_max_by_key,
+    retain_top_k, retain_within_budget, retain_within_budget_into, thin, thin_to_fraction,
+    RetainStrategy,
+};
+pub use rev::{VecGrowRevScan, VecGrowRevScanItem, VecMutScanRev, VecMutScanRevItem};
+pub use rewrite::Rewriter;
+pub use rle::{rle_compress, rle_expand};
+#[cfg(feature = "rand")]
+pub use sample::{retain_sample, retain_sample_exact};
+pub use scannable::Scannable;
+pub use scratch::{ScratchGrowScan, ScratchGrowScanItem};
+pub use segments::{SegmentScan, SegmentScanItem};
+pub use sink::{group_into, partition_map, retain_fold, retain_into, PartitionMap, Sink};
+pub use split::SplitScans;
+pub use string::{StringGrowScan, StringGrowScanItem};
+pub use suspend::SuspendedScan;
+pub use sync::{sync_to, sync_to_with};
+/// Derive [`Scannable`] for a newtype wrapping a vector.
+///
+/// The struct must have a single field or mark the wrapped field with `#[scannable]`. That field
+/// must be a `Vec<T>` or another type implementing [`Scannable`].
+///
+/// Requires the `derive` feature, which needs Rust 1.61 or later.
+///
+/// ```
+/// # use vec_mut_scan::Scannable;
+/// #[derive(Scannable)]
+/// struct Instructions(Vec<u8>);
+///
+/// let mut code = Instructions(vec![1, 0, 2, 0]);
+/// let mut scan = code.grow_scan();
+/// while let Some(insn) = scan.next() {
+///     if *insn == 0 {
+///         insn.remove();
+///     }
+/// }
+/// drop(scan);
+///
+/// assert_eq!(code.0, [1, 2]);
+/// ```
+#[cfg(feature = "derive")]
+pub use vec_mut_scan_derive::Scannable;
+pub use window::{for_each_window, WindowAction};
+
+#[doc(hidden)]
+#[cfg(feature = "derive")]
+pub mod __private {
+    pub use alloc::vec::Vec;
+}
+
+#[cfg(feature = "try-reserve")]
+use alloc::collections::TryReserveError;
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
 use core::{
-    mem,
+    cmp,
+    mem::{self, ManuallyDrop},
     ops::{Deref, DerefMut},
     ptr,
 };
@@ -19,6 +175,8 @@ use core::{
 /// Items are kept in order and every item is moved at most once, even when items are removed.
 /// Dropping the `VecMutScan` mid-iteration keeps remaining items in the vector.
 ///
+/// Like a `&mut Vec<T>`, a `VecMutScan` is `Send` if `T` is `Send` and `Sync` if `T` is `Sync`.
+///
 /// This does not implement the iterator trait, as the returned items borrow from this (i.e. this is
 /// a streaming iterator).
 ///
@@ -26,11 +184,151 @@ use core::{
 /// to the vector's item type but also provide a [`remove`](VecMutScanItem::remove) and
 /// [`replace`](VecMutScanItem::replace) method.
 pub struct VecMutScan<'a, T: 'a> {
-    vec: &'a mut Vec<T>,
+    storage: &'a mut dyn SetLen,
     base: *mut T,
     write: usize,
     read: usize,
     end: usize,
+    // Items visited from the back using `next_back` and kept are at `end..len`.
+    len: usize,
+    #[cfg(debug_assertions)]
+    check: Option<CheckFn<'a, T>>,
+}
+
+#[cfg(debug_assertions)]
+type CheckFn<'a, T> = Box<dyn FnMut(&[T], &[T]) -> bool + Send + 'a>;
+
+/// Owner of the items of a scan, which takes them back when the scan is dropped.
+///
+/// This is a `Vec<T>` for scans over a whole vector and a plain length for scans over a part of a
+/// vector (see [`VecMutScan::split_into_scans`]). With the `bytes` feature, this can also be a
+/// `BytesMut`.
+trait SetLen {
+    /// Safety: see [`Vec::set_len`].
+    unsafe fn set_len(&mut self, len: usize);
+}
+
+impl<T> SetLen for Vec<T> {
+    unsafe fn set_len(&mut self, len: usize) {
+        Vec::set_len(self, len)
+    }
+}
+
+impl SetLen for usize {
+    unsafe fn set_len(&mut self, len: usize) {
+        *self = len
+    }
+}
+
+// A `VecMutScan` behaves like a `&mut Vec<T>` when it comes to thread safety. It owns the items it
+// scans, so it can be sent to another thread whenever the items can. The storage is a `Vec<T>`, a
+// `usize` or a `BytesMut`, which is `Send` in that case, too, and the invariant check closure is
+// required to be `Send`. Shared references only give access to the items via `slices`, so it is
+// `Sync` whenever the items are. The item wrappers follow automatically, as they consist of a
+// mutable reference to the scan.
+unsafe impl<'a, T: Send> Send for VecMutScan<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for VecMutScan<'a, T> {}
+
+/// Owner of the items of a [`VecGrowScan`], which takes them back when the scan is dropped.
+///
+/// This is a `Vec<T>` or, with the `bytes` feature, a `BytesMut`.
+trait GrowStorage<T>: SetLen {
+    /// Inserts all items of `queue` at `index`, shifting all following items. The queue is left
+    /// empty, keeping its capacity.
+    fn splice_queue(&mut self, index: usize, queue: &mut VecDeque<T>);
+
+    /// Moves all items of `tail` to the end.
+    fn append(&mut self, tail: &mut Vec<T>);
+
+    /// Makes room for `additional` items after the first `len` items and returns the start of the
+    /// buffer, which may have moved. The length is left at zero.
+    ///
+    /// Safety: the length must be zero and the first `len` items of the buffer must be valid.
+    unsafe fn reserve_items(&mut self, len: usize, additional: usize) -> *mut T;
+
+    /// The number of items the buffer can hold without reallocating.
+    fn capacity(&self) -> usize;
+
+    /// Like `reserve_items`, but returns an error instead of aborting when allocation fails.
+    ///
+    /// Safety: see `reserve_items`.
+    #[cfg(feature = "try-reserve")]
+    #[allow(clippy::incompatible_msrv)]
+    unsafe fn try_reserve_items(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<*mut T, TryReserveError>;
+}
+
+impl<T> GrowStorage<T> for Vec<T> {
+    fn splice_queue(&mut self, index: usize, queue: &mut VecDeque<T>) {
+        // Unlike `Vec::splice`, this moves every item at most once. Converting the queue only moves
+        // items when the queue's buffer wrapped around.
+        let mut items = Vec::from(mem::replace(queue, VecDeque::new()));
+        let len = self.len();
+        let count = items.len();
+        self.reserve(count);
+        unsafe {
+            // After reserving, the buffer has room for `len + count` items, so we can move the items
+            // `index..len` back to make room for the queued items.
+            let base = self.as_mut_ptr();
+            ptr::copy(base.add(index), base.add(index + count), len - index);
+            ptr::copy_nonoverlapping(items.as_ptr(), base.add(index), count);
+            // The queued items were moved out, so `items` must not drop them. All items
+            // `0..len + count` are valid now.
+            items.set_len(0);
+            self.set_len(len + count);
+        }
+        // Hand back the buffer, so that it can be reused.
+        *queue = VecDeque::from(items);
+    }
+
+    fn append(&mut self, tail: &mut Vec<T>) {
+        Vec::append(self, tail)
+    }
+
+    fn capacity(&self) -> usize {
+        Vec::capacity(self)
+    }
+
+    unsafe fn reserve_items(&mut self, len: usize, additional: usize) -> *mut T {
+        // Should reserving panic, the items are kept by the vector.
+        self.set_len(len);
+        self.reserve(additional);
+        self.set_len(0);
+        self.as_mut_ptr()
+    }
+
+    #[cfg(feature = "try-reserve")]
+    #[allow(clippy::incompatible_msrv)]
+    unsafe fn try_reserve_items(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<*mut T, TryReserveError> {
+        self.set_len(len);
+        let result = self.try_reserve(additional);
+        self.set_len(0);
+        result.map(|()| self.as_mut_ptr())
+    }
+}
+
+/// Move the `len` items at offset `read` to offset `write`, closing the gap in between.
+///
+/// Safety: `write <= read` and both ranges must be within the buffer starting at `base`, as for
+/// `ptr::copy`.
+unsafe fn close_gap<T>(base: *mut T, write: usize, read: usize, len: usize) {
+    if write == read {
+        return;
+    }
+    if read - write >= len {
+        // The gap is at least as large as the moved items, so source and destination don't
+        // overlap. This is common when a scan over a large vector removed many items.
+        ptr::copy_nonoverlapping(base.add(read), base.add(write), len);
+    } else {
+        ptr::copy(base.add(read), base.add(write), len);
+    }
 }
 
 // Here is a small overview of how this is implemented, which should aid in auditing this library's
@@ -87,27 +385,67 @@ pub struct VecMutScan<'a, T: 'a> {
 
 impl<'a, T: 'a> VecMutScan<'a, T> {
     /// Begin a scan over a vector with mutation and item removal.
-    pub fn new(vec: &mut Vec<T>) -> VecMutScan<T> {
+    pub fn new(vec: &mut Vec<T>) -> VecMutScan<'_, T> {
+        Self::start_at(vec, 0)
+    }
+
+    /// Begin a scan over a vector with mutation and item removal, starting at a given index.
+    ///
+    /// The items `0..start` are treated as already visited and kept, without being moved or even
+    /// touched. The first call to [`next`][VecMutScan::next] returns the item at index `start`.
+    ///
+    /// Panics if `start` is larger than the vector's length.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers = vec![1, 2, 3, 4, 5, 6];
+    /// let mut scan = VecMutScan::start_at(&mut numbers, 3);
+    ///
+    /// while let Some(item) = scan.next() {
+    ///     if *item % 2 == 0 {
+    ///         item.remove();
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 2, 3, 5]);
+    /// ```
+    pub fn start_at(vec: &mut Vec<T>, start: usize) -> VecMutScan<'_, T> {
+        assert!(start <= vec.len(), "start index out of bounds");
         let base = vec.as_mut_ptr();
-        let write = 0;
-        let read = 0;
         let end = vec.len();
 
-        // Make sure `vec` is in a consistent state should this `VecMutScan` be leaked. In that case
-        // all items within `vec` are also leaked, which is safe. This strategy is also called leak
-        // amplification. This can be seen as the `VecMustScan` taking ownership over `vec`'s items,
-        // while still keeping them in `vec`'s buffer. As we keep a mutable reference to the `vec`
-        // we stop others from messing with its items.
-        unsafe {
-            vec.set_len(0);
-        }
+        // The items `0..end` are valid and owned by `vec`.
+        unsafe { VecMutScan::from_raw_parts(vec, base, start, end) }
+    }
+
+    /// Begin a scan over the items `base..base + end` owned by `storage`, starting at `start`.
+    ///
+    /// Safety: `base..base + end` must contain valid items owned by `storage` and `start` must not
+    /// exceed `end`. When the scan is dropped, `storage` takes back ownership of the items, which
+    /// then are contiguous, starting at `base`.
+    unsafe fn from_raw_parts(
+        storage: &'a mut dyn SetLen,
+        base: *mut T,
+        start: usize,
+        end: usize,
+    ) -> VecMutScan<'a, T> {
+        // Make sure `storage` is in a consistent state should this `VecMutScan` be leaked. In that
+        // case all items within `storage` are also leaked, which is safe. This strategy is also
+        // called leak amplification. This can be seen as the `VecMustScan` taking ownership over
+        // `storage`'s items, while still keeping them in `storage`'s buffer. As we keep a mutable
+        // reference to the `storage` we stop others from messing with its items.
+        storage.set_len(0);
 
         VecMutScan {
-            vec,
+            storage,
             base,
-            write,
-            read,
+            write: start,
+            read: start,
             end,
+            len: end,
+            #[cfg(debug_assertions)]
+            check: None,
         }
     }
 
@@ -134,15 +472,19 @@ impl<'a, T: 'a> VecMutScan<'a, T> {
     /// remaining elements starting with the element that will be returned by the following
     /// [`next`][VecMutScan::next] call.
     ///
+    /// The suffix ends with the kept items visited using [`next_back`][VecMutScan::next_back], see
+    /// [`remaining`][VecMutScan::remaining] and [`visited_back`][VecMutScan::visited_back] to tell
+    /// them apart.
+    ///
     /// This method is also present on the [`VecMutScanItem`] reference wrapper returned by
     /// [`next`][VecMutScan::next], allowing access while that wrapper borrows this `VecMutScan`.
     pub fn slices(&self) -> (&[T], &[T]) {
         unsafe {
-            // These slices cover the two disjoint parts 0..write and read..end which contain the
+            // These slices cover the two disjoint parts 0..write and read..len which contain the
             // currently valid data.
             (
                 core::slice::from_raw_parts(self.base, self.write),
-                core::slice::from_raw_parts(self.base.add(self.read), self.end - self.read),
+                core::slice::from_raw_parts(self.base.add(self.read), self.len - self.read),
             )
         }
     }
@@ -155,18 +497,375 @@ impl<'a, T: 'a> VecMutScan<'a, T> {
     /// contains the remaining elements starting with the element that will be returned by the
     /// following [`next`][VecMutScan::next] call.
     ///
+    /// As with [`slices`][VecMutScan::slices], the suffix ends with the kept items visited using
+    /// [`next_back`][VecMutScan::next_back].
+    ///
     /// This method is also present on the [`VecMutScanItem`] reference wrapper returned by
     /// [`next`][VecMutScan::next], allowing access while that wrapper borrows this `VecMutScan`.
     pub fn slices_mut(&mut self) -> (&mut [T], &mut [T]) {
         unsafe {
-            // These slices cover the two disjoint parts 0..write and read..end which contain the
+            // These slices cover the two disjoint parts 0..write and read..len which contain the
             // currently valid data.
             (
                 core::slice::from_raw_parts_mut(self.base, self.write),
-                core::slice::from_raw_parts_mut(self.base.add(self.read), self.end - self.read),
+                core::slice::from_raw_parts_mut(self.base.add(self.read), self.len - self.read),
             )
         }
     }
+
+    /// Swap the remaining items at the indices `i` and `j`, relative to the next item.
+    ///
+    /// Index zero refers to the item returned by the following [`next`][VecMutScan::next] call.
+    /// The scan's position is not changed.
+    ///
+    /// Panics if `i` or `j` is out of bounds.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut tasks = vec!["a", "b", "c", "d"];
+    /// let mut scan = VecMutScan::new(&mut tasks);
+    ///
+    /// assert_eq!(*scan.next().unwrap(), "a");
+    /// // Run "d" next.
+    /// scan.swap_remaining(0, 2);
+    /// assert_eq!(*scan.next().unwrap(), "d");
+    /// drop(scan);
+    ///
+    /// assert_eq!(tasks, ["a", "d", "c", "b"]);
+    /// ```
+    pub fn swap_remaining(&mut self, i: usize, j: usize) {
+        self.remaining_mut().swap(i, j);
+    }
+
+    /// Clone the items the vector would contain if the scan were dropped now.
+    ///
+    /// These are the visited items that were kept followed by the remaining items, i.e. the
+    /// concatenation of the [`slices`][VecMutScan::slices]. The scan is not changed.
+    pub fn snapshot(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let (visited, remaining) = self.slices();
+        let mut items = Vec::with_capacity(visited.len() + remaining.len());
+        items.extend_from_slice(visited);
+        items.extend_from_slice(remaining);
+        items
+    }
+
+    /// The number of removed items whose place hasn't been reused yet.
+    ///
+    /// This is the size of the gap between the visited and the remaining items, which is closed
+    /// by moving the remaining items when the scan is dropped. Items removed using
+    /// [`next_back`][VecMutScan::next_back] leave no gap.
+    pub fn gap_len(&self) -> usize {
+        self.read - self.write
+    }
+
+    /// Remove the longest prefix of the remaining items that all satisfy `pred` and return them.
+    ///
+    /// The removed items are moved into the returned vector using a single bulk copy. Afterwards
+    /// the scan continues with the first item not satisfying `pred`.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers = vec![1, 2, 3, 10, 4, 20];
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    /// scan.next();
+    /// let small = scan.take_matching_prefix(|x| *x < 10);
+    /// assert_eq!(*scan.next().unwrap(), 10);
+    /// drop(scan);
+    ///
+    /// assert_eq!(small, [2, 3]);
+    /// assert_eq!(numbers, [1, 10, 4, 20]);
+    /// ```
+    pub fn take_matching_prefix(&mut self, mut pred: impl FnMut(&T) -> bool) -> Vec<T> {
+        let count = self
+            .remaining()
+            .iter()
+            .take_while(|item| pred(item))
+            .count();
+        let mut taken = Vec::with_capacity(count);
+        unsafe {
+            // The items `read..read + count` are valid and we take ownership of them by adjusting
+            // the read position.
+            ptr::copy_nonoverlapping(self.base.add(self.read), taken.as_mut_ptr(), count);
+            self.read += count;
+            taken.set_len(count);
+        }
+        if count > 0 {
+            self.run_check("removing items", self.read - count);
+        }
+        taken
+    }
+
+    /// Move all remaining items to the end of `target` and end the scan, keeping the visited items.
+    ///
+    /// Room for the items is reserved at once and they are moved with a single bulk copy, so an
+    /// existing allocation of `target` is reused.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers = vec![1, 2, 3, 4];
+    /// let mut rest = Vec::with_capacity(8);
+    /// rest.push(0);
+    ///
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    /// scan.next().unwrap().remove();
+    /// scan.next();
+    /// scan.remove_rest_into(&mut rest);
+    ///
+    /// assert_eq!(numbers, [2]);
+    /// assert_eq!(rest, [0, 3, 4]);
+    /// ```
+    pub fn remove_rest_into(mut self, target: &mut Vec<T>) {
+        let count = self.end - self.read;
+        target.reserve(count);
+        unsafe {
+            // The items `read..end` are valid and we take ownership of them by adjusting the read
+            // position, after which dropping the scan only closes the gap.
+            let len = target.len();
+            ptr::copy_nonoverlapping(
+                self.base.add(self.read),
+                target.as_mut_ptr().add(len),
+                count,
+            );
+            self.read = self.end;
+            target.set_len(len + count);
+        }
+    }
+
+    /// Visit all remaining items, keeping those for which `f` returns `true`, while folding an
+    /// accumulator.
+    ///
+    /// Starting with `init`, `f` is called with the accumulator and each remaining item in turn. It
+    /// returns the new accumulator and whether to keep the item. Returns the final accumulator.
+    ///
+    /// This is equivalent to a loop using [`next`][VecMutScan::next], but uses internal iteration,
+    /// which is easier to optimize: The loop is split into a phase before the first removal, where
+    /// kept items are not touched at all, and a phase where every kept item is moved across the
+    /// gap, without checking for a gap for every item.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers = vec![1, 2, 3, 4, 5, 6];
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    /// let removed_sum = scan.fold_retain(0, |sum, x| {
+    ///     if *x % 3 == 0 {
+    ///         (sum + *x, false)
+    ///     } else {
+    ///         (sum, true)
+    ///     }
+    /// });
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 2, 4, 5]);
+    /// assert_eq!(removed_sum, 9);
+    /// ```
+    ///
+    /// With the `nightly` feature, `Copy` items use a simpler loop, which copies every kept item
+    /// unconditionally and updates the scan's state only once at the end.
+    pub fn fold_retain<B>(&mut self, init: B, f: impl FnMut(B, &mut T) -> (B, bool)) -> B {
+        #[cfg(feature = "nightly")]
+        {
+            specialize::SpecFoldRetain::spec_fold_retain(self, init, f)
+        }
+        #[cfg(not(feature = "nightly"))]
+        {
+            self.fold_retain_generic(init, f)
+        }
+    }
+
+    /// The implementation of [`fold_retain`][VecMutScan::fold_retain] for any item type.
+    fn fold_retain_generic<B>(&mut self, init: B, mut f: impl FnMut(B, &mut T) -> (B, bool)) -> B {
+        let mut acc = init;
+
+        // As long as there is no gap, kept items stay where they are.
+        while self.write == self.read && self.read != self.end {
+            let (next_acc, keep) = f(acc, unsafe { &mut *self.base.add(self.read) });
+            acc = next_acc;
+            // Adjust the positions before dropping a removed item, so a panicking drop leaves us
+            // in a consistent state.
+            self.read += 1;
+            if keep {
+                self.write += 1;
+                self.run_check("keeping an item", self.read - 1);
+            } else {
+                unsafe { ptr::drop_in_place(self.base.add(self.read - 1)) };
+                self.run_check("removing an item", self.read - 1);
+            }
+        }
+
+        // Once there is a gap, every kept item is moved across it. As `write < read`, the source
+        // and destination never overlap.
+        while self.read != self.end {
+            let (next_acc, keep) = f(acc, unsafe { &mut *self.base.add(self.read) });
+            acc = next_acc;
+            self.read += 1;
+            if keep {
+                unsafe {
+                    ptr::copy_nonoverlapping(
+                        self.base.add(self.read - 1),
+                        self.base.add(self.write),
+                        1,
+                    )
+                };
+                self.write += 1;
+                self.run_check("keeping an item", self.read - 1);
+            } else {
+                unsafe { ptr::drop_in_place(self.base.add(self.read - 1)) };
+                self.run_check("removing an item", self.read - 1);
+            }
+        }
+
+        acc
+    }
+
+    /// Visit all remaining items, keeping only those for which `f` returns `true`.
+    ///
+    /// This uses internal iteration, see [`fold_retain`][VecMutScan::fold_retain].
+    pub fn retain_rest(&mut self, mut f: impl FnMut(&mut T) -> bool) {
+        self.fold_retain((), |(), item| ((), f(item)))
+    }
+
+    /// Keep only the remaining items for which `f` returns `true` and end the scan.
+    ///
+    /// This is the same as calling [`retain_rest`][VecMutScan::retain_rest] followed by dropping the
+    /// scan.
+    pub fn finish_retain(mut self, f: impl FnMut(&mut T) -> bool) {
+        self.retain_rest(f)
+    }
+
+    /// Insert items into the gap left by removed items, without growing the vector.
+    ///
+    /// Items are taken from `iter` and placed after the visited items, until the iterator is
+    /// exhausted or the gap is filled. Inserted items are not returned during iteration. Returns
+    /// the number of inserted items. Once the gap is filled, no further items are taken from the
+    /// iterator.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers = vec![1, 2, 3, 4];
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    /// scan.next().unwrap().remove();
+    /// scan.next().unwrap().remove();
+    ///
+    /// let mut replacements = vec![7, 8, 9].into_iter();
+    /// assert_eq!(scan.fill_gap_with(&mut replacements), 2);
+    /// assert_eq!(replacements.next(), Some(9));
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [7, 8, 3, 4]);
+    /// ```
+    pub fn fill_gap_with(&mut self, iter: impl IntoIterator<Item = T>) -> usize {
+        let mut iter = iter.into_iter();
+        let start = self.write;
+        while self.write < self.read {
+            match iter.next() {
+                Some(item) => {
+                    // The gap `write..read` is uninitialized and within the buffer.
+                    unsafe { ptr::write(self.base.add(self.write), item) };
+                    self.write += 1;
+                }
+                None => break,
+            }
+        }
+        let count = self.write - start;
+        if count > 0 {
+            self.run_check("inserting items", self.read);
+        }
+        count
+    }
+
+    /// Register a closure that checks an invariant after every operation of this scan.
+    ///
+    /// The closure is called with the prefix and suffix slices (as returned by
+    /// [`slices`][VecMutScan::slices]) whenever an item is kept, removed or replaced, as well as
+    /// once when registering it. If the closure returns `false`, the scan panics with a message
+    /// naming the operation and the index of the item involved within the vector's buffer.
+    ///
+    /// This is a debugging aid and only has an effect when debug assertions are enabled. Otherwise
+    /// the closure is dropped without ever being called. The closure has to be `Send`, so that the
+    /// scan can still be sent to other threads.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers = vec![1, 2, 3, 4];
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    /// scan.set_invariant_check(|prefix, _| prefix.windows(2).all(|w| w[0] <= w[1]));
+    ///
+    /// while let Some(item) = scan.next() {
+    ///     if *item == 3 {
+    ///         // Replacing with 0 instead would panic when debug assertions are enabled.
+    ///         item.replace(2);
+    ///     }
+    /// }
+    /// ```
+    pub fn set_invariant_check(&mut self, check: impl FnMut(&[T], &[T]) -> bool + Send + 'a) {
+        #[cfg(debug_assertions)]
+        {
+            self.check = Some(Box::new(check));
+            self.run_check("registering the check", self.read);
+        }
+        #[cfg(not(debug_assertions))]
+        drop(check);
+    }
+
+    /// Validate the scan's internal state, panicking with a description of the first violated
+    /// invariant.
+    ///
+    /// This checks that the offsets of the visited items, the gap and the remaining items are
+    /// consistent and runs the check registered with
+    /// [`set_invariant_check`][VecMutScan::set_invariant_check], if any. It is meant for code
+    /// building on [`slices_mut`][VecMutScan::slices_mut] or on the gap, to verify that the scan
+    /// was not corrupted.
+    pub fn assert_invariants(&mut self) {
+        assert!(
+            self.write <= self.read,
+            "end of the visited items ({}) is past the start of the remaining items ({})",
+            self.write,
+            self.read
+        );
+        assert!(
+            self.read <= self.end,
+            "start of the remaining items ({}) is past the end of the items ({})",
+            self.read,
+            self.end
+        );
+        assert!(
+            self.end <= self.len,
+            "end of the remaining items ({}) is past the end of the buffer's items ({})",
+            self.end,
+            self.len
+        );
+        assert!(
+            !self.base.is_null() && self.base as usize % mem::align_of::<T>() == 0,
+            "the buffer pointer is null or misaligned"
+        );
+        self.run_check("checking invariants", self.read);
+    }
+
+    #[cfg(debug_assertions)]
+    fn run_check(&mut self, operation: &str, index: usize) {
+        if let Some(check) = &mut self.check {
+            // Same as `slices`, but borrowing only the fields we need.
+            let (prefix, suffix) = unsafe {
+                (
+                    core::slice::from_raw_parts(self.base, self.write),
+                    core::slice::from_raw_parts(self.base.add(self.read), self.len - self.read),
+                )
+            };
+            if !check(prefix, suffix) {
+                panic!(
+                    "invariant check failed after {} at buffer index {}",
+                    operation, index
+                );
+            }
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[inline(always)]
+    fn run_check(&mut self, _operation: &str, _index: usize) {}
 }
 
 impl<'a, T: 'a> Drop for VecMutScan<'a, T> {
@@ -178,27 +877,24 @@ impl<'a, T: 'a> Drop for VecMutScan<'a, T> {
         // items.
 
         unsafe {
-            // The read performed by copy is safe as `self.read..self.end` contains valid data and
-            // is within `vec`'s buffer.
+            // The read performed by copy is safe as `self.read..self.len` contains valid data and
+            // is within `vec`'s buffer. This includes the items visited from the back, which
+            // directly follow the remaining items.
 
             // The write performed by copy is safe as `self.write <= self.read` so
             // `self.write..self.write + suffix_len` also stays within `vec`'s buffer.
-            let suffix_len = self.end - self.read;
-            // This is required to handle overlapping copies.
-            ptr::copy(
-                self.base.add(self.read),
-                self.base.add(self.write),
-                suffix_len,
-            );
+            let suffix_len = self.len - self.read;
+            close_gap(self.base, self.write, self.read, suffix_len);
             // `0..self.write` contained valid data before the copy and the copy also moved valid
             // data to `self.write..self.write + suffix_len`. We took ownership of that data and can
-            // safely pass that ownership to `vec` here.
-            self.vec.set_len(self.write + suffix_len);
+            // safely pass that ownership to `storage` here.
+            self.storage.set_len(self.write + suffix_len);
         }
     }
 }
 
 /// Reference wrapper that enables item removal for [`VecMutScan`].
+#[repr(transparent)]
 pub struct VecMutScanItem<'s, 'a, T: 'a> {
     scan: &'s mut VecMutScan<'a, T>,
 }
@@ -206,74 +902,298 @@ pub struct VecMutScanItem<'s, 'a, T: 'a> {
 // When a `VecMutScanItem` is created, there must be valid data at `scan.read` i.e. `scan.read` must
 // not have reached `scan.end` yet.
 
+impl<'a, T: 'a> VecMutScan<'a, T> {
+    /// The action of dropping a `VecMutScanItem`.
+    ///
+    /// Safety: there must be valid data at `self.read`, i.e. there must have been an active
+    /// `VecMutScanItem`.
+    unsafe fn keep_current(&mut self) {
+        // Move the item at `read` to `write` i.e. move it over the gap (see diagrams above).
+        ptr::copy(self.base.add(self.read), self.base.add(self.write), 1);
+        // Advance the position without changing the width of the gap.
+        self.read += 1;
+        self.write += 1;
+
+        self.run_check("keeping an item", self.read - 1);
+    }
+
+    /// Keep the next `n` items, moving them over the gap at once.
+    fn keep_many(&mut self, n: usize) {
+        debug_assert!(n <= self.end - self.read);
+        let start = self.read;
+        if self.write < self.read {
+            // The items `read..read + n` are valid and the destination is within the gap or
+            // overlaps with them, see `keep_current`.
+            unsafe { ptr::copy(self.base.add(self.read), self.base.add(self.write), n) };
+        }
+        self.read += n;
+        self.write += n;
+        self.run_check("keeping items", start);
+    }
+}
+
 impl<'s, 'a, T: 'a> VecMutScanItem<'s, 'a, T> {
+    fn into_inner_forget(self) -> &'s mut VecMutScan<'a, T> {
+        // See `VecGrowScanItem::into_inner_forget`.
+        unsafe {
+            // This is safe, as `VecMutScanItem` is annotated with #[repr(transparent)]
+            mem::transmute(self)
+        }
+    }
+
     /// Removes and returns this item from the vector.
     pub fn remove(self) -> T {
+        // Do not run the `VecMutScanItem`'s drop, as it handles the case for a non-removed item and
+        // would perform a now invalid update of the `VecMutScan`.
+        let scan = self.into_inner_forget();
         unsafe {
             // Read the next item, taking local ownership of the data to return it.
-            let result = ptr::read(self.scan.base.add(self.scan.read));
+            let result = ptr::read(scan.base.add(scan.read));
             // Adjust the read pointer but keep the write pointer to create or widen the gap (see
             // diagrams above).
-            self.scan.read += 1;
-            // Do not run the `VecMutScanItem`'s drop, as it handles the case for a non-removed item
-            // and would perform a now invalid update of the `VecMutScan`.
-            mem::forget(self);
+            scan.read += 1;
+            scan.run_check("removing an item", scan.read - 1);
             result
         }
     }
 
+    /// Removes this item from the vector and pushes it onto `target`.
+    ///
+    /// This is equivalent to `target.push(item.remove())`, but moves the item directly into
+    /// `target`'s buffer.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers = vec![1, 2, 3, 4];
+    /// let mut odd = vec![];
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    ///
+    /// while let Some(value) = scan.next() {
+    ///     if *value % 2 == 1 {
+    ///         value.transfer_to(&mut odd);
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [2, 4]);
+    /// assert_eq!(odd, [1, 3]);
+    /// ```
+    pub fn transfer_to(self, target: &mut Vec<T>) {
+        // Reserve first, so that nothing changes should this panic.
+        target.reserve(1);
+        let scan = self.into_inner_forget();
+        unsafe {
+            // Move the item into `target`'s spare capacity, which can't overlap the scanned
+            // vector, as `target` is borrowed separately.
+            ptr::copy_nonoverlapping(
+                scan.base.add(scan.read),
+                target.as_mut_ptr().add(target.len()),
+                1,
+            );
+            target.set_len(target.len() + 1);
+            // Widen the gap, like `remove`.
+            scan.read += 1;
+            scan.run_check("transferring an item", scan.read - 1);
+        }
+    }
+
     /// Replaces this item with a new value, returns the old value.
     ///
     /// This is equivalent to assigning a new value or calling [`mem::replace`] on the mutable
     /// reference obtained by using [`DerefMut`], but can avoid an intermediate move within the
     /// vector's buffer.
     pub fn replace(self, value: T) -> T {
+        self.replace_ref(value).0
+    }
+
+    /// Replaces this item with a new value, returns the old value and a mutable reference to the
+    /// new value.
+    ///
+    /// This is like [`replace`][VecMutScanItem::replace], but allows further mutation of the new
+    /// value. The returned reference borrows the scan, i.e. it remains valid until the scan is used
+    /// again.
+    pub fn replace_ref(self, value: T) -> (T, &'s mut T) {
+        // Do not run the `VecMutScanItem`'s drop, as it handles the case for a non-replaced item
+        // and would perform a now invalid update of the `VecMutScan`.
+        let scan = self.into_inner_forget();
         unsafe {
             // Read the next item, taking local ownership of the data to return it.
-            let result = ptr::read(self.scan.base.add(self.scan.read));
+            let result = ptr::read(scan.base.add(scan.read));
 
             // Write the replacement in place of the removed item, adjusted for the gap between
             // write and read (see diagrams above).
-            ptr::write(self.scan.base.add(self.scan.write), value);
+            ptr::write(scan.base.add(scan.write), value);
             // Advance the position without changing the width of the gap.
-            self.scan.read += 1;
-            self.scan.write += 1;
-            // Do not run the `VecMutScanItem`'s drop, as it handles the case for a non-replaced
-            // item and would perform a now invalid update of the `VecMutScan`.
-            mem::forget(self);
-            result
+            scan.read += 1;
+            scan.write += 1;
+            scan.run_check("replacing an item", scan.read - 1);
+            (result, &mut *scan.base.add(scan.write - 1))
         }
     }
 
-    /// Access the whole vector.
+    /// Swaps this item with a value outside of the vector and advances past it.
     ///
-    /// This provides access to the whole vector at any point during the scan. In general while
-    /// scanning, the vector content is not contiguous, thus it is returned as two slices, a prefix
-    /// and a suffix. The prefix contains all elements already visited while the suffix contains the
-    /// remaining elements starting with this element.
+    /// This is equivalent to calling [`mem::swap`] on the mutable reference obtained by using
+    /// [`DerefMut`], but, like [`replace`][VecMutScanItem::replace], can avoid an intermediate move
+    /// within the vector's buffer.
     ///
-    /// This method is also present on the [`VecMutScan`] borrowed by this reference wrapper,
-    /// allowing access without an active `VecMutScanItem`.
-    pub fn slices(&self) -> (&[T], &[T]) {
-        self.scan.slices()
-    }
-
-    /// Access and mutate the whole vector.
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut buffers = vec![vec![1; 4], vec![2; 4], vec![3; 4]];
+    /// let mut spare = Vec::with_capacity(4);
+    /// let mut scan = VecMutScan::new(&mut buffers);
     ///
-    /// This provides mutable access to the whole vector at any point during the scan. In general
-    /// while scanning, the vector content is not contiguous, thus it is returned as two slices, a
-    /// prefix and a suffix. The prefix contains all elements already visited while the suffix
-    /// contains the remaining elements starting with this element.
+    /// scan.next();
+    /// // Recycle a buffer from a pool instead of allocating a new one.
+    /// scan.next().unwrap().swap_out(&mut spare);
+    /// drop(scan);
     ///
-    /// This method is also present on the [`VecMutScan`] borrowed by this reference wrapper,
-    /// allowing access without an active `VecMutScanItem`.
-    pub fn slices_mut(&mut self) -> (&mut [T], &mut [T]) {
-        self.scan.slices_mut()
+    /// assert_eq!(buffers, [vec![1; 4], vec![], vec![3; 4]]);
+    /// assert_eq!(spare, [2; 4]);
+    /// ```
+    pub fn swap_out(self, other: &mut T) {
+        let scan = self.into_inner_forget();
+        unsafe {
+            // Move the item out, move `other` into its final place, adjusted for the gap (see
+            // diagrams above), and the item into `other`.
+            let item = ptr::read(scan.base.add(scan.read));
+            ptr::copy_nonoverlapping(other, scan.base.add(scan.write), 1);
+            ptr::write(other, item);
+            // Advance the position without changing the width of the gap.
+            scan.read += 1;
+            scan.write += 1;
+            scan.run_check("swapping out an item", scan.read - 1);
+        }
     }
-}
-
-impl<'s, 'a, T: 'a> Deref for VecMutScanItem<'s, 'a, T> {
-    type Target = T;
+
+    /// Keeps this item and returns a mutable reference to it.
+    ///
+    /// This is equivalent to dropping the `VecMutScanItem`, except that the item can still be
+    /// accessed afterwards. The returned reference points to the item's final location within the
+    /// already visited prefix and borrows the scan, i.e. it remains valid until the scan is used
+    /// again.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers = vec![1, 2, 3, 4];
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    ///
+    /// let first = scan.next().unwrap().keep();
+    /// *first *= 10;
+    /// let second = scan.next().unwrap().keep();
+    /// *second += 10;
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [10, 12, 3, 4]);
+    /// ```
+    pub fn keep(self) -> &'s mut T {
+        let scan = self.into_inner_forget();
+        unsafe {
+            scan.keep_current();
+            // `keep_current` moved the item to `scan.write - 1`, the end of the visited prefix.
+            &mut *scan.base.add(scan.write - 1)
+        }
+    }
+
+    /// Swap this item with the next remaining item.
+    ///
+    /// Afterwards, this wrapper refers to the item that was following it, so that it can be
+    /// examined again, while this item becomes the next remaining item. Returns `false` and does
+    /// nothing if this is the last item.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers = vec![3, 1, 2];
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    ///
+    /// let mut item = scan.next().unwrap();
+    /// assert!(item.swap_with_next());
+    /// assert_eq!(*item, 1);
+    /// drop(item);
+    /// assert_eq!(*scan.next().unwrap(), 3);
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 3, 2]);
+    /// ```
+    pub fn swap_with_next(&mut self) -> bool {
+        let remaining = self.scan.remaining_mut();
+        if remaining.len() < 2 {
+            return false;
+        }
+        remaining.swap(0, 1);
+        true
+    }
+
+    /// Postpone this item, so that it is visited again after `n` more items.
+    ///
+    /// The item is moved `n` positions further into the remaining items, or to their end if there
+    /// are fewer remaining items. The following items move forward, so that the next call to
+    /// [`next`][VecMutScan::next] returns the item that was following this item. When `n` is zero,
+    /// the item is visited again right away.
+    ///
+    /// Deferring the last remaining item keeps it instead, as it would be visited again right away
+    /// and a scan that defers it every time would never end.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut tasks = vec![("a", 1), ("b", 0), ("c", 0), ("d", 0)];
+    /// let mut scan = VecMutScan::new(&mut tasks);
+    ///
+    /// let mut order = vec![];
+    /// while let Some(mut task) = scan.next() {
+    ///     order.push(task.0);
+    ///     if task.1 > 0 {
+    ///         // Retry this task a little later.
+    ///         task.1 -= 1;
+    ///         task.defer(2);
+    ///     } else {
+    ///         task.remove();
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(order, ["a", "b", "c", "a", "d"]);
+    /// ```
+    pub fn defer(self, n: usize) {
+        if self.scan.remaining().len() == 1 {
+            // Dropping `self` keeps the item.
+            return;
+        }
+        let remaining = self.scan.remaining_mut();
+        let len = remaining.len().min(n.saturating_add(1));
+        remaining[..len].rotate_left(1);
+        // The current position now contains the next remaining item, so this must not advance.
+        self.into_inner_forget();
+    }
+
+    /// Access the whole vector.
+    ///
+    /// This provides access to the whole vector at any point during the scan. In general while
+    /// scanning, the vector content is not contiguous, thus it is returned as two slices, a prefix
+    /// and a suffix. The prefix contains all elements already visited while the suffix contains the
+    /// remaining elements starting with this element.
+    ///
+    /// This method is also present on the [`VecMutScan`] borrowed by this reference wrapper,
+    /// allowing access without an active `VecMutScanItem`.
+    pub fn slices(&self) -> (&[T], &[T]) {
+        self.scan.slices()
+    }
+
+    /// Access and mutate the whole vector.
+    ///
+    /// This provides mutable access to the whole vector at any point during the scan. In general
+    /// while scanning, the vector content is not contiguous, thus it is returned as two slices, a
+    /// prefix and a suffix. The prefix contains all elements already visited while the suffix
+    /// contains the remaining elements starting with this element.
+    ///
+    /// This method is also present on the [`VecMutScan`] borrowed by this reference wrapper,
+    /// allowing access without an active `VecMutScanItem`.
+    pub fn slices_mut(&mut self) -> (&mut [T], &mut [T]) {
+        self.scan.slices_mut()
+    }
+}
+
+impl<'s, 'a, T: 'a> Deref for VecMutScanItem<'s, 'a, T> {
+    type Target = T;
 
     fn deref(&self) -> &Self::Target {
         // Within a `VecMutScanItem` the offset `scan.read` contains valid data owned by the
@@ -293,21 +1213,42 @@ impl<'s, 'a, T: 'a> DerefMut for VecMutScanItem<'s, 'a, T> {
 
 impl<'s, 'a, T: 'a> Drop for VecMutScanItem<'s, 'a, T> {
     fn drop(&mut self) {
-        unsafe {
-            // Move the item at `scan.read` to `scan.write` i.e. move it over the gap (see diagrams
-            // above).
-            ptr::copy(
-                self.scan.base.add(self.scan.read),
-                self.scan.base.add(self.scan.write),
-                1,
-            );
-            // Advance the position without changing the width of the gap.
-            self.scan.read += 1;
-            self.scan.write += 1;
-        }
+        unsafe { self.scan.keep_current() }
     }
 }
 
+/// How a [`VecGrowScan`] makes room for items inserted while there is no gap left by removed
+/// items, see [`set_insert_strategy`][VecGrowScan::set_insert_strategy].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InsertStrategy {
+    /// Queue the inserted items in a `VecDeque`.
+    ///
+    /// While items are queued, every kept item is moved through the queue and the queued items
+    /// are spliced into the vector when the scan is dropped. This works best when insertions are
+    /// mostly balanced by removals, as a later removal moves a queued item into place.
+    ///
+    /// There is no chunked variant of the queue, as kept items would still move through it. For
+    /// scans that insert far more items than they remove, use [`Gap`][InsertStrategy::Gap] or
+    /// [`Auto`][InsertStrategy::Auto] instead.
+    Queue,
+    /// Grow the vector and move the remaining items back, opening a gap as large as the number of
+    /// remaining items.
+    ///
+    /// This moves the remaining items once, after which inserted items are placed directly and
+    /// kept items move over the gap, so no items are queued. As the gap's size is proportional to
+    /// the remaining items, items are moved a constant number of times on average, at the cost of
+    /// growing the vector's buffer more than otherwise needed. This works best for scans that
+    /// insert far more items than they remove.
+    Gap,
+    /// Open a gap within the buffer's spare capacity if it has room for a gap like the one
+    /// [`Gap`][InsertStrategy::Gap] would open. Otherwise queue inserted items until the queue
+    /// would exceed the number of remaining items, then proceed like `Gap`, which reallocates once
+    /// instead of queueing even more items.
+    ///
+    /// This is the default.
+    Auto,
+}
+
 /// Forward scan over a vector with mutation, item insertion and removal.
 ///
 /// Provides an iterator like interface over a vector which allows mutation,
@@ -318,13 +1259,16 @@ impl<'s, 'a, T: 'a> Drop for VecMutScanItem<'s, 'a, T> {
 /// Internally, the items are kept in the vector in order. When items are removed, a gap of
 /// uninitialized memory is created, and the items get moved as the iteration continues. When
 /// additional items are inserted, and there is no gap to be filled, the excess is stored in a
-/// [`VecDeque`].
+/// [`VecDeque`] or the vector is grown mid-scan to open a gap, as chosen by the
+/// [`InsertStrategy`].
 ///
 /// Overall, a linear number of moves is performed, but the exact number varies due to potential
 /// reallocations. If no items are inserted, every item is moved at most once.
 ///
 /// Dropping the `VecGrowScan` mid-iteration keeps remaining items in the vector.
 ///
+/// Like a `&mut Vec<T>`, a `VecGrowScan` is `Send` if `T` is `Send` and `Sync` if `T` is `Sync`.
+///
 /// This does not implement the iterator trait, as the returned items borrow from this (i.e. this is
 /// a streaming iterator).
 ///
@@ -332,42 +1276,136 @@ impl<'s, 'a, T: 'a> Drop for VecMutScanItem<'s, 'a, T> {
 /// to the vector's item type but also provide a [`remove`](VecGrowScanItem::remove) and
 /// [`replace`](VecGrowScanItem::replace) method.
 pub struct VecGrowScan<'a, T: 'a> {
-    vec: &'a mut Vec<T>,
+    vec: &'a mut dyn GrowStorage<T>,
     base: *mut T,
     write: usize,
     read: usize,
     end: usize,
     queue: VecDeque<T>,
+    // Number of items at the back of the queue that were inserted, with no kept item queued after
+    // them. May exceed the queue's length once queued items were moved into place.
+    pending: usize,
+    // Number of clones made by `VecGrowScanItem::duplicate` that directly follow the current item.
+    // They are kept without being visited once the current item is kept or removed.
+    duplicates: usize,
+    tail: Vec<T>,
+    parked: Vec<T>,
+    source: Option<Box<dyn Iterator<Item = T> + Send + 'a>>,
+    insert_strategy: InsertStrategy,
+    #[cfg(debug_assertions)]
+    check: Option<GrowCheckFn<'a, T>>,
 }
 
+#[cfg(debug_assertions)]
+type GrowCheckFn<'a, T> = Box<dyn FnMut(&[T], &[T], &[T], &[T]) -> bool + Send + 'a>;
+
+// See the corresponding impls for `VecMutScan`. The storage is a `Vec<T>` or a `BytesMut`. The queue
+// and the tail are owned like the items in the vector.
+unsafe impl<'a, T: Send> Send for VecGrowScan<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for VecGrowScan<'a, T> {}
+
 // invariant: if there's a gap in the vector, then the queue is empty.
 // corollary: if there are items in the queue, then there is no gap in the vector.
 
 impl<'a, T: 'a> VecGrowScan<'a, T> {
     /// Begin a scan over a vector with mutation, insertion and removal.
-    pub fn new(vec: &mut Vec<T>) -> VecGrowScan<T> {
+    pub fn new(vec: &mut Vec<T>) -> VecGrowScan<'_, T> {
+        Self::start_at(vec, 0)
+    }
+
+    /// Begin a scan over a vector with mutation, insertion and removal, starting at a given index.
+    ///
+    /// The items `0..start` are treated as already visited and kept, without being moved or even
+    /// touched. The first call to [`next`][VecGrowScan::next] returns the item at index `start`
+    /// and insertions made before that are placed at index `start`.
+    ///
+    /// Panics if `start` is larger than the vector's length.
+    pub fn start_at(vec: &mut Vec<T>, start: usize) -> VecGrowScan<'_, T> {
+        assert!(start <= vec.len(), "start index out of bounds");
         let base = vec.as_mut_ptr();
-        let write = 0;
-        let read = 0;
         let end = vec.len();
-        let queue = VecDeque::new();
 
-        // Make sure `vec` is in a consistent state should this `VecMutScan` be leaked. In that case
-        // all items within `vec` are also leaked, which is safe. This strategy is also called leak
-        // amplification. This can be seen as the `VecMustScan` taking ownership over `vec`'s items,
-        // while still keeping them in `vec`'s buffer. As we keep a mutable reference to the `vec`
-        // we stop others from messing with its items.
-        unsafe {
-            vec.set_len(0);
-        }
+        // The items `0..end` are valid and owned by `vec`.
+        unsafe { VecGrowScan::from_raw_parts(vec, base, start, end) }
+    }
+
+    /// Begin a scan over a vector with mutation, insertion and removal, with room for `capacity`
+    /// queued insertions.
+    ///
+    /// Items inserted while there is no gap left by removed items are queued. Pre-allocating the
+    /// queue avoids growing it repeatedly when many such insertions are expected.
+    pub fn with_queue_capacity(vec: &mut Vec<T>, capacity: usize) -> VecGrowScan<'_, T> {
+        VecGrowScan::with_queue(vec, VecDeque::with_capacity(capacity))
+    }
+
+    /// Begin a scan over a vector with mutation, insertion and removal, reusing the buffer of
+    /// `queue` for queued insertions.
+    ///
+    /// Any items in `queue` are dropped. Together with [`into_queue`][VecGrowScan::into_queue],
+    /// this allows repeated scans to share a single queue allocation.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// # use std::collections::VecDeque;
+    /// let mut queue = VecDeque::with_capacity(16);
+    ///
+    /// for round in 0..3 {
+    ///     let mut numbers = vec![1, 2, 3];
+    ///     let mut scan = VecGrowScan::with_queue(&mut numbers, queue);
+    ///     loop {
+    ///         let value = match scan.next() {
+    ///             Some(item) => *item,
+    ///             None => break,
+    ///         };
+    ///         scan.insert(value * 10 + round);
+    ///     }
+    ///     queue = scan.into_queue();
+    ///
+    ///     assert_eq!(numbers, [1, 10 + round, 2, 20 + round, 3, 30 + round]);
+    ///     assert!(queue.is_empty());
+    ///     assert!(queue.capacity() >= 16);
+    /// }
+    /// ```
+    pub fn with_queue(vec: &mut Vec<T>, mut queue: VecDeque<T>) -> VecGrowScan<'_, T> {
+        queue.clear();
+        let mut scan = VecGrowScan::new(vec);
+        scan.queue = queue;
+        scan
+    }
+
+    /// Begin a scan over the items `base..base + end` owned by `storage`, starting at `start`.
+    ///
+    /// Safety: `base..base + end` must contain valid items owned by `storage` and `start` must not
+    /// exceed `end`. When the scan is dropped, `storage` takes back ownership of the items, which
+    /// then are contiguous, starting at `base`, before any further items are inserted.
+    unsafe fn from_raw_parts(
+        storage: &'a mut dyn GrowStorage<T>,
+        base: *mut T,
+        start: usize,
+        end: usize,
+    ) -> VecGrowScan<'a, T> {
+        // Make sure `storage` is in a consistent state should this `VecGrowScan` be leaked. In that
+        // case all items within `storage` are also leaked, which is safe. This strategy is also
+        // called leak amplification. This can be seen as the `VecGrowScan` taking ownership over
+        // `storage`'s items, while still keeping them in `storage`'s buffer. As we keep a mutable
+        // reference to the `storage` we stop others from messing with its items.
+        storage.set_len(0);
 
         VecGrowScan {
-            vec,
+            vec: storage,
             base,
-            write,
-            read,
+            write: start,
+            read: start,
             end,
-            queue,
+            queue: VecDeque::new(),
+            pending: 0,
+            duplicates: 0,
+            tail: Vec::new(),
+            parked: Vec::new(),
+            source: None,
+            insert_strategy: InsertStrategy::Auto,
+            #[cfg(debug_assertions)]
+            check: None,
         }
     }
 
@@ -379,6 +1417,7 @@ impl<'a, T: 'a> VecGrowScan<'a, T> {
         // This just constructs a VecGrowScanItem without updating any state. The read and write
         // offsets are adjusted by `VecGrowScanItem` whenever it is dropped or one of its
         // self-consuming methods are called.
+        self.keep_duplicates();
         if self.read != self.end {
             Some(VecGrowScanItem { scan: self })
         } else {
@@ -389,6 +1428,8 @@ impl<'a, T: 'a> VecGrowScan<'a, T> {
     /// Insert an item between the items that have been visited, and the items that haven't been
     /// visited yet. Inserted items are not returned during iteration.
     ///
+    /// Returns a mutable reference to the inserted item.
+    ///
     /// ```
     /// # use vec_mut_scan::VecGrowScan;
     /// let mut numbers = vec![1, 2, 4, 5];
@@ -403,7 +1444,121 @@ impl<'a, T: 'a> VecGrowScan<'a, T> {
     ///
     /// assert_eq!(numbers, [1, 2, 3, 4, 5]);
     /// ```
-    pub fn insert(&mut self, item: T) {
+    pub fn insert(&mut self, item: T) -> &mut T {
+        self.make_room(1);
+        let queued = self.write == self.read;
+        self.insert_inner(item);
+        self.run_check("inserting an item", self.read);
+        unsafe { self.last_placed(queued) }
+    }
+
+    /// Insert an item constructed by `f` between the items that have been visited, and the items
+    /// that haven't been visited yet.
+    ///
+    /// Like [`insert`][VecGrowScan::insert], but `f` is only called once the destination of the
+    /// item is determined and any room needed for it is allocated. Should allocation fail, `f` is
+    /// not called.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// let mut lines = vec![String::from("a"), String::from("b")];
+    /// let mut scan = VecGrowScan::new(&mut lines);
+    ///
+    /// scan.next();
+    /// scan.insert_with(|| "-".repeat(3)).push('>');
+    /// drop(scan);
+    ///
+    /// assert_eq!(lines, ["a", "--->", "b"]);
+    /// ```
+    pub fn insert_with(&mut self, f: impl FnOnce() -> T) -> &mut T {
+        self.make_room(1);
+        let queued = self.write == self.read;
+        if queued {
+            self.queue.reserve(1);
+        }
+        // Neither branch of `insert_inner` can panic or allocate now.
+        self.insert_inner(f());
+        self.run_check("inserting an item", self.read);
+        unsafe { self.last_placed(queued) }
+    }
+
+    /// Insert a clone of `item` between the items that have been visited, and the items that
+    /// haven't been visited yet.
+    ///
+    /// Like [`insert_with`][VecGrowScan::insert_with], the clone is made once room for it is
+    /// allocated, directly at its destination where possible.
+    pub fn insert_cloned(&mut self, item: &T) -> &mut T
+    where
+        T: Clone,
+    {
+        self.insert_with(|| item.clone())
+    }
+
+    /// Mutable reference to the item most recently placed at the end of the visited items.
+    ///
+    /// This is the last item of the queue if `queued` is true and the item right before the gap
+    /// otherwise.
+    ///
+    /// Safety: that item must exist and no other items must have been placed after it.
+    unsafe fn last_placed(&mut self, queued: bool) -> &mut T {
+        self.placed_before(queued, 0)
+    }
+
+    /// Like [`last_placed`][VecGrowScan::last_placed], but for the item that was followed by
+    /// `count` more placed items.
+    unsafe fn placed_before(&mut self, queued: bool, count: usize) -> &mut T {
+        let queue_len = self.queue.len();
+        if !queued {
+            &mut *self.base.add(self.write - 1 - count)
+        } else if count < queue_len {
+            &mut self.queue[queue_len - 1 - count]
+        } else {
+            // Each of the following items was pushed to the back of the queue and moved the
+            // queue's front item to the end of the visited items, so the item itself was moved
+            // out of the queue, followed by `count + 1 - queue_len` placed items.
+            &mut *self.base.add(self.write + queue_len - 1 - count)
+        }
+    }
+
+    /// Keep the clones made by [`VecGrowScanItem::duplicate`], which follow the current position.
+    fn keep_duplicates(&mut self) {
+        for _ in 0..mem::replace(&mut self.duplicates, 0) {
+            drop(VecGrowScanItem { scan: self });
+        }
+    }
+
+    /// Open a gap for inserting `additional` items, if there is none and the insert strategy asks
+    /// for it.
+    fn make_room(&mut self, additional: usize) {
+        if self.write < self.read {
+            return;
+        }
+        let remaining = self.end - self.read;
+        let extra = cmp::max(remaining, cmp::max(additional, 1));
+        // When the buffer's spare capacity is enough for the queued items and the gap, opening the
+        // gap doesn't reallocate and can use all of it.
+        let spare = if mem::size_of::<T>() == 0 {
+            0
+        } else {
+            self.vec.capacity() - self.end
+        };
+        let fits = spare >= self.queue.len() + extra;
+        let open = match self.insert_strategy {
+            InsertStrategy::Queue => false,
+            InsertStrategy::Gap => true,
+            InsertStrategy::Auto => fits || self.queue.len() + additional > remaining,
+        };
+        if open {
+            self.open_gap(if fits {
+                spare - self.queue.len()
+            } else {
+                extra
+            });
+        }
+    }
+
+    fn insert_inner(&mut self, item: T) {
+        self.make_room(1);
         if self.write < self.read {
             // The queue is empty by invariant, so this is the right place.
             unsafe {
@@ -412,6 +1567,185 @@ impl<'a, T: 'a> VecGrowScan<'a, T> {
             }
         } else {
             self.queue.push_back(item);
+            self.pending += 1;
+        }
+    }
+
+    /// Insert an item before the items that haven't been visited yet, so that it is returned by
+    /// the following [`next`][VecGrowScan::next] call.
+    ///
+    /// Unlike items inserted using [`insert`][VecGrowScan::insert], this item is visited by the
+    /// scan, which allows for work-list style processing, where an item is expanded into further
+    /// items that need processing themselves.
+    ///
+    /// This is a constant time operation, except when no items were removed and no items were
+    /// visited so far. In that case the vector grows and the remaining items are moved, leaving
+    /// room for as many further insertions as there are remaining items.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// let mut numbers = vec![2, 1];
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    /// scan.insert_next(3);
+    ///
+    /// // Count down from every number.
+    /// while let Some(item) = scan.next() {
+    ///     if *item > 1 {
+    ///         let next = *item - 1;
+    ///         item.insert_visit_after(next);
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [3, 2, 1, 2, 1, 1]);
+    /// ```
+    pub fn insert_next(&mut self, item: T) {
+        if self.write == self.read {
+            if self.write > 0 {
+                // Move the last placed item to the front of the queue, which keeps the order of
+                // the visited items, to make room for the new item.
+                self.pending = cmp::min(self.pending, self.queue.len());
+                unsafe {
+                    self.queue
+                        .push_front(ptr::read(self.base.add(self.write - 1)));
+                }
+                self.write -= 1;
+            } else {
+                let remaining = self.end - self.read;
+                self.open_gap(cmp::max(remaining, 1));
+            }
+        }
+        // Now `write < read`, so there is room for the new item in front of the remaining items.
+        self.read -= 1;
+        unsafe {
+            ptr::write(self.base.add(self.read), item);
+        }
+        self.run_check("inserting an item", self.read);
+    }
+
+    /// Insert a sequence of items before the items that haven't been visited yet, so that they are
+    /// returned by the following [`next`][VecGrowScan::next] calls, in order.
+    ///
+    /// See [`insert_next`][VecGrowScan::insert_next].
+    pub fn insert_many_next(&mut self, iter: impl IntoIterator<Item = T>) {
+        let items: Vec<T> = iter.into_iter().collect();
+        for item in items.into_iter().rev() {
+            self.insert_next(item);
+        }
+    }
+
+    /// Move all queued items into their place within the vector, growing it if necessary.
+    ///
+    /// Items inserted while there is no gap left by removed items are queued and only moved into
+    /// place when the gap opens or when the scan is dropped. Until then, every kept item is moved
+    /// through the queue. This moves the remaining items once, to make room for the queued items,
+    /// so that following kept items are not moved at all.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// let mut numbers = vec![1, 2, 3];
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    /// scan.insert_many(vec![7, 8]);
+    /// scan.flush_pending();
+    /// assert_eq!(scan.slices(), (&[7, 8][..], &[][..], &[][..], &[1, 2, 3][..]));
+    /// ```
+    pub fn flush_pending(&mut self) {
+        if !self.queue.is_empty() {
+            // By invariant, there is no gap.
+            self.open_gap(0);
+        }
+    }
+
+    /// Remove the queued insertions that were not moved into place yet and return them, in
+    /// insertion order.
+    ///
+    /// This takes back speculative insertions, instead of having them placed when the scan is
+    /// dropped. Inserted items are moved into place right away when there is a gap left by removed
+    /// items or when the [`InsertStrategy`] grows the vector. Queued insertions are also no longer
+    /// pending once an item is kept while there are queued items, as that item is queued behind
+    /// them.
+    ///
+    /// ```
+    /// # use vec_mut_scan::{InsertStrategy, VecGrowScan};
+    /// let mut numbers = vec![1, 2, 3];
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    /// scan.set_insert_strategy(InsertStrategy::Queue);
+    ///
+    /// scan.next();
+    /// scan.insert_many(vec![4, 5]);
+    /// // Abort the speculative insertions.
+    /// assert_eq!(scan.drain_pending(), [4, 5]);
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 2, 3]);
+    /// ```
+    pub fn drain_pending(&mut self) -> Vec<T> {
+        let count = cmp::min(self.pending, self.queue.len());
+        self.pending = 0;
+        let start = self.queue.len() - count;
+        let drained = self.queue.drain(start..).collect();
+        self.run_check("removing items", self.read);
+        drained
+    }
+
+    /// Set how room is made for items inserted while there is no gap left by removed items.
+    ///
+    /// See [`InsertStrategy`]. The default is [`InsertStrategy::Auto`].
+    ///
+    /// ```
+    /// # use vec_mut_scan::{InsertStrategy, VecGrowScan};
+    /// let mut numbers: Vec<u32> = (0..1000).collect();
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    /// scan.set_insert_strategy(InsertStrategy::Gap);
+    ///
+    /// loop {
+    ///     let value = match scan.next() {
+    ///         Some(item) => *item,
+    ///         None => break,
+    ///     };
+    ///     // Every item is duplicated, so there is no point in queueing any of them.
+    ///     scan.insert(value);
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers.len(), 2000);
+    /// assert!(numbers.chunks(2).all(|pair| pair[0] == pair[1]));
+    /// ```
+    pub fn set_insert_strategy(&mut self, strategy: InsertStrategy) {
+        self.insert_strategy = strategy;
+    }
+
+    /// Grow the vector, so that there is a gap of `extra` items after moving all queued items into
+    /// place.
+    ///
+    /// Must only be called when there is no gap.
+    fn open_gap(&mut self, extra: usize) {
+        debug_assert!(self.write == self.read);
+        unsafe {
+            // Without a gap, the items `0..end` are valid.
+            let base = self.vec.reserve_items(self.end, self.queue.len() + extra);
+            self.open_reserved_gap(base, extra);
+        }
+    }
+
+    /// Like [`open_gap`][VecGrowScan::open_gap], but with the vector already grown.
+    ///
+    /// Safety: must only be called when there is no gap and `base` must be the start of the
+    /// vector's buffer as returned by `reserve_items` for `queue.len() + extra` additional items.
+    unsafe fn open_reserved_gap(&mut self, base: *mut T, extra: usize) {
+        let shift = self.queue.len() + extra;
+        self.base = base;
+        // Move the remaining items back, leaving room for the queued items and the gap.
+        ptr::copy(
+            self.base.add(self.read),
+            self.base.add(self.read + shift),
+            self.end - self.read,
+        );
+        self.read += shift;
+        self.end += shift;
+        while let Some(item) = self.queue.pop_front() {
+            ptr::write(self.base.add(self.write), item);
+            self.write += 1;
         }
     }
 
@@ -421,16 +1755,297 @@ impl<'a, T: 'a> VecGrowScan<'a, T> {
     /// Equivalent to repeatedly calling [`insert`][VecGrowScan::insert], except that reallocations
     /// will be minimized with iterator size hints.
     pub fn insert_many(&mut self, iter: impl IntoIterator<Item = T>) {
+        self.insert_many_inner(iter);
+        self.run_check("inserting items", self.read);
+    }
+
+    /// Insert clones of a slice's items between the items that have been visited, and the items
+    /// that haven't been visited yet, keeping their order.
+    ///
+    /// Room for all items is reserved at once, after which each clone is made directly at its
+    /// destination.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// let separator = [String::from("-"), String::from("-")];
+    /// let mut words = vec![String::from("a"), String::from("b")];
+    /// let mut scan = VecGrowScan::new(&mut words);
+    ///
+    /// scan.next();
+    /// scan.insert_many_cloned(&separator);
+    /// drop(scan);
+    ///
+    /// assert_eq!(words, ["a", "-", "-", "b"]);
+    /// ```
+    pub fn insert_many_cloned(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        // The exact size hint makes `insert_many_inner` fill the gap and then reserve room for the
+        // rest at once.
+        self.insert_many_inner(items.iter().cloned());
+        self.run_check("inserting items", self.read);
+    }
+
+    /// Insert all items of a vector between the items that have been visited, and the items that
+    /// haven't been visited yet, keeping their order.
+    ///
+    /// Like [`insert_many`][VecGrowScan::insert_many], but the items are moved in bulk instead of
+    /// one by one. When the items have to be queued and the queue is empty, the queue takes over
+    /// the vector's allocation instead.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// let mut numbers = vec![1, 5];
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    ///
+    /// scan.next();
+    /// scan.insert_vec(vec![2, 3, 4]);
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 2, 3, 4, 5]);
+    /// ```
+    pub fn insert_vec(&mut self, mut items: Vec<T>) {
+        loop {
+            let len = items.len();
+            let count = cmp::min(self.read - self.write, len);
+            if count > 0 {
+                unsafe {
+                    // The queue is empty by invariant, so the gap is the right place. The moved
+                    // items are forgotten by the vector and the rest is moved to its front.
+                    ptr::copy_nonoverlapping(items.as_ptr(), self.base.add(self.write), count);
+                    self.write += count;
+                    ptr::copy(items.as_ptr().add(count), items.as_mut_ptr(), len - count);
+                    items.set_len(len - count);
+                }
+            }
+            if items.is_empty() {
+                break;
+            }
+            self.make_room(items.len());
+            if self.write == self.read {
+                self.pending += items.len();
+                if self.queue.is_empty() && self.queue.capacity() < items.len() {
+                    self.queue = VecDeque::from(items);
+                } else {
+                    self.queue.extend(items);
+                }
+                break;
+            }
+        }
+        self.run_check("inserting items", self.read);
+    }
+
+    fn insert_many_inner(&mut self, iter: impl IntoIterator<Item = T>) {
         let mut iter = iter.into_iter();
-        while self.write < self.read {
-            if let Some(item) = iter.next() {
-                self.insert(item);
-            } else {
-                return;
+        loop {
+            while self.write < self.read {
+                if let Some(item) = iter.next() {
+                    self.insert_inner(item);
+                } else {
+                    return;
+                }
+            }
+            self.make_room(iter.size_hint().0);
+            if self.write == self.read {
+                break;
             }
         }
 
+        let queued = self.queue.len();
         self.queue.extend(iter);
+        self.pending += self.queue.len() - queued;
+    }
+
+    /// Append an item after all items that haven't been visited yet, i.e. at the end of the
+    /// vector. Appended items are not returned during iteration.
+    ///
+    /// Returns a mutable reference to the appended item.
+    ///
+    /// This can be used at any point of the scan, including after [`next`][VecGrowScan::next]
+    /// returned `None`. Appended items are kept separately until the scan is dropped and are not
+    /// included in the slices returned by [`slices`][VecGrowScan::slices], see
+    /// [`appended`][VecGrowScan::appended] instead.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// let mut numbers = vec![1, 2, 3];
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    ///
+    /// let mut sum = 0;
+    /// while let Some(item) = scan.next() {
+    ///     sum += *item;
+    /// }
+    /// scan.append(sum);
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 2, 3, 6]);
+    /// ```
+    pub fn append(&mut self, item: T) -> &mut T {
+        self.tail.push(item);
+        self.tail.last_mut().unwrap()
+    }
+
+    /// Append a sequence of items after all items that haven't been visited yet, i.e. at the end
+    /// of the vector. Appended items are not returned during iteration.
+    ///
+    /// Equivalent to repeatedly calling [`append`][VecGrowScan::append], except that reallocations
+    /// will be minimized with iterator size hints.
+    pub fn append_many(&mut self, iter: impl IntoIterator<Item = T>) {
+        self.tail.extend(iter);
+    }
+
+    /// The items parked with [`VecGrowScanItem::park`] and not yet unparked, in parking order.
+    pub fn parked(&self) -> &[T] {
+        &self.parked
+    }
+
+    /// Insert all parked items at the current position, in parking order.
+    ///
+    /// This is equivalent to [`insert_many`][VecGrowScan::insert_many] with the parked items, so
+    /// they are placed between the visited and the remaining items and are not returned during
+    /// iteration.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// // Move all negative numbers after the following zero.
+    /// let mut numbers = vec![1, -2, 3, -4, 0, 5, -6, 0, 7];
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    ///
+    /// loop {
+    ///     let item = match scan.next() {
+    ///         Some(item) => item,
+    ///         None => break,
+    ///     };
+    ///     if *item < 0 {
+    ///         item.park();
+    ///     } else if *item == 0 {
+    ///         drop(item);
+    ///         scan.unpark_all_here();
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 3, 0, -2, -4, 5, 0, -6, 7]);
+    /// ```
+    pub fn unpark_all_here(&mut self) {
+        let parked = mem::replace(&mut self.parked, Vec::new());
+        self.insert_many(parked);
+    }
+
+    /// Insert the parked items for which `pred` returns `true` at the current position, in parking
+    /// order. The other items stay parked.
+    ///
+    /// Returns the number of unparked items.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// // Move every task after the task it depends on.
+    /// let mut tasks = vec![("build", Some("fetch")), ("test", Some("build")), ("fetch", None)];
+    /// let mut scan = VecGrowScan::new(&mut tasks);
+    /// let mut done = vec![];
+    ///
+    /// loop {
+    ///     let task = match scan.next() {
+    ///         Some(task) => task,
+    ///         None => break,
+    ///     };
+    ///     match task.1 {
+    ///         Some(dependency) if !done.contains(&dependency) => task.park(),
+    ///         _ => {
+    ///             done.push(task.0);
+    ///             drop(task);
+    ///             // Unparked tasks are not visited, so they are done once unparked.
+    ///             scan.unpark_matching(|task| {
+    ///                 let ready = done.contains(&task.1.unwrap());
+    ///                 if ready {
+    ///                     done.push(task.0);
+    ///                 }
+    ///                 ready
+    ///             });
+    ///         }
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(tasks, [("fetch", None), ("build", Some("fetch")), ("test", Some("build"))]);
+    /// ```
+    pub fn unpark_matching(&mut self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        let mut unparked = Vec::new();
+        let mut scan = VecMutScan::new(&mut self.parked);
+        while let Some(item) = scan.next() {
+            if pred(&item) {
+                unparked.push(item.remove());
+            }
+        }
+        drop(scan);
+        let count = unparked.len();
+        self.insert_many(unparked);
+        count
+    }
+
+    /// Set an iterator providing items that are inserted whenever an item is removed.
+    ///
+    /// Every time [`VecGrowScanItem::remove`] opens a gap, the next item of `source` is inserted
+    /// into it, i.e. in place of the removed item. An existing gap is filled right away. Items are
+    /// only taken from `source` when they can be placed without moving any other items, so the
+    /// source is consumed lazily instead of being collected into the queue of pending insertions.
+    /// Inserted items are not returned during iteration.
+    ///
+    /// Items of `source` that were not needed to fill gaps are inserted after the visited items
+    /// when the scan is dropped or when another source is set. The source has to be `Send`, so
+    /// that the scan can still be sent to other threads.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// let mut numbers = vec![1, 2, 3, 4, 5];
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    /// scan.set_insert_source((10..).step_by(10).take(4));
+    ///
+    /// while let Some(item) = scan.next() {
+    ///     if *item % 2 == 0 {
+    ///         item.remove();
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 10, 3, 20, 5, 30, 40]);
+    /// ```
+    pub fn set_insert_source(&mut self, source: impl Iterator<Item = T> + Send + 'a) {
+        if let Some(previous) = self.source.take() {
+            self.insert_many_inner(previous);
+        }
+        self.source = Some(Box::new(source));
+        self.fill_gap_from_source();
+        self.run_check("inserting items", self.read);
+    }
+
+    /// Fill the gap with items of the insert source, as long as it provides items.
+    fn fill_gap_from_source(&mut self) {
+        if let Some(source) = &mut self.source {
+            while self.write < self.read {
+                match source.next() {
+                    Some(item) => unsafe {
+                        // The queue is empty by invariant, so this is the right place.
+                        ptr::write(self.base.add(self.write), item);
+                        self.write += 1;
+                    },
+                    None => {
+                        self.source = None;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Access the items appended so far.
+    ///
+    /// These are the items added using [`append`][VecGrowScan::append] or
+    /// [`append_many`][VecGrowScan::append_many], in order. They will follow all the items returned
+    /// by [`slices`][VecGrowScan::slices] when the scan is dropped.
+    pub fn appended(&self) -> &[T] {
+        &self.tail
     }
 
     /// Access the whole vector.
@@ -482,10 +2097,365 @@ impl<'a, T: 'a> VecGrowScan<'a, T> {
             )
         }
     }
+
+    /// Swap the remaining items at the indices `i` and `j`, relative to the next item.
+    ///
+    /// Index zero refers to the item returned by the following [`next`][VecGrowScan::next] call.
+    /// The scan's position is not changed.
+    ///
+    /// Panics if `i` or `j` is out of bounds.
+    pub fn swap_remaining(&mut self, i: usize, j: usize) {
+        self.slices_mut().3.swap(i, j);
+    }
+
+    /// Clone the items the vector would contain if the scan were dropped now.
+    ///
+    /// These are the visited and inserted items, the remaining items, the appended items and the
+    /// parked items, in that order. Items of an insert source that weren't inserted yet are not
+    /// included. The scan is not changed.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// let mut tokens = vec!["a", "+", "b"];
+    /// let mut scan = VecGrowScan::new(&mut tokens);
+    /// let mut states = vec![];
+    ///
+    /// loop {
+    ///     match scan.next() {
+    ///         Some(token) if *token == "+" => drop(token.replace_with_many(vec!["add", "("])),
+    ///         Some(_) => continue,
+    ///         None => break,
+    ///     }
+    ///     states.push(scan.snapshot());
+    /// }
+    /// scan.append(")");
+    /// states.push(scan.snapshot());
+    /// drop(scan);
+    ///
+    /// assert_eq!(states, [vec!["a", "add", "(", "b"], vec!["a", "add", "(", "b", ")"]]);
+    /// assert_eq!(tokens, states[1]);
+    /// ```
+    pub fn snapshot(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let (visited, queue_front, queue_back, remaining) = self.slices();
+        let parts = [
+            visited,
+            queue_front,
+            queue_back,
+            remaining,
+            &self.tail[..],
+            &self.parked[..],
+        ];
+        let mut items = Vec::with_capacity(parts.iter().map(|part| part.len()).sum());
+        for part in &parts {
+            items.extend_from_slice(part);
+        }
+        items
+    }
+
+    /// The number of items that can be inserted into the gap left by removed items.
+    ///
+    /// Items inserted using [`insert`][VecGrowScan::insert] are moved into the gap directly, which
+    /// is cheaper than queuing them, as happens when there is no gap.
+    pub fn gap_len(&self) -> usize {
+        self.read - self.write
+    }
+
+    /// Returns `true` if there is a gap left by removed items, so that the next inserted item is
+    /// moved into place directly instead of being queued.
+    pub fn has_gap(&self) -> bool {
+        self.read != self.write
+    }
+
+    /// Remove the longest prefix of the remaining items that all satisfy `pred` and return them.
+    ///
+    /// This works like [`VecMutScan::take_matching_prefix`]. When there are queued insertions, the
+    /// items are removed one at a time, as the queued items are moved into the created gap.
+    pub fn take_matching_prefix(&mut self, mut pred: impl FnMut(&T) -> bool) -> Vec<T> {
+        let count = self.slices().3.iter().take_while(|item| pred(item)).count();
+        let mut taken = Vec::with_capacity(count);
+        if self.queue.is_empty() {
+            unsafe {
+                // See `VecMutScan::take_matching_prefix`. Without queued items, removing items
+                // only creates or widens the gap.
+                ptr::copy_nonoverlapping(self.base.add(self.read), taken.as_mut_ptr(), count);
+                self.read += count;
+                taken.set_len(count);
+            }
+            if count > 0 {
+                self.run_check("removing items", self.read - count);
+            }
+        } else {
+            for _ in 0..count {
+                taken.push(self.next().unwrap().remove());
+            }
+        }
+        taken
+    }
+
+    /// Move all remaining items to the end of `target` and end the scan, keeping the visited,
+    /// inserted and appended items.
+    ///
+    /// This works like [`VecMutScan::remove_rest_into`]. Items of an insert source that were not
+    /// inserted yet are inserted before the scan ends, like when dropping it.
+    pub fn remove_rest_into(mut self, target: &mut Vec<T>) {
+        let count = self.end - self.read;
+        target.reserve(count);
+        unsafe {
+            // See `VecMutScan::remove_rest_into`. Queued items precede the remaining items, so they
+            // are placed at `write` when the scan is dropped, whether or not `end` changes.
+            let len = target.len();
+            ptr::copy_nonoverlapping(
+                self.base.add(self.read),
+                target.as_mut_ptr().add(len),
+                count,
+            );
+            self.end = self.read;
+            target.set_len(len + count);
+        }
+    }
+
+    /// Replace all remaining items with the items of an iterator, returning the replaced items.
+    ///
+    /// The new items become the remaining items, i.e. they are returned by following
+    /// [`next`][VecGrowScan::next] calls. To end the scan instead, drop it afterwards, which keeps
+    /// the new items.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// let mut numbers = vec![1, 2, 3, 4];
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    /// scan.next();
+    /// let old = scan.replace_rest(vec![5, 6]);
+    /// assert_eq!(*scan.next().unwrap(), 5);
+    /// drop(scan);
+    ///
+    /// assert_eq!(old, [2, 3, 4]);
+    /// assert_eq!(numbers, [1, 5, 6]);
+    /// ```
+    pub fn replace_rest(&mut self, iter: impl IntoIterator<Item = T>) -> Vec<T> {
+        let old = self.take_matching_prefix(|_| true);
+        let mut new: Vec<T> = iter.into_iter().collect();
+        unsafe {
+            // There are no remaining items, so the items `0..write` are valid and all other visited
+            // items are queued. We move the queued items into place, followed by the new items.
+            self.base = self
+                .vec
+                .reserve_items(self.write, self.queue.len() + new.len());
+            while let Some(item) = self.queue.pop_front() {
+                ptr::write(self.base.add(self.write), item);
+                self.write += 1;
+            }
+            ptr::copy_nonoverlapping(new.as_ptr(), self.base.add(self.write), new.len());
+            self.read = self.write;
+            self.end = self.write + new.len();
+            // The new items were moved out.
+            new.set_len(0);
+        }
+        self.run_check("replacing items", self.read);
+        old
+    }
+
+    /// Replace all remaining items with the items of an iterator, dropping the replaced items.
+    ///
+    /// See [`replace_rest`][VecGrowScan::replace_rest].
+    pub fn set_rest(&mut self, iter: impl IntoIterator<Item = T>) {
+        self.replace_rest(iter);
+    }
+
+    /// Visit all remaining items, keeping those for which `f` returns `true`, while folding an
+    /// accumulator.
+    ///
+    /// This works like [`VecMutScan::fold_retain`]. As long as there are queued insertions or an
+    /// insert source that may fill the gaps of removed items, items are processed like when using
+    /// [`next`][VecGrowScan::next]. Afterwards, the same optimized loop as for `VecMutScan` is used.
+    pub fn fold_retain<B>(&mut self, init: B, mut f: impl FnMut(B, &mut T) -> (B, bool)) -> B {
+        let mut acc = init;
+
+        // Removing items through the wrapper also fills their gaps from the insert source, which
+        // is dropped once it runs out of items.
+        while !self.queue.is_empty() || self.source.is_some() {
+            let mut item = match self.next() {
+                Some(item) => item,
+                None => return acc,
+            };
+            let (next_acc, keep) = f(acc, &mut item);
+            acc = next_acc;
+            if !keep {
+                item.remove();
+            }
+        }
+
+        // Without queued items, keeping an item works like for `VecMutScan`, see there.
+        while self.write == self.read && self.read != self.end {
+            let (next_acc, keep) = f(acc, unsafe { &mut *self.base.add(self.read) });
+            acc = next_acc;
+            self.read += 1;
+            if keep {
+                self.write += 1;
+                self.run_check("keeping an item", self.read - 1);
+            } else {
+                unsafe { ptr::drop_in_place(self.base.add(self.read - 1)) };
+                self.run_check("removing an item", self.read - 1);
+            }
+        }
+
+        while self.read != self.end {
+            let (next_acc, keep) = f(acc, unsafe { &mut *self.base.add(self.read) });
+            acc = next_acc;
+            self.read += 1;
+            if keep {
+                unsafe {
+                    ptr::copy_nonoverlapping(
+                        self.base.add(self.read - 1),
+                        self.base.add(self.write),
+                        1,
+                    )
+                };
+                self.write += 1;
+                self.run_check("keeping an item", self.read - 1);
+            } else {
+                unsafe { ptr::drop_in_place(self.base.add(self.read - 1)) };
+                self.run_check("removing an item", self.read - 1);
+            }
+        }
+
+        acc
+    }
+
+    /// Visit all remaining items, keeping only those for which `f` returns `true`.
+    ///
+    /// This uses internal iteration, see [`fold_retain`][VecGrowScan::fold_retain].
+    pub fn retain_rest(&mut self, mut f: impl FnMut(&mut T) -> bool) {
+        self.fold_retain((), |(), item| ((), f(item)))
+    }
+
+    /// Keep only the remaining items for which `f` returns `true` and end the scan.
+    ///
+    /// This is the same as calling [`retain_rest`][VecGrowScan::retain_rest] followed by dropping the
+    /// scan.
+    pub fn finish_retain(mut self, f: impl FnMut(&mut T) -> bool) {
+        self.retain_rest(f)
+    }
+
+    /// Register a closure that checks an invariant after every operation of this scan.
+    ///
+    /// The closure is called with the four slices (as returned by
+    /// [`slices`][VecGrowScan::slices]) whenever an item is kept, removed, replaced or inserted, as
+    /// well as once when registering it. If the closure returns `false`, the scan panics with a
+    /// message naming the operation and the index of the item involved within the vector's buffer.
+    /// As items can be inserted and moved, this can differ from the item's original index.
+    ///
+    /// This is a debugging aid and only has an effect when debug assertions are enabled. Otherwise
+    /// the closure is dropped without ever being called. The closure has to be `Send`, so that the
+    /// scan can still be sent to other threads.
+    pub fn set_invariant_check(
+        &mut self,
+        check: impl FnMut(&[T], &[T], &[T], &[T]) -> bool + Send + 'a,
+    ) {
+        #[cfg(debug_assertions)]
+        {
+            self.check = Some(Box::new(check));
+            self.run_check("registering the check", self.read);
+        }
+        #[cfg(not(debug_assertions))]
+        drop(check);
+    }
+
+    /// Validate the scan's internal state, panicking with a description of the first violated
+    /// invariant.
+    ///
+    /// This checks that the offsets of the visited items, the gap and the remaining items are
+    /// consistent, that items are only queued while there is no gap and runs the check registered
+    /// with [`set_invariant_check`][VecGrowScan::set_invariant_check], if any. It is meant for code
+    /// building on [`slices_mut`][VecGrowScan::slices_mut] or on the gap, to verify that the scan
+    /// was not corrupted.
+    pub fn assert_invariants(&mut self) {
+        assert!(
+            self.write <= self.read,
+            "end of the visited items ({}) is past the start of the remaining items ({})",
+            self.write,
+            self.read
+        );
+        assert!(
+            self.read <= self.end,
+            "start of the remaining items ({}) is past the end of the items ({})",
+            self.read,
+            self.end
+        );
+        assert!(
+            !self.base.is_null() && self.base as usize % mem::align_of::<T>() == 0,
+            "the buffer pointer is null or misaligned"
+        );
+        assert!(
+            self.queue.is_empty() || self.write == self.read,
+            "{} items are queued while there is a gap of {} items",
+            self.queue.len(),
+            self.read - self.write
+        );
+        self.run_check("checking invariants", self.read);
+    }
+
+    #[cfg(debug_assertions)]
+    fn run_check(&mut self, operation: &str, index: usize) {
+        if let Some(check) = &mut self.check {
+            // Same as `slices`, but borrowing only the fields we need.
+            let (mid_l, mid_r) = self.queue.as_slices();
+            let (prefix, suffix) = unsafe {
+                (
+                    core::slice::from_raw_parts(self.base, self.write),
+                    core::slice::from_raw_parts(self.base.add(self.read), self.end - self.read),
+                )
+            };
+            if !check(prefix, mid_l, mid_r, suffix) {
+                panic!(
+                    "invariant check failed after {} at buffer index {}",
+                    operation, index
+                );
+            }
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[inline(always)]
+    fn run_check(&mut self, _operation: &str, _index: usize) {}
 }
 
 impl<'a, T: 'a> Drop for VecGrowScan<'a, T> {
     fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+impl<'a, T: 'a> VecGrowScan<'a, T> {
+    /// Ends the scan, returning the emptied queue so that its buffer can be reused, e.g. with
+    /// [`with_queue`][VecGrowScan::with_queue].
+    ///
+    /// This is equivalent to dropping the scan, except for returning the queue.
+    pub fn into_queue(self) -> VecDeque<T> {
+        let mut this = ManuallyDrop::new(self);
+        this.finish();
+        // `this` is not dropped, so every field that owns resources is moved out or dropped
+        // exactly once here. This has to be kept in sync with the fields of `VecGrowScan`.
+        unsafe {
+            ptr::drop_in_place(&mut this.tail);
+            ptr::drop_in_place(&mut this.parked);
+            ptr::drop_in_place(&mut this.source);
+            #[cfg(debug_assertions)]
+            ptr::drop_in_place(&mut this.check);
+            ptr::read(&this.queue)
+        }
+    }
+
+    /// Moves all remaining, queued and pending items back into the vector, as done when the scan
+    /// is dropped. Must be called exactly once.
+    fn finish(&mut self) {
+        if let Some(source) = self.source.take() {
+            self.insert_many_inner(source);
+        }
+
         // When we are dropped, there might be a gap of uninitialized (after dropping) memory
         // between a prefix of non-removed items we iterated over and a suffix of items we did not
         // iterate over. We need to move the suffix to close the gap, so we have a consecutive
@@ -500,12 +2470,7 @@ impl<'a, T: 'a> Drop for VecGrowScan<'a, T> {
                 // The write performed by copy is safe as `self.write <= self.read` so
                 // `self.write..self.write + suffix_len` also stays within `vec`'s buffer.
                 let suffix_len = self.end - self.read;
-                // This is required to handle overlapping copies.
-                ptr::copy(
-                    self.base.add(self.read),
-                    self.base.add(self.write),
-                    suffix_len,
-                );
+                close_gap(self.base, self.write, self.read, suffix_len);
                 // `0..self.write` contained valid data before the copy and the copy also moved valid
                 // data to `self.write..self.write + suffix_len`. We took ownership of that data and can
                 // safely pass that ownership to `vec` here.
@@ -517,11 +2482,11 @@ impl<'a, T: 'a> Drop for VecGrowScan<'a, T> {
                 self.vec.set_len(self.end);
             }
 
-            self.vec.splice(
-                self.write..self.write,
-                mem::replace(&mut self.queue, VecDeque::new()).into_iter(),
-            );
+            self.vec.splice_queue(self.write, &mut self.queue);
         }
+
+        self.vec.append(&mut self.tail);
+        self.vec.append(&mut self.parked);
     }
 }
 
@@ -540,6 +2505,13 @@ impl<'s, 'a, T: 'a> VecGrowScanItem<'s, 'a, T> {
     unsafe fn remove_deferring_forget(&mut self) -> T {
         // Read the next item, taking local ownership of the data to return it.
         let result = ptr::read(self.scan.base.add(self.scan.read));
+        self.vacate_deferring_forget();
+        result
+    }
+
+    /// The part of [`remove_deferring_forget`][VecGrowScanItem::remove_deferring_forget] after
+    /// the item was moved out.
+    unsafe fn vacate_deferring_forget(&mut self) {
         // Adjust the read pointer but keep the write pointer to create or widen the gap (see
         // diagrams above).
         self.scan.read += 1;
@@ -548,7 +2520,6 @@ impl<'s, 'a, T: 'a> VecGrowScanItem<'s, 'a, T> {
             ptr::write(self.scan.base.add(self.scan.write), dequeued);
             self.scan.write += 1;
         }
-        result
     }
 
     /// The action of drop, but without actually consuming the item, so that the underlying
@@ -571,6 +2542,7 @@ impl<'s, 'a, T: 'a> VecGrowScanItem<'s, 'a, T> {
             self.scan
                 .queue
                 .push_back(ptr::read(self.scan.base.add(self.scan.read)));
+            self.scan.pending = 0;
             // Move the dequeued item into that same slot.
             ptr::write(self.scan.base.add(self.scan.write), dequeued);
             // Advance the position of the (zero-sized) gap.
@@ -580,6 +2552,7 @@ impl<'s, 'a, T: 'a> VecGrowScanItem<'s, 'a, T> {
             self.scan.read += 1;
             self.scan.write += 1;
         }
+        self.scan.keep_duplicates();
     }
 
     fn into_inner_forget(self) -> &'s mut VecGrowScan<'a, T> {
@@ -601,11 +2574,53 @@ impl<'s, 'a, T: 'a> VecGrowScanItem<'s, 'a, T> {
 
     /// Removes and returns this item from the vector.
     pub fn remove(mut self) -> T {
+        let result = unsafe { self.remove_deferring_forget() };
+        let scan = self.into_inner_forget();
+        scan.keep_duplicates();
+        scan.fill_gap_from_source();
+
+        scan.run_check("removing an item", scan.read - 1);
+        result
+    }
+
+    /// Removes this item from the vector, parking it in a side buffer of the scan.
+    ///
+    /// Parked items can be reinserted later during the scan using
+    /// [`unpark_all_here`][VecGrowScan::unpark_all_here] or
+    /// [`unpark_matching`][VecGrowScan::unpark_matching]. Items that are still parked when the scan
+    /// is dropped are placed at the end of the vector, after any appended items.
+    pub fn park(mut self) {
+        let item = unsafe { self.remove_deferring_forget() };
+        let scan = self.into_inner_forget();
+        scan.parked.push(item);
+        scan.keep_duplicates();
+        scan.fill_gap_from_source();
+
+        scan.run_check("parking an item", scan.read - 1);
+    }
+
+    /// Removes this item from the vector and pushes it onto `target`.
+    ///
+    /// This is equivalent to `target.push(item.remove())`, but moves the item directly into
+    /// `target`'s buffer.
+    pub fn transfer_to(mut self, target: &mut Vec<T>) {
+        // Reserve first, so that nothing changes should this panic.
+        target.reserve(1);
         unsafe {
-            let result = self.remove_deferring_forget();
-            mem::forget(self);
-            result
+            // See `VecMutScanItem::transfer_to`.
+            ptr::copy_nonoverlapping(
+                self.scan.base.add(self.scan.read),
+                target.as_mut_ptr().add(target.len()),
+                1,
+            );
+            target.set_len(target.len() + 1);
+            self.vacate_deferring_forget();
         }
+        let scan = self.into_inner_forget();
+        scan.keep_duplicates();
+        scan.fill_gap_from_source();
+
+        scan.run_check("transferring an item", scan.read - 1);
     }
 
     /// Replaces this item with a new value, returns the old value.
@@ -613,13 +2628,63 @@ impl<'s, 'a, T: 'a> VecGrowScanItem<'s, 'a, T> {
     /// This is equivalent to assigning a new value or calling [`mem::replace`] on the mutable
     /// reference obtained by using [`DerefMut`], but can avoid an intermediate move within the
     /// vector's buffer.
-    pub fn replace(mut self, value: T) -> T {
-        unsafe {
-            let result = self.remove_deferring_forget();
-            self.scan.insert(value);
-            mem::forget(self);
-            result
+    pub fn replace(self, value: T) -> T {
+        self.replace_ref(value).0
+    }
+
+    /// Replaces this item with a new value, returns the old value and a mutable reference to the
+    /// new value.
+    ///
+    /// This is like [`replace`][VecGrowScanItem::replace], but allows further mutation of the new
+    /// value. The returned reference borrows the scan, i.e. it remains valid until the scan is used
+    /// again.
+    pub fn replace_ref(mut self, value: T) -> (T, &'s mut T) {
+        let result = unsafe { self.remove_deferring_forget() };
+        let scan = self.into_inner_forget();
+
+        scan.make_room(1);
+        let queued = scan.write == scan.read;
+        scan.insert_inner(value);
+        let duplicates = scan.duplicates;
+        scan.keep_duplicates();
+        scan.run_check("replacing an item", scan.read - 1);
+        (result, unsafe { scan.placed_before(queued, duplicates) })
+    }
+
+    /// Swaps this item with a value outside of the vector and advances past it.
+    ///
+    /// This is equivalent to calling [`mem::swap`] on the mutable reference obtained by using
+    /// [`DerefMut`], but, like [`replace`][VecGrowScanItem::replace], can avoid an intermediate
+    /// move within the vector's buffer.
+    pub fn swap_out(mut self, other: &mut T) {
+        let result = unsafe { self.remove_deferring_forget() };
+        let scan = self.into_inner_forget();
+
+        scan.make_room(1);
+        if scan.write == scan.read {
+            scan.queue.reserve(1);
         }
+        // Neither branch of `insert_inner` can panic or allocate now.
+        scan.insert_inner(mem::replace(other, result));
+        scan.keep_duplicates();
+        scan.run_check("swapping out an item", scan.read - 1);
+    }
+
+    /// Keeps this item and returns a mutable reference to it.
+    ///
+    /// This is equivalent to dropping the `VecGrowScanItem`, except that the item can still be
+    /// accessed afterwards. The returned reference borrows the scan, i.e. it remains valid until
+    /// the scan is used again.
+    pub fn keep(mut self) -> &'s mut T {
+        // When there is no gap and the queue is non-empty, advancing moves the item to the back of
+        // the queue, otherwise it ends up at the end of the visited prefix.
+        let queued = self.scan.read == self.scan.write && !self.scan.queue.is_empty();
+        let duplicates = self.scan.duplicates;
+        unsafe { self.advance_deferring_forget() };
+        let scan = self.into_inner_forget();
+
+        scan.run_check("keeping an item", scan.read - 1);
+        unsafe { scan.placed_before(queued, duplicates) }
     }
 
     // NOTE: in the following functions, take special care to behave properly when a callback
@@ -630,7 +2695,9 @@ impl<'s, 'a, T: 'a> VecGrowScanItem<'s, 'a, T> {
         let result = unsafe { self.remove_deferring_forget() };
         let scan = self.into_inner_forget();
 
-        scan.insert_many(values);
+        scan.insert_many_inner(values);
+        scan.keep_duplicates();
+        scan.run_check("replacing an item", scan.read - 1);
         result
     }
 
@@ -640,7 +2707,9 @@ impl<'s, 'a, T: 'a> VecGrowScanItem<'s, 'a, T> {
         let removed = unsafe { self.remove_deferring_forget() };
         let scan = self.into_inner_forget();
 
-        scan.insert(f(removed));
+        scan.insert_inner(f(removed));
+        scan.keep_duplicates();
+        scan.run_check("replacing an item", scan.read - 1);
     }
 
     /// Like [`replace_with_many`][VecGrowScanItem::replace_with_many], but compute the replacement
@@ -653,12 +2722,16 @@ impl<'s, 'a, T: 'a> VecGrowScanItem<'s, 'a, T> {
         let removed = unsafe { self.remove_deferring_forget() };
         let scan = self.into_inner_forget();
 
-        scan.insert_many(f(removed));
+        scan.insert_many_inner(f(removed));
+        scan.keep_duplicates();
+        scan.run_check("replacing an item", scan.read - 1);
     }
 
     /// Insert an item before the current item.
-    pub fn insert_before(&mut self, value: T) {
-        self.scan.insert(value);
+    ///
+    /// Returns a mutable reference to the inserted item.
+    pub fn insert_before(&mut self, value: T) -> &mut T {
+        self.scan.insert(value)
     }
 
     /// Insert a sequence of items before the current item.
@@ -675,8 +2748,10 @@ impl<'s, 'a, T: 'a> VecGrowScanItem<'s, 'a, T> {
     /// current item will not be removed. If you need to insert multiple elements, you can either
     /// use [`insert_many_after`][VecGrowScanItem::insert_many_after], or use
     /// [`VecGrowScan::insert`] after you drop this `VecGrowScanItem`.
-    pub fn insert_after(self, value: T) {
-        self.into_inner().insert(value);
+    ///
+    /// Returns a mutable reference to the inserted item.
+    pub fn insert_after(self, value: T) -> &'s mut T {
+        self.into_inner().insert(value)
     }
 
     /// Insert a sequence of items after the current item. Inserted items are not returned during iteration.
@@ -689,6 +2764,98 @@ impl<'s, 'a, T: 'a> VecGrowScanItem<'s, 'a, T> {
         self.into_inner().insert_many(values)
     }
 
+    /// Insert an item after the current item, so that it is returned by the following
+    /// [`next`][VecGrowScan::next] call.
+    ///
+    /// See [`VecGrowScan::insert_next`].
+    pub fn insert_visit_after(self, value: T) {
+        self.into_inner().insert_next(value)
+    }
+
+    /// Insert a sequence of items after the current item, so that they are returned by the
+    /// following [`next`][VecGrowScan::next] calls, in order.
+    ///
+    /// See [`VecGrowScan::insert_next`].
+    pub fn insert_many_visit_after(self, values: impl IntoIterator<Item = T>) {
+        self.into_inner().insert_many_next(values)
+    }
+
+    /// Insert a clone of this item directly after it, without consuming this wrapper.
+    ///
+    /// The clone is not visited by the scan. This wrapper keeps referring to the original item, so
+    /// further changes, including more duplication, affect only the original. The clones are kept
+    /// in place when the original is removed or replaced. Until this wrapper is consumed, they are
+    /// part of the remaining items returned by [`slices`][VecGrowScanItem::slices].
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// let mut numbers = vec![1, 2, 3];
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    ///
+    /// while let Some(mut item) = scan.next() {
+    ///     for _ in 1..*item {
+    ///         item.duplicate();
+    ///         *item += 10;
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 12, 2, 23, 13, 3]);
+    /// ```
+    pub fn duplicate(&mut self)
+    where
+        T: Clone,
+    {
+        let value = (**self).clone();
+        let scan = &mut *self.scan;
+        unsafe {
+            // Move the original out and the clone into its place, then put the original back in
+            // front of the clone. Should `insert_next` panic while allocating, the original is
+            // leaked, which is safe.
+            let original = ptr::read(scan.base.add(scan.read));
+            ptr::write(scan.base.add(scan.read), value);
+            scan.insert_next(original);
+        }
+        scan.duplicates += 1;
+    }
+
+    /// Swap this item with the next remaining item.
+    ///
+    /// Afterwards, this wrapper refers to the item that was following it, so that it can be
+    /// examined again, while this item becomes the next remaining item. Returns `false` and does
+    /// nothing if this is the last item. Appended items are not considered remaining.
+    pub fn swap_with_next(&mut self) -> bool {
+        // Skip the clones made by `duplicate`, which stay directly after the current item.
+        let next = self.scan.duplicates + 1;
+        let remaining = self.slices_mut().3;
+        if remaining.len() <= next {
+            return false;
+        }
+        remaining.swap(0, next);
+        true
+    }
+
+    /// Postpone this item, so that it is visited again after `n` more items.
+    ///
+    /// See [`VecMutScanItem::defer`]. Items inserted using [`insert_next`][VecGrowScan::insert_next]
+    /// or the `insert_visit_after` methods count towards the `n` items, appended items do not.
+    /// Deferring the last remaining item keeps it.
+    pub fn defer(mut self, n: usize) {
+        // The clones made by `duplicate` are kept in place of this item.
+        let duplicates = self.scan.duplicates;
+        if self.slices().3.len() == duplicates + 1 {
+            // Dropping `self` keeps the item and its clones.
+            return;
+        }
+        self.slices_mut().3[..duplicates + 1].rotate_left(1);
+        self.scan.keep_duplicates();
+        let remaining = self.slices_mut().3;
+        let len = remaining.len().min(n.saturating_add(1));
+        remaining[..len].rotate_left(1);
+        // The current position now contains the next remaining item, so this must not advance.
+        self.into_inner_forget();
+    }
+
     /// Access the whole vector.
     ///
     /// This provides access to the whole vector at any point during the scan.
@@ -721,35 +2888,536 @@ impl<'s, 'a, T: 'a> VecGrowScanItem<'s, 'a, T> {
 impl<'s, 'a, T: 'a> Deref for VecGrowScanItem<'s, 'a, T> {
     type Target = T;
 
-    fn deref(&self) -> &Self::Target {
-        // Within a `VecGrowScanItem` the offset `scan.read` contains valid data owned by the
-        // `VecGrowScan` on which we have a mutable borrow, thus we are allowed to reference it.
-        unsafe { &*self.scan.base.add(self.scan.read) }
+    fn deref(&self) -> &Self::Target {
+        // Within a `VecGrowScanItem` the offset `scan.read` contains valid data owned by the
+        // `VecGrowScan` on which we have a mutable borrow, thus we are allowed to reference it.
+        unsafe { &*self.scan.base.add(self.scan.read) }
+    }
+}
+
+impl<'s, 'a, T: 'a> DerefMut for VecGrowScanItem<'s, 'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // Within a `VecGrowScanItem` the offset `scan.read` contains valid data owned by the
+        // `VecGrowScan` on which we have a mutable borrow, thus we are allowed to mutably reference
+        // it.
+        unsafe { &mut *self.scan.base.add(self.scan.read) }
+    }
+}
+
+impl<'s, 'a, T: 'a> Drop for VecGrowScanItem<'s, 'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            self.advance_deferring_forget();
+        }
+        self.scan.run_check("keeping an item", self.scan.read - 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{boxed::Box, rc::Rc, vec};
+
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    #[test]
+    fn auto_traits() {
+        assert_send::<VecMutScan<u8>>();
+        assert_sync::<VecMutScan<u8>>();
+        assert_send::<VecMutScanItem<u8>>();
+        assert_sync::<VecMutScanItem<u8>>();
+        assert_send::<VecGrowScan<u8>>();
+        assert_sync::<VecGrowScan<u8>>();
+        assert_send::<VecGrowScanItem<u8>>();
+        assert_sync::<VecGrowScanItem<u8>>();
+        assert_send::<SplitScans<u8>>();
+        assert_sync::<SplitScans<u8>>();
+
+        // `Cell` is `Send` but not `Sync`.
+        assert_send::<VecMutScan<core::cell::Cell<u8>>>();
+        assert_send::<VecMutScanItem<core::cell::Cell<u8>>>();
+        assert_send::<VecGrowScan<core::cell::Cell<u8>>>();
+        assert_send::<VecGrowScanItem<core::cell::Cell<u8>>>();
+    }
+
+    #[test]
+    fn visit_inserted() {
+        let mut input: Vec<_> = vec![Rc::new(3), Rc::new(1)];
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.insert_next(Rc::new(2));
+        scan.insert(Rc::new(0));
+
+        // Expand every `n` into `n` copies of `n - 1`.
+        let mut visited = vec![];
+        while let Some(item) = scan.next() {
+            visited.push(**item);
+            let n = **item;
+            if n > 1 {
+                item.insert_many_visit_after((0..n).map(|_| Rc::new(n - 1)));
+            } else if n == 0 {
+                item.remove();
+            }
+        }
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(visited, [2, 1, 1, 3, 2, 1, 1, 2, 1, 1, 2, 1, 1, 1]);
+        assert_eq!(values, [0, 2, 1, 1, 3, 2, 1, 1, 2, 1, 1, 2, 1, 1, 1]);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    fn swap_with_next() {
+        let mut input = vec![5, 1, 4, 2, 8, 0];
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.insert(9);
+
+        // A single bubble sort pass that also removes zeros.
+        while let Some(mut item) = scan.next() {
+            if item.slices().3.get(1).map_or(false, |next| *next < *item) {
+                assert!(item.swap_with_next());
+            }
+            if *item == 8 {
+                assert!(!item.swap_with_next());
+            } else if *item == 0 {
+                item.remove();
+            }
+        }
+        drop(scan);
+
+        assert_eq!(input, [9, 1, 4, 2, 5, 8]);
+    }
+
+    #[test]
+    fn defer() {
+        let mut input: Vec<_> = (0..5).map(Rc::new).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+
+        let mut visited = vec![];
+        let mut deferred = 0;
+        while let Some(item) = scan.next() {
+            visited.push(**item);
+            if **item == 1 && deferred < 2 {
+                deferred += 1;
+                item.defer(1);
+            } else if **item == 3 {
+                item.replace_with_many(vec![Rc::new(30), Rc::new(31)]);
+            } else if **item == 4 && deferred < 3 {
+                // With no following items, this item is kept.
+                deferred += 1;
+                item.defer(10);
+            } else if **item == 0 {
+                item.remove();
+            }
+        }
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(visited, [0, 1, 2, 1, 3, 1, 4]);
+        assert_eq!(deferred, 3);
+        assert_eq!(values, [2, 30, 31, 1, 4]);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    fn duplicate() {
+        let mut input: Vec<_> = (0..4).map(Rc::new).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+
+        while let Some(mut item) = scan.next() {
+            match **item {
+                0 => {
+                    item.duplicate();
+                    *item.keep() = Rc::new(10);
+                }
+                1 => item.duplicate(),
+                2 => {
+                    item.duplicate();
+                    item.duplicate();
+                    item.remove();
+                }
+                3 => {
+                    item.duplicate();
+                    assert!(!item.swap_with_next());
+                    *item = Rc::new(30);
+                }
+                _ => (),
+            }
+        }
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [10, 0, 1, 1, 2, 2, 30, 3]);
+        assert_eq!(Rc::strong_count(&input[2]), 2);
+        assert_eq!(Rc::strong_count(&input[4]), 2);
+        assert_eq!(Rc::strong_count(&input[6]), 1);
+        assert_eq!(Rc::strong_count(&input[7]), 1);
+    }
+
+    #[test]
+    fn insert_source() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let pulled = AtomicUsize::new(0);
+        let mut input: Vec<_> = (0..8).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next().unwrap().remove();
+        scan.next();
+
+        scan.set_insert_source((100..103).inspect(|_| {
+            pulled.fetch_add(1, Ordering::Relaxed);
+        }));
+        assert_eq!(pulled.load(Ordering::Relaxed), 1);
+
+        while let Some(item) = scan.next() {
+            match *item {
+                2 => {
+                    item.insert_after(50);
+                }
+                3 | 4 => {
+                    item.remove();
+                }
+                5 => break,
+                _ => (),
+            }
+        }
+        // The queued item fills the first gap.
+        assert_eq!(pulled.load(Ordering::Relaxed), 2);
+
+        // The remaining items of the previous source are queued.
+        scan.set_insert_source(vec![200, 201].into_iter());
+        assert_eq!(pulled.load(Ordering::Relaxed), 3);
+        scan.next().unwrap().remove();
+        scan.next().unwrap().remove();
+        drop(scan);
+
+        assert_eq!(input, [1, 100, 2, 50, 101, 5, 102, 200, 201]);
+    }
+
+    #[test]
+    fn gap_introspection() {
+        let mut input: Vec<_> = (0..6).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+        assert!(!scan.has_gap());
+        scan.next().unwrap().remove();
+        scan.next().unwrap().remove();
+        assert_eq!(scan.gap_len(), 2);
+        scan.insert(10);
+        assert_eq!(scan.gap_len(), 1);
+        scan.next();
+        scan.insert(11);
+        assert!(!scan.has_gap());
+        scan.insert(12);
+        scan.next().unwrap().remove();
+        // The gap is filled from the queue.
+        assert!(!scan.has_gap());
+        drop(scan);
+        assert_eq!(input, [10, 2, 11, 12, 4, 5]);
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next();
+        scan.next().unwrap().remove();
+        assert_eq!(scan.gap_len(), 1);
+    }
+
+    #[test]
+    fn reuse_queue() {
+        let item = Rc::new(0);
+        let mut queue = VecDeque::with_capacity(8);
+        queue.push_back(item.clone());
+
+        for _ in 0..3 {
+            let mut input = vec![item.clone(); 4];
+            let mut scan = VecGrowScan::with_queue(&mut input, queue);
+            scan.insert(item.clone());
+            scan.next();
+            scan.insert(item.clone());
+            scan.insert(item.clone());
+            scan.append(item.clone());
+            queue = scan.into_queue();
+
+            assert!(queue.is_empty());
+            assert!(queue.capacity() >= 8);
+            assert_eq!(input.len(), 8);
+            assert_eq!(Rc::strong_count(&item), 9);
+        }
+        assert_eq!(Rc::strong_count(&item), 1);
+
+        let mut input: Vec<_> = (0..3).collect();
+        let mut scan = VecGrowScan::with_queue_capacity(&mut input, 10);
+        assert!(scan.queue.capacity() >= 10);
+        scan.next();
+        scan.insert(10);
+        drop(scan);
+        assert_eq!(input, [0, 10, 1, 2]);
+    }
+
+    #[test]
+    fn park_unpark() {
+        let mut input: Vec<_> = (0..10).map(Rc::new).collect();
+        let input_copy = input.clone();
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next().unwrap().park();
+        scan.next().unwrap().park();
+        scan.next();
+        scan.next().unwrap().park();
+        assert_eq!(scan.parked().len(), 3);
+        assert_eq!(scan.unpark_matching(|x| **x % 2 == 1), 2);
+        scan.next().unwrap().remove();
+        scan.unpark_all_here();
+        assert!(scan.parked().is_empty());
+        scan.next().unwrap().park();
+        scan.next();
+        scan.append(Rc::new(20));
+        scan.next().unwrap().park();
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [2, 1, 3, 0, 6, 8, 9, 20, 5, 7]);
+        drop(input_copy);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    fn insert_with() {
+        let mut input: Vec<_> = (0..4).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next().unwrap().remove();
+        *scan.insert_with(|| 10) += 1;
+        *scan.insert_with(|| 20) += 1;
+        scan.next();
+        drop(scan);
+        assert_eq!(input, [11, 21, 1, 2, 3]);
+    }
+
+    #[test]
+    fn insert_strategies() {
+        for &strategy in &[
+            InsertStrategy::Queue,
+            InsertStrategy::Gap,
+            InsertStrategy::Auto,
+        ] {
+            let mut input: Vec<_> = (0..40).map(Rc::new).collect();
+            let input_copy = input.clone();
+            let mut expected = vec![];
+            let mut next = 0;
+            let mut value = 100;
+
+            let mut scan = VecGrowScan::new(&mut input);
+            scan.set_insert_strategy(strategy);
+            for step in 0..80usize {
+                value += 1;
+                match step * 7 % 6 {
+                    0 | 1 => {
+                        scan.insert(Rc::new(value));
+                        expected.push(value);
+                    }
+                    2 => {
+                        scan.insert_many((0..3).map(|i| Rc::new(value * 10 + i)));
+                        expected.extend((0..3).map(|i| value * 10 + i));
+                    }
+                    3 => {
+                        if let Some(item) = scan.next() {
+                            let (_, new) = item.replace_ref(Rc::new(value));
+                            assert_eq!(**new, value);
+                            expected.push(value);
+                            next += 1;
+                        }
+                    }
+                    4 => {
+                        if let Some(item) = scan.next() {
+                            item.remove();
+                            next += 1;
+                        }
+                    }
+                    _ => {
+                        if scan.next().is_some() {
+                            expected.push(next);
+                            next += 1;
+                        }
+                    }
+                }
+            }
+            drop(scan);
+            expected.extend(next..40);
+
+            let values: Vec<_> = input.iter().map(|x| **x).collect();
+            assert_eq!(values, expected);
+            drop(input);
+            assert!(input_copy.iter().all(|x| Rc::strong_count(x) == 1));
+        }
+    }
+
+    #[test]
+    fn invariants() {
+        let mut input: Vec<_> = (0..6).collect();
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next().unwrap().remove();
+        scan.next();
+        scan.assert_invariants();
+        drop(scan);
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next();
+        scan.insert(10);
+        scan.assert_invariants();
+        scan.next().unwrap().remove();
+        scan.next().unwrap().remove();
+        scan.assert_invariants();
+        drop(scan);
+        assert_eq!(input, [1, 10, 4, 5]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn invariant_violation() {
+        let mut input: Vec<_> = (0..6).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next().unwrap().remove();
+        // Pretend an item was queued despite the gap.
+        scan.queue.push_back(10);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            scan.assert_invariants();
+        }));
+        let message = result.unwrap_err();
+        assert_eq!(
+            message.downcast_ref::<alloc::string::String>().unwrap(),
+            "1 items are queued while there is a gap of 1 items"
+        );
+
+        scan.queue.clear();
+        drop(scan);
+        assert_eq!(input, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn replace_rest() {
+        let mut input: Vec<_> = (0..6).map(Rc::new).collect();
+        let input_copy = input.clone();
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next();
+        scan.insert(Rc::new(10));
+        scan.next();
+        let old = scan.replace_rest((20..25).map(Rc::new));
+        assert_eq!(old.len(), 4);
+        scan.next().unwrap().remove();
+        scan.next();
+        scan.set_rest(vec![Rc::new(30)]);
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [0, 10, 1, 21, 30]);
+        drop((input, old));
+        assert!(input_copy.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    fn flush_pending() {
+        let mut input: Vec<_> = (0..4).map(Rc::new).collect();
+        let input_copy = input.clone();
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next()
+            .unwrap()
+            .insert_many_after((10..13).map(Rc::new));
+        scan.flush_pending();
+        scan.flush_pending();
+        scan.next();
+        scan.next().unwrap().remove();
+        scan.insert(Rc::new(20));
+        scan.flush_pending();
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [0, 10, 11, 12, 1, 20, 3]);
+        drop(input);
+        assert!(input_copy.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    fn take_prefix() {
+        let mut input: Vec<_> = (0..10).map(Rc::new).collect();
+        let input_copy = input.clone();
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.insert(Rc::new(10));
+        assert_eq!(scan.take_matching_prefix(|x| **x < 3).len(), 3);
+        scan.next();
+        assert_eq!(scan.take_matching_prefix(|x| **x < 6).len(), 2);
+        assert!(scan.take_matching_prefix(|_| false).is_empty());
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [10, 3, 6, 7, 8, 9]);
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next().unwrap().remove();
+        let taken: Vec<_> = scan.take_matching_prefix(|x| **x != 8);
+        drop(scan);
+
+        let values: Vec<_> = taken.iter().map(|x| **x).collect();
+        assert_eq!(values, [3, 6, 7]);
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [8, 9]);
+        drop((input, taken));
+        assert!(input_copy.iter().all(|x| Rc::strong_count(x) == 1));
     }
-}
 
-impl<'s, 'a, T: 'a> DerefMut for VecGrowScanItem<'s, 'a, T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        // Within a `VecGrowScanItem` the offset `scan.read` contains valid data owned by the
-        // `VecGrowScan` on which we have a mutable borrow, thus we are allowed to mutably reference
-        // it.
-        unsafe { &mut *self.scan.base.add(self.scan.read) }
-    }
-}
+    #[test]
+    fn internal_iteration() {
+        let mut input: Vec<_> = (0..10).map(Rc::new).collect();
+        let input_copy = input.clone();
 
-impl<'s, 'a, T: 'a> Drop for VecGrowScanItem<'s, 'a, T> {
-    fn drop(&mut self) {
-        unsafe {
-            self.advance_deferring_forget();
-        }
-    }
-}
+        let mut scan = VecMutScan::new(&mut input);
+        let removed = scan.fold_retain(vec![], |mut removed, x| {
+            let keep = **x % 3 != 1;
+            if !keep {
+                removed.push(**x);
+            }
+            (removed, keep)
+        });
+        drop(scan);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        assert_eq!(removed, [1, 4, 7]);
+        assert_eq!(input.len(), 7);
 
-    use alloc::{boxed::Box, rc::Rc, vec};
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.insert(Rc::new(10));
+        scan.insert(Rc::new(11));
+        scan.next().unwrap().remove();
+        scan.retain_rest(|x| **x != 5);
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [10, 11, 2, 3, 6, 8, 9]);
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next();
+        scan.finish_retain(|x| **x % 2 == 0);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [10, 2, 6, 8]);
+
+        let mut scan = VecMutScan::new(&mut input);
+        assert_eq!(scan.fill_gap_with(vec![Rc::new(20)]), 0);
+        scan.next().unwrap().remove();
+        scan.next();
+        scan.next().unwrap().remove();
+        scan.next().unwrap().remove();
+        assert_eq!(scan.fill_gap_with(vec![Rc::new(21)]), 1);
+        assert_eq!(scan.fill_gap_with((22..25).map(Rc::new)), 2);
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [2, 21, 22, 23]);
+        drop(input);
+        assert!(input_copy.iter().all(|x| Rc::strong_count(x) == 1));
+    }
 
     #[test]
     fn check_item_drops() {
@@ -842,6 +3510,157 @@ mod tests {
         assert_eq!(input, b"fooBarBaz");
     }
 
+    #[test]
+    fn start_at() {
+        let mut input: Vec<_> = (0..10).map(Rc::new).collect();
+        let input_copy = input.clone();
+
+        let mut scan = VecMutScan::start_at(&mut input, 4);
+
+        assert_eq!(scan.slices().0.len(), 4);
+        assert_eq!(**scan.next().unwrap(), 4);
+        while let Some(item) = scan.next() {
+            if **item % 3 == 0 {
+                item.remove();
+            }
+        }
+
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|rc| **rc).collect();
+        assert_eq!(values, [0, 1, 2, 3, 4, 5, 7, 8]);
+
+        let ref_counts: Vec<_> = input_copy.iter().map(Rc::strong_count).collect();
+        assert_eq!(ref_counts, [2, 2, 2, 2, 2, 2, 1, 2, 2, 1]);
+    }
+
+    #[test]
+    fn keep() {
+        let mut input: Vec<_> = (1..=6).collect();
+        let mut scan = VecMutScan::new(&mut input);
+
+        let mut prev = None;
+        while let Some(item) = scan.next() {
+            if *item % 3 == 0 {
+                item.remove();
+            } else {
+                let current = item.keep();
+                if let Some(prev) = prev {
+                    *current += prev;
+                }
+                prev = Some(*current);
+            }
+        }
+
+        drop(scan);
+
+        assert_eq!(input, [1, 3, 7, 12]);
+    }
+
+    #[test]
+    fn grow_keep() {
+        let mut input = vec![1, 2, 3, 4];
+        let mut scan = VecGrowScan::new(&mut input);
+
+        *scan.next().unwrap().keep() += 10;
+        scan.insert(0);
+        *scan.next().unwrap().keep() += 20;
+        scan.next().unwrap().remove();
+        *scan.next().unwrap().keep() += 30;
+
+        drop(scan);
+
+        assert_eq!(input, [11, 0, 22, 34]);
+    }
+
+    #[test]
+    fn replace_ref() {
+        let mut input = vec![1, 2, 3];
+        let mut scan = VecMutScan::new(&mut input);
+
+        scan.next().unwrap().remove();
+        let (old, new) = scan.next().unwrap().replace_ref(20);
+        *new += old;
+
+        drop(scan);
+
+        assert_eq!(input, [22, 3]);
+    }
+
+    #[test]
+    fn grow_replace_ref() {
+        let mut input = vec![1, 2, 3];
+        let mut scan = VecGrowScan::new(&mut input);
+
+        scan.insert(0);
+        let (old, new) = scan.next().unwrap().replace_ref(10);
+        *new += old;
+        let (old, new) = scan.next().unwrap().replace_ref(20);
+        *new += old;
+
+        drop(scan);
+
+        assert_eq!(input, [0, 11, 22, 3]);
+    }
+
+    #[test]
+    fn grow_start_at() {
+        let mut nums = vec![1, 2, 3, 5, 6];
+        let mut scan = VecGrowScan::start_at(&mut nums, 3);
+
+        scan.insert(4);
+        assert_eq!(*scan.next().unwrap(), 5);
+
+        drop(scan);
+
+        assert_eq!(nums, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn invariant_check() {
+        let mut input: Vec<_> = (0..8).collect();
+        let mut scan = VecMutScan::new(&mut input);
+        let mut calls = 0;
+
+        scan.set_invariant_check(|prefix, suffix| {
+            calls += 1;
+            prefix.len() + suffix.len() >= 6
+        });
+
+        while let Some(item) = scan.next() {
+            if *item % 4 == 1 {
+                item.remove();
+            } else if *item % 4 == 2 {
+                item.replace(20);
+            }
+        }
+
+        drop(scan);
+
+        assert_eq!(calls, 9);
+        assert_eq!(input, [0, 20, 3, 4, 20, 7]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "invariant check failed after inserting an item at buffer index 2")]
+    fn grow_invariant_check_failure() {
+        let mut input = vec![1, 2, 4];
+        let mut scan = VecGrowScan::new(&mut input);
+        // Opening a gap would shift the reported index.
+        scan.set_insert_strategy(InsertStrategy::Queue);
+
+        scan.set_invariant_check(|a, b, c, d| {
+            let all: Vec<_> = a.iter().chain(b).chain(c).chain(d).collect();
+            all.windows(2).all(|w| w[0] <= w[1])
+        });
+
+        scan.next();
+        scan.next().unwrap().insert_after(3);
+        scan.insert(5);
+    }
+
     #[test]
     fn grow_check_item_drops() {
         let mut input: Vec<_> = vec![0, 1, 2, 3, 4, 5, 6, 7]
@@ -965,6 +3784,24 @@ mod tests {
         assert_eq!(nums, [1, 2, 3, 4, 5]);
     }
 
+    #[test]
+    fn insert_returns_reference() {
+        let mut nums = vec![1, 2, 3];
+        let mut scan = VecGrowScan::new(&mut nums);
+
+        *scan.insert(0) += 10;
+        let mut item = scan.next().unwrap();
+        *item.insert_before(0) += 20;
+        *item.insert_after(0) += 30;
+        scan.next().unwrap().remove();
+        *scan.insert(0) += 40;
+        *scan.append(0) += 50;
+
+        drop(scan);
+
+        assert_eq!(nums, [10, 20, 1, 30, 40, 3, 50]);
+    }
+
     #[test]
     fn drop_after_partial_scan_with_inserts() {
         let mut nums = vec![1, 2, 5, 6];
@@ -982,6 +3819,22 @@ mod tests {
         assert_eq!(nums, [1, 2, 3, 4, 5, 6]);
     }
 
+    #[test]
+    fn append() {
+        let mut nums = vec![1, 2, 3, 4];
+        let mut scan = VecGrowScan::new(&mut nums);
+
+        scan.append(10);
+        scan.next();
+        scan.next().unwrap().insert_after(20);
+        scan.append_many([11, 12].iter().copied());
+        assert_eq!(scan.appended(), [10, 11, 12]);
+
+        drop(scan);
+
+        assert_eq!(nums, [1, 2, 20, 3, 4, 10, 11, 12]);
+    }
+
     #[test]
     fn replace_with() {
         let mut vec = (1..=5).map(Box::new).collect();
@@ -1020,4 +3873,338 @@ mod tests {
 
         assert_eq!(vec, [2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13]);
     }
+
+    #[test]
+    fn transfer_to() {
+        let mut input: Vec<_> = (0..6).map(Rc::new).collect();
+        let input_copy = input.clone();
+        let mut target = vec![Rc::new(10)];
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next();
+        scan.next().unwrap().transfer_to(&mut target);
+        scan.next().unwrap().transfer_to(&mut target);
+        drop(scan);
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.insert(Rc::new(20));
+        scan.next().unwrap().transfer_to(&mut target);
+        scan.next();
+        scan.next().unwrap().transfer_to(&mut target);
+        drop(scan);
+
+        let values = |v: &Vec<Rc<i32>>| v.iter().map(|x| **x).collect::<Vec<_>>();
+        assert_eq!(values(&input), [20, 3, 5]);
+        assert_eq!(values(&target), [10, 1, 2, 0, 4]);
+        drop(input_copy);
+        assert!(input
+            .iter()
+            .chain(&target)
+            .all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    fn swap_out() {
+        let mut input: Vec<_> = (0..6).map(Rc::new).collect();
+        let input_copy = input.clone();
+        let mut other = Rc::new(10);
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next().unwrap().remove();
+        scan.next().unwrap().swap_out(&mut other);
+        drop(scan);
+        assert_eq!(*other, 1);
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.insert(Rc::new(20));
+        scan.next().unwrap().swap_out(&mut other);
+        assert_eq!(*other, 10);
+        scan.next().unwrap().remove();
+        scan.next().unwrap().swap_out(&mut other);
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [20, 1, 10, 4, 5]);
+        assert_eq!(*other, 3);
+        drop(input_copy);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    fn insert_cloned() {
+        let mut input: Vec<_> = (0..4).map(Rc::new).collect();
+        let input_copy = input.clone();
+        let value = Rc::new(10);
+        let values = [Rc::new(20), Rc::new(21), Rc::new(22)];
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next().unwrap().remove();
+        scan.insert_many_cloned(&values);
+        *scan.insert_cloned(&value) = Rc::new(11);
+        scan.next();
+        scan.insert_cloned(&value);
+        drop(scan);
+
+        let result: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(result, [20, 21, 22, 11, 1, 10, 2, 3]);
+        assert_eq!(Rc::strong_count(&value), 2);
+        assert!(values.iter().all(|x| Rc::strong_count(x) == 2));
+        drop(input_copy);
+    }
+
+    #[test]
+    fn gap_in_spare_capacity() {
+        let mut input: Vec<u32> = Vec::with_capacity(64);
+        input.extend(0..8);
+        let buffer = input.as_ptr();
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next();
+        scan.insert_many(vec![10, 11]);
+        // The gap uses the spare capacity instead of queueing the items.
+        assert!(scan.has_gap());
+        assert_eq!(scan.slices().0, [0, 10, 11]);
+        loop {
+            let value = match scan.next() {
+                Some(item) => *item,
+                None => break,
+            };
+            for _ in 0..10 {
+                scan.insert(value);
+            }
+        }
+        drop(scan);
+
+        assert_eq!(input.len(), 80);
+        assert_eq!(input[3..14], [1; 11]);
+        assert_ne!(input.as_ptr(), buffer);
+    }
+
+    #[test]
+    fn swap_remaining() {
+        let mut input: Vec<_> = (0..6).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next().unwrap().remove();
+        scan.insert(10);
+        scan.swap_remaining(0, 4);
+        assert_eq!(*scan.next().unwrap(), 5);
+        drop(scan);
+        assert_eq!(input, [10, 5, 2, 3, 4, 1]);
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next().unwrap().remove();
+        scan.swap_remaining(1, 1);
+        scan.swap_remaining(4, 0);
+        drop(scan);
+        assert_eq!(input, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut input: Vec<_> = (0..6).map(Rc::new).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.set_insert_strategy(InsertStrategy::Queue);
+        scan.next();
+        scan.insert(Rc::new(10));
+        scan.next().unwrap().park();
+        scan.next();
+        scan.append(Rc::new(20));
+        let snapshot = scan.snapshot();
+        drop(scan);
+
+        let values = |v: &Vec<Rc<i32>>| v.iter().map(|x| **x).collect::<Vec<_>>();
+        assert_eq!(values(&snapshot), [0, 10, 2, 3, 4, 5, 20, 1]);
+        assert_eq!(values(&snapshot), values(&input));
+        drop(snapshot);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next().unwrap().remove();
+        scan.next();
+        assert_eq!(values(&scan.snapshot()), [10, 2, 3, 4, 5, 20, 1]);
+    }
+
+    #[test]
+    fn insert_vec() {
+        let mut input: Vec<_> = (0..6).map(Rc::new).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.set_insert_strategy(InsertStrategy::Queue);
+        scan.next().unwrap().remove();
+        scan.next().unwrap().remove();
+        let donor: Vec<_> = (10..15).map(Rc::new).collect();
+        let donor_buffer = donor.as_ptr();
+        scan.insert_vec(donor);
+        // Two items fill the gap, the rest is queued in the donor's buffer.
+        assert_eq!(scan.queue.as_slices().0.as_ptr(), donor_buffer);
+        scan.next();
+        scan.insert_vec(vec![]);
+        scan.insert_vec(vec![Rc::new(20)]);
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [10, 11, 12, 13, 14, 2, 20, 3, 4, 5]);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.set_insert_strategy(InsertStrategy::Gap);
+        scan.next();
+        scan.insert_vec((30..33).map(Rc::new).collect());
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [10, 30, 31, 32, 11, 12, 13, 14, 2, 20, 3, 4, 5]);
+    }
+
+    #[test]
+    fn remove_rest_into() {
+        let mut input: Vec<_> = (0..6).map(Rc::new).collect();
+        let mut target = vec![Rc::new(10)];
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next().unwrap().remove();
+        scan.next();
+        scan.remove_rest_into(&mut target);
+
+        let values = |v: &Vec<Rc<i32>>| v.iter().map(|x| **x).collect::<Vec<_>>();
+        assert_eq!(values(&input), [1]);
+        assert_eq!(values(&target), [10, 2, 3, 4, 5]);
+
+        let mut input: Vec<_> = (0..4).map(Rc::new).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.set_insert_strategy(InsertStrategy::Queue);
+        scan.next();
+        scan.insert(Rc::new(20));
+        scan.append(Rc::new(30));
+        scan.next();
+        scan.remove_rest_into(&mut target);
+
+        assert_eq!(values(&input), [0, 20, 1, 30]);
+        assert_eq!(values(&target), [10, 2, 3, 4, 5, 2, 3]);
+        assert!(input
+            .iter()
+            .chain(&target)
+            .all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    fn drain_pending() {
+        let mut input: Vec<_> = (0..6).map(Rc::new).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.set_insert_strategy(InsertStrategy::Queue);
+        scan.next();
+        scan.insert(Rc::new(10));
+        scan.next();
+        scan.insert_many((11..13).map(Rc::new));
+        scan.insert_vec(vec![Rc::new(13)]);
+        let values = |v: &[Rc<i32>]| v.iter().map(|x| **x).collect::<Vec<_>>();
+        // The first insertion is followed by a kept item.
+        assert_eq!(values(&scan.drain_pending()), [11, 12, 13]);
+        assert!(scan.drain_pending().is_empty());
+
+        scan.insert(Rc::new(14));
+        scan.next().unwrap().remove();
+        scan.next().unwrap().remove();
+        // Both queued items were moved into the slots of the removed items.
+        assert!(scan.drain_pending().is_empty());
+        scan.insert(Rc::new(15));
+        scan.insert_next(Rc::new(16));
+        assert_eq!(values(&scan.drain_pending()), [15]);
+        drop(scan);
+
+        assert_eq!(values(&input), [0, 10, 1, 14, 16, 4, 5]);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    fn fold_retain_insert_source() {
+        let mut input: Vec<_> = (0..8).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.set_insert_source(vec![10, 11].into_iter());
+        scan.retain_rest(|x| *x % 3 != 0);
+        drop(scan);
+
+        assert_eq!(input, [10, 1, 2, 11, 4, 5, 7]);
+    }
+
+    #[test]
+    fn duplicate_defer() {
+        let mut input: Vec<_> = (0..3).map(Rc::new).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+
+        let mut seen = vec![];
+        while let Some(mut item) = scan.next() {
+            seen.push(**item);
+            if seen.len() == 1 {
+                item.duplicate();
+                item.defer(1);
+            }
+        }
+        drop(scan);
+
+        assert_eq!(seen, [0, 1, 0, 2]);
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [0, 1, 0, 2]);
+        assert_eq!(Rc::strong_count(&input[0]), 2);
+    }
+
+    #[test]
+    fn duplicate_queued() {
+        let mut input: Vec<_> = (1..6).map(Rc::new).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+
+        scan.next();
+        // Without a gap, the original and its clones are moved through the queue.
+        let mut item = scan.next().unwrap();
+        item.duplicate();
+        *item.keep() = Rc::new(20);
+        let mut item = scan.next().unwrap();
+        item.duplicate();
+        item.duplicate();
+        let (old, new) = item.replace_ref(Rc::new(30));
+        *new = Rc::new(31);
+        assert_eq!(*old, 3);
+        drop(old);
+        scan.insert(Rc::new(40));
+        let mut item = scan.next().unwrap();
+        item.duplicate();
+        assert_eq!(**item.keep(), 4);
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [1, 20, 2, 31, 3, 3, 40, 4, 4, 5]);
+        assert!(input.iter().all(|x| Rc::strong_count(x) <= 2));
+    }
+
+    #[test]
+    fn defer_last() {
+        let mut input = vec![1, 2, 3];
+        let mut scan = VecMutScan::new(&mut input);
+
+        let mut visited = vec![];
+        while let Some(item) = scan.next() {
+            visited.push(*item);
+            if *item == 3 || visited.len() == 1 {
+                item.defer(5);
+            }
+        }
+        drop(scan);
+
+        assert_eq!(visited, [1, 2, 3, 1, 3]);
+        assert_eq!(input, [2, 1, 3]);
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.append(4);
+        let mut visited = vec![];
+        while let Some(mut item) = scan.next() {
+            visited.push(*item);
+            if *item == 3 {
+                // Appended items are not remaining, so this is the last remaining item.
+                item.duplicate();
+                item.defer(1);
+            }
+        }
+        drop(scan);
+
+        assert_eq!(visited, [2, 1, 3]);
+        assert_eq!(input, [2, 1, 3, 3, 4]);
+    }
 }
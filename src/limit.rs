@@ -0,0 +1,183 @@
+use crate::{VecMutScan, VecMutScanItem};
+
+impl<'a, T: 'a> VecMutScan<'a, T> {
+    /// Keep the longest prefix of the remaining items that all satisfy `pred`, without visiting
+    /// them, and return their number.
+    ///
+    /// The kept items are moved over the gap left by removed items with a single move. Afterwards
+    /// the scan continues with the first item not satisfying `pred`.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut lines = vec!["# a", "# b", "c", "# d"];
+    /// let mut scan = VecMutScan::new(&mut lines);
+    ///
+    /// // Remove everything after the leading comments.
+    /// assert_eq!(scan.skip_while(|line| line.starts_with('#')), 2);
+    /// while let Some(line) = scan.next() {
+    ///     line.remove();
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(lines, ["# a", "# b"]);
+    /// ```
+    pub fn skip_while(&mut self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        let count = self
+            .remaining()
+            .iter()
+            .take_while(|item| pred(item))
+            .count();
+        self.keep_many(count);
+        count
+    }
+
+    /// Returns a view of the scan that visits only the next remaining item and every `step`-th
+    /// item after it, keeping the items in between.
+    ///
+    /// Like with [`Iterator::step_by`], the first remaining item is always visited. The skipped
+    /// items are moved over the gap left by removed items at once. Once the view is dropped, the
+    /// scan continues after the last visited item.
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers: Vec<u32> = (0..10).collect();
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    ///
+    /// let mut every_third = scan.step_by(3);
+    /// while let Some(item) = every_third.next() {
+    ///     item.remove();
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 2, 4, 5, 7, 8]);
+    /// ```
+    pub fn step_by<'s>(&'s mut self, step: usize) -> VecMutScanStepBy<'s, 'a, T> {
+        assert!(step != 0, "step must be non-zero");
+        VecMutScanStepBy {
+            scan: self,
+            step,
+            first: true,
+        }
+    }
+
+    /// Returns a view of the scan that visits at most `n` further items.
+    ///
+    /// Once the view is dropped, the scan continues after the last visited item.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers = vec![1, 2, 3, 4, 5];
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    ///
+    /// let mut first_two = scan.take(2);
+    /// while let Some(item) = first_two.next() {
+    ///     item.remove();
+    /// }
+    /// assert_eq!(*scan.next().unwrap(), 3);
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [3, 4, 5]);
+    /// ```
+    pub fn take<'s>(&'s mut self, n: usize) -> VecMutScanTake<'s, 'a, T> {
+        VecMutScanTake {
+            scan: self,
+            left: n,
+        }
+    }
+}
+
+/// View of a [`VecMutScan`] visiting only every `step`-th item, see
+/// [`step_by`][VecMutScan::step_by].
+pub struct VecMutScanStepBy<'s, 'a, T: 'a> {
+    scan: &'s mut VecMutScan<'a, T>,
+    step: usize,
+    first: bool,
+}
+
+impl<'s, 'a, T: 'a> VecMutScanStepBy<'s, 'a, T> {
+    /// Keep the items up to the next item to visit and advance to it.
+    ///
+    /// This returns a reference wrapper that enables item removal (see [`VecMutScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'t>(&'t mut self) -> Option<VecMutScanItem<'t, 'a, T>> {
+        if !self.first {
+            let remaining = self.scan.end - self.scan.read;
+            self.scan.keep_many(remaining.min(self.step - 1));
+        }
+        self.first = false;
+        self.scan.next()
+    }
+}
+
+/// View of a [`VecMutScan`] visiting a limited number of items, see [`take`][VecMutScan::take].
+pub struct VecMutScanTake<'s, 'a, T: 'a> {
+    scan: &'s mut VecMutScan<'a, T>,
+    left: usize,
+}
+
+impl<'s, 'a, T: 'a> VecMutScanTake<'s, 'a, T> {
+    /// Advance to the next item, unless the limit was reached.
+    ///
+    /// This returns a reference wrapper that enables item removal (see [`VecMutScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'t>(&'t mut self) -> Option<VecMutScanItem<'t, 'a, T>> {
+        if self.left == 0 {
+            return None;
+        }
+        let item = self.scan.next()?;
+        self.left -= 1;
+        Some(item)
+    }
+
+    /// The number of items this view may still visit.
+    pub fn remaining_limit(&self) -> usize {
+        self.left
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec, vec::Vec};
+
+    #[test]
+    fn limited_traversals() {
+        let mut input: Vec<_> = (0..12).map(Rc::new).collect();
+        let copies = input.clone();
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next().unwrap().remove();
+        assert_eq!(scan.skip_while(|x| **x < 3), 2);
+        let mut seen = vec![];
+        let mut step_by = scan.step_by(4);
+        while let Some(item) = step_by.next() {
+            seen.push(**item);
+            if **item != 3 {
+                item.remove();
+            }
+        }
+        drop(scan);
+
+        assert_eq!(seen, [3, 7, 11]);
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [1, 2, 3, 4, 5, 6, 8, 9, 10]);
+
+        let mut scan = VecMutScan::new(&mut input);
+        let mut take = scan.take(3);
+        take.next().unwrap().remove();
+        take.next();
+        assert_eq!(take.remaining_limit(), 1);
+        take.next().unwrap().remove();
+        assert!(take.next().is_none());
+        assert_eq!(scan.skip_while(|_| true), 6);
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [2, 4, 5, 6, 8, 9, 10]);
+        drop(copies);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+}
@@ -0,0 +1,148 @@
+use alloc::vec::Vec;
+
+use crate::{VecGrowScan, VecGrowScanItem, VecMutScan, VecMutScanItem};
+
+/// Compares the concatenation of `parts` with `other`.
+fn parts_eq<T: PartialEq>(parts: &[&[T]], mut other: &[T]) -> bool {
+    if parts.iter().map(|part| part.len()).sum::<usize>() != other.len() {
+        return false;
+    }
+    for part in parts {
+        let (head, rest) = other.split_at(part.len());
+        if *part != head {
+            return false;
+        }
+        other = rest;
+    }
+    true
+}
+
+impl<'a, T: 'a + PartialEq> VecMutScan<'a, T> {
+    /// Compare the vector's current contents with a slice.
+    ///
+    /// The contents are the visited items followed by the remaining items and the items visited
+    /// from the back, i.e. the vector the scan would leave behind when dropped now. This compares
+    /// them without moving any items.
+    ///
+    /// The same comparison is available using `==` with a slice or vector on the right hand side.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers = vec![1, 2, 3, 4];
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    /// scan.next().unwrap().remove();
+    ///
+    /// assert!(scan.logical_eq(&[2, 3, 4]));
+    /// assert!(scan == vec![2, 3, 4]);
+    /// ```
+    pub fn logical_eq(&self, other: &[T]) -> bool {
+        let (visited, remaining) = self.slices();
+        parts_eq(&[visited, remaining], other)
+    }
+}
+
+impl<'s, 'a, T: 'a + PartialEq> VecMutScanItem<'s, 'a, T> {
+    /// Compare the vector's current contents with a slice.
+    ///
+    /// See [`VecMutScan::logical_eq`].
+    pub fn logical_eq(&self, other: &[T]) -> bool {
+        self.scan.logical_eq(other)
+    }
+}
+
+impl<'a, T: 'a + PartialEq> VecGrowScan<'a, T> {
+    /// Compare the vector's current contents with a slice.
+    ///
+    /// The contents are the visited and inserted items followed by the remaining items, the
+    /// appended items and the parked items, i.e. the vector the scan would leave behind when
+    /// dropped now. This compares them without moving any items.
+    ///
+    /// The same comparison is available using `==` with a slice or vector on the right hand side.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecGrowScan;
+    /// let mut numbers = vec![1, 2, 3, 4];
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    /// scan.next().unwrap().insert_many_after(vec![5, 6]);
+    /// scan.next().unwrap().park();
+    /// scan.append(7);
+    ///
+    /// assert!(scan.logical_eq(&[1, 5, 6, 3, 4, 7, 2]));
+    /// ```
+    pub fn logical_eq(&self, other: &[T]) -> bool {
+        let (visited, queued_front, queued_back, remaining) = self.slices();
+        parts_eq(
+            &[
+                visited,
+                queued_front,
+                queued_back,
+                remaining,
+                self.appended(),
+                self.parked(),
+            ],
+            other,
+        )
+    }
+}
+
+impl<'s, 'a, T: 'a + PartialEq> VecGrowScanItem<'s, 'a, T> {
+    /// Compare the vector's current contents with a slice.
+    ///
+    /// See [`VecGrowScan::logical_eq`].
+    pub fn logical_eq(&self, other: &[T]) -> bool {
+        self.scan.logical_eq(other)
+    }
+}
+
+impl<'a, T: 'a + PartialEq> PartialEq<[T]> for VecMutScan<'a, T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.logical_eq(other)
+    }
+}
+
+impl<'a, T: 'a + PartialEq> PartialEq<Vec<T>> for VecMutScan<'a, T> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        self.logical_eq(other)
+    }
+}
+
+impl<'a, T: 'a + PartialEq> PartialEq<[T]> for VecGrowScan<'a, T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.logical_eq(other)
+    }
+}
+
+impl<'a, T: 'a + PartialEq> PartialEq<Vec<T>> for VecGrowScan<'a, T> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        self.logical_eq(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn grow_contents() {
+        let mut input = vec![1, 2, 3, 4];
+        let mut scan = VecGrowScan::new(&mut input);
+
+        let mut item = scan.next().unwrap();
+        item.insert_before(0);
+        assert!(item.logical_eq(&[0, 1, 2, 3, 4]));
+        item.remove();
+        scan.insert(10);
+        scan.insert(11);
+        scan.append(20);
+
+        assert!(scan == vec![0, 10, 11, 2, 3, 4, 20]);
+        assert!(scan != vec![0, 10, 11, 2, 3, 4]);
+        assert!(scan != vec![0, 10, 11, 2, 3, 5, 20]);
+        assert!(scan != vec![0, 10, 2, 11, 3, 4, 20]);
+        drop(scan);
+
+        assert_eq!(input, [0, 10, 11, 2, 3, 4, 20]);
+    }
+}
@@ -0,0 +1,78 @@
+use alloc::vec::Vec;
+
+use crate::VecMutScan;
+
+/// Stably rearranges the items into `n` contiguous regions, as assigned by `class`.
+///
+/// The closure returns the class of each item, which must be less than `n`. Afterwards, the
+/// vector contains all items of class 0, followed by all items of class 1 and so on, each in their
+/// original order. Returns the end index of each class's region.
+///
+/// Items of class 0 are compacted in place, while items of the other classes are moved into a
+/// separate buffer per class and appended at the end. Thus, the buffering needed is bounded by the
+/// number of items not in class 0, which should be the most common class.
+///
+/// Panics if `class` returns a value not less than `n`. In that case, the items of the other
+/// classes that were already buffered are dropped.
+///
+/// ```
+/// # use vec_mut_scan::partition_n;
+/// let mut numbers: Vec<i32> = vec![5, -3, 0, 8, -1, 0, 2];
+/// let ends = partition_n(&mut numbers, 3, |x| match x.signum() {
+///     1 => 0,
+///     0 => 1,
+///     _ => 2,
+/// });
+///
+/// assert_eq!(numbers, [5, 8, 2, 0, 0, -3, -1]);
+/// assert_eq!(ends, [3, 5, 7]);
+/// ```
+pub fn partition_n<T>(
+    vec: &mut Vec<T>,
+    n: usize,
+    mut class: impl FnMut(&T) -> usize,
+) -> Vec<usize> {
+    let mut buffers: Vec<Vec<T>> = (1..n).map(|_| Vec::new()).collect();
+
+    let mut scan = VecMutScan::new(vec);
+    while let Some(item) = scan.next() {
+        match class(&item) {
+            0 => (),
+            index => {
+                assert!(index < n, "class out of range");
+                buffers[index - 1].push(item.remove());
+            }
+        }
+    }
+    drop(scan);
+
+    let mut ends = Vec::with_capacity(n);
+    if n > 0 {
+        ends.push(vec.len());
+    }
+    for mut buffer in buffers {
+        vec.append(&mut buffer);
+        ends.push(vec.len());
+    }
+    ends
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classes() {
+        let mut input: Vec<_> = (0..20).collect();
+        let ends = partition_n(&mut input, 4, |x| (x * 7 % 5).min(3));
+
+        assert_eq!(
+            input,
+            [0, 5, 10, 15, 3, 8, 13, 18, 1, 6, 11, 16, 2, 4, 7, 9, 12, 14, 17, 19]
+        );
+        assert_eq!(ends, [4, 8, 12, 20]);
+
+        let ends = partition_n(&mut input, 1, |_| 0);
+        assert_eq!(ends, [20]);
+    }
+}
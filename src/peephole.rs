@@ -0,0 +1,142 @@
+use alloc::vec::Vec;
+#[cfg(feature = "const-generics")]
+use core::convert::TryInto;
+
+use crate::VecGrowScan;
+
+/// Replace matching windows of consecutive items, as done by a peephole optimizer.
+///
+/// Every window of `window` consecutive items is passed to `rewrite`, starting with the first
+/// window. When `rewrite` returns `None`, the window advances by one item. When it returns a
+/// sequence of replacement items, which may be shorter or longer than the window, the window's
+/// items are replaced and the next window starts right after the window's items. The replacement
+/// items are not passed to `rewrite` again.
+///
+/// The window length is passed at runtime. Windows are only formed as long as there are enough
+/// remaining items. The items of the window are passed mutably, so that they can be moved into the
+/// replacement, e.g. using [`mem::take`](core::mem::take).
+///
+/// Panics if `window` is zero.
+///
+/// ```
+/// # use vec_mut_scan::peephole;
+/// #[derive(Debug, PartialEq)]
+/// enum Op {
+///     Push(i32),
+///     Add,
+///     Pop,
+/// }
+///
+/// let mut code = vec![Op::Push(1), Op::Push(2), Op::Add, Op::Push(3), Op::Pop, Op::Push(4)];
+///
+/// peephole(&mut code, 2, |ops| match ops {
+///     // Remove pushes that are immediately popped.
+///     [Op::Push(_), Op::Pop] => Some(vec![]),
+///     _ => None,
+/// });
+/// peephole(&mut code, 3, |ops| match ops {
+///     // Fold constant additions.
+///     [Op::Push(a), Op::Push(b), Op::Add] => Some(vec![Op::Push(*a + *b)]),
+///     _ => None,
+/// });
+///
+/// assert_eq!(code, [Op::Push(3), Op::Push(4)]);
+/// ```
+pub fn peephole<T, I: IntoIterator<Item = T>>(
+    vec: &mut Vec<T>,
+    window: usize,
+    mut rewrite: impl FnMut(&mut [T]) -> Option<I>,
+) {
+    assert!(window > 0, "window length must be positive");
+
+    let mut scan = VecGrowScan::new(vec);
+    loop {
+        let replacement = {
+            let remaining = scan.slices_mut().3;
+            if remaining.len() < window {
+                break;
+            }
+            rewrite(&mut remaining[..window])
+        };
+
+        match replacement {
+            Some(items) => {
+                for _ in 0..window {
+                    scan.next().unwrap().remove();
+                }
+                scan.insert_many(items);
+            }
+            None => {
+                scan.next();
+            }
+        }
+    }
+}
+
+/// Like [`peephole`], but with the window length `N` as a const generic parameter, so that each
+/// window is passed as an array.
+///
+/// Panics if `N` is zero.
+///
+/// Requires the `const-generics` feature, which needs Rust 1.51 or later.
+///
+/// ```
+/// # use vec_mut_scan::peephole_array;
+/// let mut numbers = vec![1, 2, 3, 3, 4, 5, 5];
+///
+/// // Merge pairs of equal numbers.
+/// peephole_array(&mut numbers, |[a, b]| if a == b { Some(vec![*a * 10]) } else { None });
+///
+/// assert_eq!(numbers, [1, 2, 30, 4, 50]);
+/// ```
+#[cfg(feature = "const-generics")]
+pub fn peephole_array<T, I: IntoIterator<Item = T>, const N: usize>(
+    vec: &mut Vec<T>,
+    mut rewrite: impl FnMut(&mut [T; N]) -> Option<I>,
+) {
+    peephole(vec, N, |window| rewrite(window.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn windows() {
+        let mut input: Vec<_> = (0..10).collect();
+        let mut windows = vec![];
+
+        peephole(&mut input, 3, |window| {
+            windows.push(window[0]);
+            if window[0] % 3 == 1 {
+                Some(vec![100 + window[0]; window[0] / 3])
+            } else {
+                None
+            }
+        });
+
+        assert_eq!(windows, [0, 1, 4, 7]);
+        assert_eq!(input, [0, 104, 107, 107]);
+    }
+
+    #[test]
+    #[cfg(feature = "const-generics")]
+    fn array_windows() {
+        let mut input: Vec<_> = (0..10).collect();
+        let mut windows = vec![];
+
+        peephole_array(&mut input, |&mut [a, b, c]| {
+            windows.push(a);
+            if a % 3 == 1 {
+                Some(vec![a + b + c])
+            } else {
+                None
+            }
+        });
+
+        assert_eq!(windows, [0, 1, 4, 7]);
+        assert_eq!(input, [0, 6, 15, 24]);
+    }
+}
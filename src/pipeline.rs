@@ -0,0 +1,158 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::VecGrowScan;
+
+/// A sequence of per-item transformations that are applied to a vector in a single scan.
+///
+/// Stages are registered using the builder methods and are run in the order of registration for
+/// every item of the vector. This allows independent transformations, e.g. defined in different
+/// modules, to be fused into a single traversal of the vector.
+///
+/// When a [`remove_if`](Pipeline::remove_if) stage removes an item, the following stages are not
+/// run for that item. Items produced by earlier insertion stages for that item are still inserted.
+/// Inserted items are not processed by the pipeline.
+///
+/// ```
+/// # use vec_mut_scan::Pipeline;
+/// let mut numbers = vec![1, 2, 3, 4, 5];
+///
+/// Pipeline::new()
+///     .mutate(|x| *x *= 10)
+///     .remove_if(|x| *x == 30)
+///     .insert_after(|x| if *x % 20 == 0 { Some(*x + 1) } else { None })
+///     .run(&mut numbers);
+///
+/// assert_eq!(numbers, [10, 20, 21, 40, 41, 50]);
+/// ```
+pub struct Pipeline<'f, T> {
+    stages: Vec<Stage<'f, T>>,
+}
+
+type InsertFn<'f, T> = Box<dyn FnMut(&T) -> Option<T> + 'f>;
+
+enum Stage<'f, T> {
+    Mutate(Box<dyn FnMut(&mut T) + 'f>),
+    RemoveIf(Box<dyn FnMut(&mut T) -> bool + 'f>),
+    InsertBefore(InsertFn<'f, T>),
+    InsertAfter(InsertFn<'f, T>),
+}
+
+impl<'f, T> Default for Pipeline<'f, T> {
+    fn default() -> Self {
+        Pipeline { stages: Vec::new() }
+    }
+}
+
+impl<'f, T> Pipeline<'f, T> {
+    /// Create an empty pipeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a stage that mutates each item.
+    pub fn mutate(mut self, f: impl FnMut(&mut T) + 'f) -> Self {
+        self.stages.push(Stage::Mutate(Box::new(f)));
+        self
+    }
+
+    /// Add a stage that removes each item for which `f` returns `true`.
+    ///
+    /// Following stages are not run for removed items.
+    pub fn remove_if(mut self, f: impl FnMut(&mut T) -> bool + 'f) -> Self {
+        self.stages.push(Stage::RemoveIf(Box::new(f)));
+        self
+    }
+
+    /// Add a stage that can insert a new item before each item.
+    pub fn insert_before(mut self, f: impl FnMut(&T) -> Option<T> + 'f) -> Self {
+        self.stages.push(Stage::InsertBefore(Box::new(f)));
+        self
+    }
+
+    /// Add a stage that can insert a new item after each item.
+    ///
+    /// When multiple stages insert items after the same item, they are inserted in the order of
+    /// the stages.
+    pub fn insert_after(mut self, f: impl FnMut(&T) -> Option<T> + 'f) -> Self {
+        self.stages.push(Stage::InsertAfter(Box::new(f)));
+        self
+    }
+
+    /// Apply all stages to every item of a vector using a single scan.
+    ///
+    /// The pipeline is not consumed and can be run on multiple vectors.
+    pub fn run(&mut self, vec: &mut Vec<T>) {
+        let mut scan = VecGrowScan::new(vec);
+        let mut after = Vec::new();
+
+        while let Some(mut item) = scan.next() {
+            let mut removed = false;
+            for stage in self.stages.iter_mut() {
+                match stage {
+                    Stage::Mutate(f) => f(&mut item),
+                    Stage::RemoveIf(f) => {
+                        if f(&mut item) {
+                            removed = true;
+                            break;
+                        }
+                    }
+                    Stage::InsertBefore(f) => {
+                        if let Some(value) = f(&item) {
+                            item.insert_before(value);
+                        }
+                    }
+                    Stage::InsertAfter(f) => {
+                        if let Some(value) = f(&item) {
+                            after.push(value);
+                        }
+                    }
+                }
+            }
+            if removed {
+                // This keeps the items inserted by earlier stages.
+                item.replace_with_many(after.drain(..));
+            } else {
+                item.insert_many_after(after.drain(..));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn removal_short_circuits() {
+        let mut input = vec![1, 2, 3, 4, 5, 6];
+        let mut mutated = 0;
+
+        Pipeline::new()
+            .insert_after(|x| if *x == 3 { Some(30) } else { None })
+            .remove_if(|x| *x % 3 == 0)
+            .mutate(|x| {
+                mutated += 1;
+                *x += 100;
+            })
+            .insert_before(|x| if *x == 102 { Some(0) } else { None })
+            .run(&mut input);
+
+        assert_eq!(input, [101, 0, 102, 30, 104, 105]);
+        assert_eq!(mutated, 4);
+    }
+
+    #[test]
+    fn reusable() {
+        let mut pipeline = Pipeline::new().remove_if(|x: &mut i32| *x < 0);
+
+        let mut a = vec![1, -2, 3];
+        let mut b = vec![-4, 5];
+        pipeline.run(&mut a);
+        pipeline.run(&mut b);
+
+        assert_eq!(a, [1, 3]);
+        assert_eq!(b, [5]);
+    }
+}
@@ -0,0 +1,177 @@
+use alloc::vec::Vec;
+use bytemuck::Pod;
+use core::{marker::PhantomData, mem, ptr};
+
+use crate::VecMutScan;
+
+/// Forward scan over a byte vector interpreted as a sequence of records with mutation and record
+/// removal.
+///
+/// Each record consists of `size_of::<T>()` bytes, which are reinterpreted as a value of the
+/// [`Pod`] type `T`. Removing a record removes all of its bytes, so the vector always contains
+/// whole records. As the bytes of a record need not be aligned for `T`, records are read and
+/// written by value. Trailing bytes that do not form a complete record are kept and not visited.
+///
+/// Requires the `bytemuck` feature.
+///
+/// ```
+/// # use vec_mut_scan::RecordScan;
+/// let mut log: Vec<u8> = vec![];
+/// for entry in [3u32, 10, 4, 12].iter() {
+///     log.extend_from_slice(&entry.to_le_bytes());
+/// }
+///
+/// let mut scan = RecordScan::<[u8; 4]>::new(&mut log);
+/// while let Some(mut record) = scan.next() {
+///     let value = u32::from_le_bytes(record.get());
+///     if value < 5 {
+///         record.remove();
+///     } else {
+///         record.set((value * 2).to_le_bytes());
+///     }
+/// }
+/// drop(scan);
+///
+/// assert_eq!(log, [20, 0, 0, 0, 24, 0, 0, 0]);
+/// ```
+pub struct RecordScan<'a, T> {
+    scan: VecMutScan<'a, u8>,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T: Pod> RecordScan<'a, T> {
+    /// Begin a scan over the records of a byte vector with mutation and record removal.
+    ///
+    /// Panics if `T` is zero-sized.
+    pub fn new(vec: &'a mut Vec<u8>) -> Self {
+        assert!(mem::size_of::<T>() > 0, "records must not be zero-sized");
+        RecordScan {
+            scan: VecMutScan::new(vec),
+            marker: PhantomData,
+        }
+    }
+
+    /// Advance to the next record of the vector.
+    ///
+    /// This returns a reference wrapper that enables record removal (see [`RecordScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<RecordScanItem<'s, 'a, T>> {
+        if self.scan.slices().1.len() >= mem::size_of::<T>() {
+            Some(RecordScanItem { scan: self })
+        } else {
+            None
+        }
+    }
+
+    /// Access the bytes of the vector.
+    ///
+    /// Returns the bytes of the visited records and the bytes of the remaining records, including
+    /// any trailing bytes.
+    pub fn byte_slices(&self) -> (&[u8], &[u8]) {
+        self.scan.slices()
+    }
+
+    /// Moves past the next record, keeping it or removing it.
+    fn advance(&mut self, remove: bool) {
+        for _ in 0..mem::size_of::<T>() {
+            let byte = self.scan.next().unwrap();
+            if remove {
+                byte.remove();
+            }
+        }
+    }
+}
+
+/// Reference wrapper that enables record removal for [`RecordScan`].
+///
+/// Dropping the wrapper keeps the record.
+pub struct RecordScanItem<'s, 'a, T: Pod> {
+    scan: &'s mut RecordScan<'a, T>,
+}
+
+impl<'s, 'a, T: Pod> RecordScanItem<'s, 'a, T> {
+    fn into_inner_forget(self) -> &'s mut RecordScan<'a, T> {
+        let item = mem::ManuallyDrop::new(self);
+        // The reference is moved out of `item`, which is never used or dropped afterwards.
+        unsafe { ptr::read(&item.scan) }
+    }
+
+    /// The bytes of this record.
+    pub fn bytes(&self) -> &[u8] {
+        &self.scan.scan.slices().1[..mem::size_of::<T>()]
+    }
+
+    /// The bytes of this record, for mutation.
+    pub fn bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.scan.scan.slices_mut().1[..mem::size_of::<T>()]
+    }
+
+    /// Read this record.
+    pub fn get(&self) -> T {
+        bytemuck::pod_read_unaligned(self.bytes())
+    }
+
+    /// Overwrite this record.
+    pub fn set(&mut self, value: T) {
+        self.bytes_mut().copy_from_slice(bytemuck::bytes_of(&value));
+    }
+
+    /// Removes and returns this record from the vector.
+    pub fn remove(self) -> T {
+        let value = self.get();
+        self.into_inner_forget().advance(true);
+        value
+    }
+
+    /// Replaces this record with a new value, returns the old value.
+    pub fn replace(mut self, value: T) -> T {
+        let old = self.get();
+        self.set(value);
+        old
+    }
+}
+
+impl<'s, 'a, T: Pod> Drop for RecordScanItem<'s, 'a, T> {
+    fn drop(&mut self) {
+        self.scan.advance(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn records() {
+        let mut input: Vec<u8> = (0..15).collect();
+
+        let mut scan = RecordScan::<[u8; 3]>::new(&mut input);
+        let mut seen = vec![];
+        while let Some(record) = scan.next() {
+            let value = record.get();
+            seen.push(value[0]);
+            match value[0] {
+                3 => assert_eq!(record.remove(), [3, 4, 5]),
+                6 => assert_eq!(record.replace([7, 7, 7]), [6, 7, 8]),
+                9 => drop(record.remove()),
+                _ => (),
+            }
+        }
+        assert_eq!(scan.byte_slices().0, [0, 1, 2, 7, 7, 7, 12, 13, 14]);
+        drop(scan);
+
+        assert_eq!(seen, [0, 3, 6, 9, 12]);
+        assert_eq!(input, [0, 1, 2, 7, 7, 7, 12, 13, 14]);
+
+        input.push(15);
+        let mut scan = RecordScan::<u32>::new(&mut input);
+        while let Some(record) = scan.next() {
+            record.remove();
+        }
+        drop(scan);
+
+        assert_eq!(input, [14, 15]);
+    }
+}
@@ -0,0 +1,531 @@
+use alloc::vec::Vec;
+use core::{cmp::Ordering, mem, ops::Sub};
+
+use crate::{coalesce, DoubleBufferScan, Sink, VecMutScan};
+
+/// Retain only the `k` greatest items according to `cmp`, keeping their relative order.
+///
+/// When several items compare equal, earlier items are preferred. Finding the items to keep uses a
+/// bounded heap of `k` candidate indices, taking `O(n log k)` comparisons, after which the other
+/// items are removed in a single scan.
+///
+/// ```
+/// # use vec_mut_scan::retain_top_k;
+/// let mut scores = vec![("a", 3), ("b", 9), ("c", 1), ("d", 7), ("e", 9)];
+/// retain_top_k(&mut scores, 3, |x, y| x.1.cmp(&y.1));
+///
+/// assert_eq!(scores, [("b", 9), ("d", 7), ("e", 9)]);
+/// ```
+pub fn retain_top_k<T>(vec: &mut Vec<T>, k: usize, mut cmp: impl FnMut(&T, &T) -> Ordering) {
+    if k >= vec.len() {
+        return;
+    }
+
+    let mut keep = {
+        // `a` is a worse candidate than `b` if it compares less or compares equal and comes later.
+        let items = &vec[..];
+        let mut worse = |a: usize, b: usize| match cmp(&items[a], &items[b]) {
+            Ordering::Less => true,
+            Ordering::Equal => a > b,
+            Ordering::Greater => false,
+        };
+
+        // Binary heap of candidate indices with the worst candidate at the root.
+        let mut heap: Vec<usize> = Vec::with_capacity(k);
+        for index in 0..items.len() {
+            if heap.len() < k {
+                heap.push(index);
+                sift_up(&mut heap, &mut worse);
+            } else if k > 0 && worse(heap[0], index) {
+                heap[0] = index;
+                sift_down(&mut heap, &mut worse);
+            }
+        }
+        heap
+    };
+    keep.sort_unstable();
+
+    let mut keep = keep.into_iter().peekable();
+    let mut index = 0;
+    let mut scan = VecMutScan::new(vec);
+    while let Some(item) = scan.next() {
+        if keep.peek() == Some(&index) {
+            keep.next();
+        } else {
+            item.remove();
+        }
+        index += 1;
+    }
+}
+
+/// Among consecutive items with equal keys, retain only the greatest item according to `cmp`.
+///
+/// Each run of items with equal keys is reduced to a single item, kept at the position of the
+/// run's first item. When several items of a run compare equal, the earliest one is kept. Sort or
+/// group the vector by key first to reduce all items sharing a key, e.g. to compact superseded
+/// records.
+///
+/// ```
+/// # use vec_mut_scan::retain_max_by_key;
+/// let mut records = vec![("a", 1, 'x'), ("a", 3, 'y'), ("b", 2, 'z'), ("a", 2, 'w')];
+/// retain_max_by_key(&mut records, |record| record.0, |x, y| x.1.cmp(&y.1));
+///
+/// assert_eq!(records, [("a", 3, 'y'), ("b", 2, 'z'), ("a", 2, 'w')]);
+/// ```
+pub fn retain_max_by_key<T, K: PartialEq>(
+    vec: &mut Vec<T>,
+    mut key: impl FnMut(&T) -> K,
+    mut cmp: impl FnMut(&T, &T) -> Ordering,
+) {
+    coalesce(vec, |current, last| {
+        if key(current) != key(last) {
+            return false;
+        }
+        if cmp(current, last) == Ordering::Greater {
+            mem::swap(current, last);
+        }
+        true
+    })
+}
+
+/// How [`retain_adaptive`] removes items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RetainStrategy {
+    /// Choose a strategy based on the removal rate among the first items.
+    Auto,
+    /// Compact the kept items within the vector's buffer, keeping its capacity.
+    InPlace,
+    /// Move the kept items into a new buffer sized for them, releasing the old one.
+    OutOfPlace,
+}
+
+/// Retain only the items for which `keep` returns `true`, choosing between in-place compaction and
+/// copying the kept items out.
+///
+/// Both strategies move every kept item at most once. Compacting in place keeps the vector's
+/// capacity, which is wasteful when most items are removed from a large vector. Copying the kept
+/// items into a new buffer then allows releasing the old one. With [`RetainStrategy::Auto`], the
+/// first items are scanned in place and when at least three quarters of them are removed, the
+/// remaining items are processed out of place, sizing the new buffer by the observed rate.
+///
+/// Returns the strategy used for the remaining items.
+///
+/// ```
+/// # use vec_mut_scan::{retain_adaptive, RetainStrategy};
+/// let mut ids: Vec<u32> = (0..10_000).collect();
+/// let used = retain_adaptive(&mut ids, RetainStrategy::Auto, |id| *id % 100 == 0);
+///
+/// assert_eq!(used, RetainStrategy::OutOfPlace);
+/// assert_eq!(ids.len(), 100);
+/// assert!(ids.capacity() < 1000);
+/// ```
+pub fn retain_adaptive<T>(
+    vec: &mut Vec<T>,
+    strategy: RetainStrategy,
+    mut keep: impl FnMut(&mut T) -> bool,
+) -> RetainStrategy {
+    const SAMPLE: usize = 64;
+
+    let mut sampled = 0;
+    let mut removed = 0;
+    if strategy == RetainStrategy::Auto {
+        let mut scan = VecMutScan::new(vec);
+        while sampled < SAMPLE {
+            let mut item = match scan.next() {
+                Some(item) => item,
+                None => break,
+            };
+            sampled += 1;
+            if !keep(&mut item) {
+                item.remove();
+                removed += 1;
+            }
+        }
+    }
+
+    let kept = sampled - removed;
+    let remaining = vec.len() - kept;
+    let strategy = match strategy {
+        RetainStrategy::Auto if remaining > 0 && removed * 4 >= sampled * 3 => {
+            RetainStrategy::OutOfPlace
+        }
+        RetainStrategy::Auto => RetainStrategy::InPlace,
+        strategy => strategy,
+    };
+
+    if strategy == RetainStrategy::OutOfPlace {
+        // Estimate the number of kept items, rounding up. Without a sample, the new buffer grows as
+        // needed.
+        let estimate = match sampled {
+            0 => 0,
+            _ => remaining / sampled * kept + (remaining % sampled * kept + sampled - 1) / sampled,
+        };
+        let mut dest = Vec::with_capacity(kept + estimate);
+        dest.extend(vec.drain(..kept));
+        let mut scan = DoubleBufferScan::new(vec, &mut dest);
+        while let Some(mut item) = scan.next() {
+            if !keep(&mut item) {
+                item.remove();
+            }
+        }
+        drop(scan);
+        *vec = dest;
+    } else {
+        let mut scan = VecMutScan::start_at(vec, kept);
+        while let Some(mut item) = scan.next() {
+            if !keep(&mut item) {
+                item.remove();
+            }
+        }
+    }
+    strategy
+}
+
+/// Retain only the items for which `keep` returns `true`, passing each item's original index.
+///
+/// The index is the item's position before the scan started, so positional rules such as removing
+/// all items of a given index set don't need a separately maintained counter.
+///
+/// ```
+/// # use vec_mut_scan::retain_enumerated;
+/// let mut items = vec!['a', 'b', 'c', 'd', 'e'];
+/// let drop = [1, 3];
+/// retain_enumerated(&mut items, |index, _| !drop.contains(&index));
+///
+/// assert_eq!(items, ['a', 'c', 'e']);
+/// ```
+pub fn retain_enumerated<T>(vec: &mut Vec<T>, mut keep: impl FnMut(usize, &mut T) -> bool) {
+    retain_enumerated_with_output(vec, |index, _, item| keep(index, item))
+}
+
+/// Retain only the items for which `keep` returns `true`, passing each item's original index and
+/// the index it has when kept.
+///
+/// The output index equals the number of items kept so far.
+///
+/// ```
+/// # use vec_mut_scan::retain_enumerated_with_output;
+/// // Keep at most three items, removing every item at an odd original index.
+/// let mut items: Vec<_> = (10..20).collect();
+/// retain_enumerated_with_output(&mut items, |index, output, _| index % 2 == 0 && output < 3);
+///
+/// assert_eq!(items, [10, 12, 14]);
+/// ```
+pub fn retain_enumerated_with_output<T>(
+    vec: &mut Vec<T>,
+    mut keep: impl FnMut(usize, usize, &mut T) -> bool,
+) {
+    let mut scan = VecMutScan::new(vec);
+    loop {
+        // The scan started at index zero, so its read offset is the item's original index and its
+        // write offset the number of kept items.
+        let (index, output) = (scan.read, scan.write);
+        let mut item = match scan.next() {
+            Some(item) => item,
+            None => break,
+        };
+        if !keep(index, output, &mut item) {
+            item.remove();
+        }
+    }
+}
+
+/// Retain only every `n`-th item, starting with the first one.
+///
+/// This keeps the items at the indices `0`, `n`, `2 * n`, ... and removes all others in a single
+/// scan, e.g. to downsample a buffer of samples.
+///
+/// Panics if `n` is zero.
+///
+/// ```
+/// # use vec_mut_scan::thin;
+/// let mut samples: Vec<_> = (0..10).collect();
+/// thin(&mut samples, 3);
+///
+/// assert_eq!(samples, [0, 3, 6, 9]);
+/// ```
+pub fn thin<T>(vec: &mut Vec<T>, n: usize) {
+    assert!(n > 0, "cannot keep every 0th item");
+
+    let mut index = 0;
+    let mut scan = VecMutScan::new(vec);
+    while let Some(item) = scan.next() {
+        if index % n != 0 {
+            item.remove();
+        }
+        index += 1;
+    }
+}
+
+/// Retain an evenly spaced selection of the given fraction of all items, starting with the first
+/// one.
+///
+/// Of `len` items, `(len as f64 * fraction) as usize` items are kept. The remaining items are
+/// removed in a single scan.
+///
+/// Panics if `fraction` is not within `0.0..=1.0`.
+///
+/// ```
+/// # use vec_mut_scan::thin_to_fraction;
+/// let mut samples: Vec<_> = (0..10).collect();
+/// thin_to_fraction(&mut samples, 0.4);
+///
+/// assert_eq!(samples, [0, 3, 5, 8]);
+/// ```
+pub fn thin_to_fraction<T>(vec: &mut Vec<T>, fraction: f64) {
+    assert!(
+        (0.0..=1.0).contains(&fraction),
+        "fraction must be within 0.0..=1.0"
+    );
+
+    let len = vec.len();
+    let target = ((len as f64 * fraction) as usize).min(len);
+    if target == 0 {
+        vec.clear();
+        return;
+    }
+
+    // Like Bresenham's line algorithm, this adds `target` for every item and keeps an item whenever
+    // the accumulator reaches `len`. Starting at `len - target` keeps the first item and exactly
+    // `target` items overall.
+    let mut acc = len - target;
+    let mut scan = VecMutScan::new(vec);
+    while let Some(item) = scan.next() {
+        acc += target;
+        if acc >= len {
+            acc -= len;
+        } else {
+            item.remove();
+        }
+    }
+}
+
+/// Restore the heap property after pushing an index.
+fn sift_up(heap: &mut [usize], worse: &mut impl FnMut(usize, usize) -> bool) {
+    let mut pos = heap.len() - 1;
+    while pos > 0 {
+        let parent = (pos - 1) / 2;
+        if !worse(heap[pos], heap[parent]) {
+            break;
+        }
+        heap.swap(pos, parent);
+        pos = parent;
+    }
+}
+
+/// Restore the heap property after replacing the root.
+fn sift_down(heap: &mut [usize], worse: &mut impl FnMut(usize, usize) -> bool) {
+    let mut pos = 0;
+    loop {
+        let mut child = 2 * pos + 1;
+        if child >= heap.len() {
+            break;
+        }
+        if child + 1 < heap.len() && worse(heap[child + 1], heap[child]) {
+            child += 1;
+        }
+        if !worse(heap[child], heap[pos]) {
+            break;
+        }
+        heap.swap(pos, child);
+        pos = child;
+    }
+}
+
+/// Retain items in order as long as their costs fit into a budget, removing all items that don't
+/// fit. Returns the unused budget.
+///
+/// Each item is kept if its cost, as returned by `cost`, does not exceed what is left of the
+/// budget, in which case its cost is subtracted. Otherwise it is removed, but later items with a
+/// lower cost may still be kept. Costs must not be negative.
+///
+/// ```
+/// # use vec_mut_scan::retain_within_budget;
+/// // Keep cache entries, most recently used first, within a size limit.
+/// let mut entries = vec![("a", 40), ("b", 70), ("c", 20), ("d", 10)];
+/// let unused = retain_within_budget(&mut entries, 100, |entry| entry.1);
+///
+/// assert_eq!(entries, [("a", 40), ("c", 20), ("d", 10)]);
+/// assert_eq!(unused, 30);
+/// ```
+pub fn retain_within_budget<T, C>(vec: &mut Vec<T>, budget: C, mut cost: impl FnMut(&T) -> C) -> C
+where
+    C: Copy + PartialOrd + Sub<Output = C>,
+{
+    let mut scan = VecMutScan::new(vec);
+    scan.fold_retain(budget, |left, item| charge(left, cost(item)))
+}
+
+/// Retain items in order as long as their costs fit into a budget, passing all items that don't
+/// fit to `sink`. Returns the unused budget.
+///
+/// This is [`retain_within_budget`], except that the removed items are passed to the sink in order.
+///
+/// ```
+/// # use vec_mut_scan::retain_within_budget_into;
+/// let mut quota_requests = vec![3, 8, 2, 4];
+/// let mut rejected = vec![];
+/// retain_within_budget_into(&mut quota_requests, 10, |&amount| amount, &mut rejected);
+///
+/// assert_eq!(quota_requests, [3, 2, 4]);
+/// assert_eq!(rejected, [8]);
+/// ```
+pub fn retain_within_budget_into<T, C, S: Sink<T> + ?Sized>(
+    vec: &mut Vec<T>,
+    budget: C,
+    mut cost: impl FnMut(&T) -> C,
+    sink: &mut S,
+) -> C
+where
+    C: Copy + PartialOrd + Sub<Output = C>,
+{
+    let mut left = budget;
+    let mut scan = VecMutScan::new(vec);
+    while let Some(item) = scan.next() {
+        let (next_left, keep) = charge(left, cost(&item));
+        left = next_left;
+        if !keep {
+            sink.accept(item.remove());
+        }
+    }
+    left
+}
+
+/// Subtracts `cost` from the budget `left` if it fits, returning the new budget and whether it fit.
+fn charge<C: Copy + PartialOrd + Sub<Output = C>>(left: C, cost: C) -> (C, bool) {
+    // Comparing before subtracting avoids overflowing unsigned costs.
+    if cost <= left {
+        (left - cost, true)
+    } else {
+        (left, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn top_k() {
+        let input: Vec<u32> = (0..200).map(|i| (i * 7919) % 101).collect();
+
+        for &k in &[0, 1, 5, 50, 199, 200, 300] {
+            let mut expected: Vec<_> = input.iter().copied().enumerate().collect();
+            expected.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            expected.truncate(k);
+            expected.sort();
+            let expected: Vec<_> = expected.into_iter().map(|(_, x)| x).collect();
+
+            let mut output = input.clone();
+            retain_top_k(&mut output, k, Ord::cmp);
+            assert_eq!(output, expected);
+        }
+
+        let mut ties = vec![(1, 'a'), (2, 'b'), (1, 'c'), (2, 'd'), (1, 'e')];
+        retain_top_k(&mut ties, 3, |x, y| x.0.cmp(&y.0));
+        assert_eq!(ties, [(1, 'a'), (2, 'b'), (2, 'd')]);
+    }
+
+    #[test]
+    fn max_by_key() {
+        let mut input: Vec<_> = (0..30).map(|i| (i / 4, (i * 7) % 5, i)).collect();
+        retain_max_by_key(&mut input, |x| x.0, |x, y| x.1.cmp(&y.1));
+
+        let mut expected = vec![];
+        for chunk in (0..30).collect::<Vec<_>>().chunks(4) {
+            let mut best = chunk[0];
+            for &i in chunk {
+                if (i * 7) % 5 > (best * 7) % 5 {
+                    best = i;
+                }
+            }
+            expected.push((best / 4, (best * 7) % 5, best));
+        }
+        assert_eq!(input, expected);
+
+        let mut ties = vec![(1, 'a'), (1, 'b'), (2, 'c')];
+        retain_max_by_key(&mut ties, |x| x.0, |_, _| Ordering::Equal);
+        assert_eq!(ties, [(1, 'a'), (2, 'c')]);
+    }
+
+    #[test]
+    fn adaptive() {
+        let strategies = [
+            RetainStrategy::Auto,
+            RetainStrategy::InPlace,
+            RetainStrategy::OutOfPlace,
+        ];
+        for &len in &[0, 10, 64, 65, 1000] {
+            for &modulus in &[1, 2, 5, 50] {
+                for &strategy in &strategies {
+                    let mut input: Vec<_> = (0..len).collect();
+                    let used = retain_adaptive(&mut input, strategy, |x| *x % modulus == 0);
+
+                    let expected: Vec<_> = (0..len).filter(|x| x % modulus == 0).collect();
+                    assert_eq!(input, expected);
+                    assert_ne!(used, RetainStrategy::Auto);
+                    if strategy != RetainStrategy::Auto {
+                        assert_eq!(used, strategy);
+                    }
+                }
+            }
+        }
+
+        let mut input: Vec<_> = (0..1000).collect();
+        let used = retain_adaptive(&mut input, RetainStrategy::Auto, |x| *x % 2 == 0);
+        assert_eq!(used, RetainStrategy::InPlace);
+        assert_eq!(input.capacity(), 1000);
+    }
+
+    #[test]
+    fn enumerated() {
+        let mut input: Vec<_> = (0..20).map(|i| i * 10).collect();
+        let mut kept = 0;
+        retain_enumerated_with_output(&mut input, |index, output, item| {
+            assert_eq!(*item, index * 10);
+            assert_eq!(output, kept);
+            let keep = index % 3 != 1;
+            kept += keep as usize;
+            keep
+        });
+        let expected: Vec<_> = (0..20).filter(|i| i % 3 != 1).map(|i| i * 10).collect();
+        assert_eq!(input, expected);
+
+        retain_enumerated(&mut input, |index, _| index >= 4);
+        assert_eq!(input, &expected[4..]);
+    }
+
+    #[test]
+    fn thinning() {
+        let mut input: Vec<_> = (0..10).collect();
+        thin(&mut input, 1);
+        assert_eq!(input.len(), 10);
+        thin(&mut input, 4);
+        assert_eq!(input, [0, 4, 8]);
+
+        for len in 0..20 {
+            for &fraction in &[0.0, 0.1, 0.25, 0.5, 0.9, 1.0] {
+                let mut input: Vec<_> = (0..len).collect();
+                thin_to_fraction(&mut input, fraction);
+                assert_eq!(input.len(), (len as f64 * fraction) as usize);
+                assert!(input.is_empty() || input[0] == 0);
+            }
+        }
+    }
+
+    #[test]
+    fn within_budget() {
+        let mut input: Vec<u64> = vec![5, 7, 2, u64::MAX, 1, 3, 0];
+        let mut removed = vec![];
+        let unused = retain_within_budget_into(&mut input, 10, |&x| x, &mut removed);
+        assert_eq!(input, [5, 2, 1, 0]);
+        assert_eq!(removed, [7, u64::MAX, 3]);
+        assert_eq!(unused, 2);
+
+        let mut input = vec![0.5, 0.25, 0.5, 0.25];
+        assert_eq!(retain_within_budget(&mut input, 1.0, |&x| x), 0.0);
+        assert_eq!(input, [0.5, 0.25, 0.25]);
+    }
+}
@@ -0,0 +1,551 @@
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{
+    mem,
+    ops::{Deref, DerefMut},
+    ptr, slice,
+};
+
+use crate::GrowStorage;
+
+/// Backward scan over a vector with mutation, insertion and removal.
+///
+/// This is the mirror image of [`VecGrowScan`](crate::VecGrowScan): items are visited from the
+/// last to the first one and inserted items are placed between the items that haven't been
+/// visited yet and the visited items, i.e. in front of the visited items. Accordingly, "before"
+/// and "after" refer to the order of the scan, so that an item inserted before the current item
+/// ends up behind it within the vector. Sequences of items passed to the scan are always given in
+/// the vector's order and keep that order.
+///
+/// Dropping the `VecGrowRevScan` mid-iteration keeps the remaining items in front of the visited
+/// items.
+///
+/// ```
+/// # use vec_mut_scan::VecGrowRevScan;
+/// // Expand every `2` into `[1, 1]`, visiting from the back.
+/// let mut numbers = vec![2, 3, 2];
+/// let mut scan = VecGrowRevScan::new(&mut numbers);
+/// let mut visited = vec![];
+///
+/// while let Some(item) = scan.next() {
+///     visited.push(*item);
+///     if *item == 2 {
+///         item.replace_with_many(vec![1, 1]);
+///     }
+/// }
+/// drop(scan);
+///
+/// assert_eq!(visited, [2, 3, 2]);
+/// assert_eq!(numbers, [1, 1, 3, 1, 1]);
+/// ```
+pub struct VecGrowRevScan<'a, T: 'a> {
+    vec: &'a mut Vec<T>,
+    base: *mut T,
+    read: usize,
+    write: usize,
+    end: usize,
+    queue: VecDeque<T>,
+}
+
+// See `VecGrowScan`.
+unsafe impl<'a, T: Send> Send for VecGrowRevScan<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for VecGrowRevScan<'a, T> {}
+
+// The vector's buffer consists of the remaining items `0..read`, the gap `read..write` and the
+// visited items `write..end`. The queued items belong between the gap and the visited items, in
+// the queue's order.
+//
+// invariant: if there's a gap in the vector, then the queue is empty.
+
+impl<'a, T: 'a> VecGrowRevScan<'a, T> {
+    /// Begin a backward scan over a vector with mutation, insertion and removal.
+    pub fn new(vec: &'a mut Vec<T>) -> Self {
+        let base = vec.as_mut_ptr();
+        let end = vec.len();
+
+        // Leak amplification, see `VecGrowScan::new`.
+        unsafe { vec.set_len(0) };
+
+        VecGrowRevScan {
+            vec,
+            base,
+            read: end,
+            write: end,
+            end,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Advance to the previous item of the vector.
+    ///
+    /// This returns a reference wrapper that enables item insertion and removal (see
+    /// [`VecGrowRevScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<VecGrowRevScanItem<'s, 'a, T>> {
+        if self.read != 0 {
+            Some(VecGrowRevScanItem { scan: self })
+        } else {
+            None
+        }
+    }
+
+    /// Insert an item between the items that haven't been visited yet and the visited items.
+    /// Inserted items are not returned during iteration.
+    ///
+    /// Each inserted item is placed in front of the previously inserted items.
+    pub fn insert(&mut self, item: T) {
+        if self.read < self.write {
+            // The queue is empty by invariant, so this is the right place.
+            self.write -= 1;
+            unsafe { ptr::write(self.base.add(self.write), item) };
+        } else {
+            self.queue.push_front(item);
+        }
+    }
+
+    /// Insert a sequence of items between the items that haven't been visited yet and the visited
+    /// items, keeping their order. Inserted items are not returned during iteration.
+    ///
+    /// The sequence is placed in front of the previously inserted items.
+    pub fn insert_many(&mut self, iter: impl IntoIterator<Item = T>) {
+        let items: Vec<T> = iter.into_iter().collect();
+        for item in items.into_iter().rev() {
+            self.insert(item);
+        }
+    }
+
+    /// Moves the current item at `read - 1` out of the vector.
+    fn take_current(&mut self) -> T {
+        unsafe {
+            self.read -= 1;
+            let result = ptr::read(self.base.add(self.read));
+            // Fill the new gap with a queued item, if any.
+            if let Some(dequeued) = self.queue.pop_back() {
+                self.write -= 1;
+                ptr::write(self.base.add(self.write), dequeued);
+            }
+            result
+        }
+    }
+
+    /// Marks the current item at `read - 1` as visited.
+    fn keep_current(&mut self) {
+        unsafe {
+            self.read -= 1;
+            if self.read + 1 != self.write {
+                // Move the item over the gap.
+                self.write -= 1;
+                ptr::copy_nonoverlapping(self.base.add(self.read), self.base.add(self.write), 1);
+            } else if let Some(dequeued) = self.queue.pop_back() {
+                // There is no gap, so the item moves to the front of the queue, making room for
+                // the last queued item, which directly precedes the visited items.
+                let item = ptr::read(self.base.add(self.read));
+                self.queue.push_front(item);
+                ptr::write(self.base.add(self.read), dequeued);
+                self.write -= 1;
+            } else {
+                self.write -= 1;
+            }
+        }
+    }
+
+    /// Access the whole vector.
+    ///
+    /// This returns the items that haven't been visited yet, the queued items as two slices and
+    /// the visited items, in the vector's order.
+    pub fn slices(&self) -> (&[T], &[T], &[T], &[T]) {
+        let (mid_l, mid_r) = self.queue.as_slices();
+        unsafe {
+            (
+                slice::from_raw_parts(self.base, self.read),
+                mid_l,
+                mid_r,
+                slice::from_raw_parts(self.base.add(self.write), self.end - self.write),
+            )
+        }
+    }
+
+    /// Access and mutate the whole vector.
+    ///
+    /// See [`slices`][VecGrowRevScan::slices].
+    pub fn slices_mut(&mut self) -> (&mut [T], &mut [T], &mut [T], &mut [T]) {
+        let (mid_l, mid_r) = self.queue.as_mut_slices();
+        unsafe {
+            (
+                slice::from_raw_parts_mut(self.base, self.read),
+                mid_l,
+                mid_r,
+                slice::from_raw_parts_mut(self.base.add(self.write), self.end - self.write),
+            )
+        }
+    }
+}
+
+impl<'a, T: 'a> Drop for VecGrowRevScan<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.queue.is_empty() {
+                // Move the visited items to the front, closing the gap.
+                let visited = self.end - self.write;
+                ptr::copy(self.base.add(self.write), self.base.add(self.read), visited);
+                self.vec.set_len(self.read + visited);
+            } else {
+                // By invariant, there is no gap to fix up.
+                self.vec.set_len(self.end);
+                self.vec.splice_queue(self.write, &mut self.queue);
+            }
+        }
+    }
+}
+
+/// Reference wrapper that enables item insertion and removal for [`VecGrowRevScan`].
+///
+/// Dropping the wrapper keeps the item.
+#[repr(transparent)]
+pub struct VecGrowRevScanItem<'s, 'a, T: 'a> {
+    scan: &'s mut VecGrowRevScan<'a, T>,
+}
+
+// When a `VecGrowRevScanItem` is created, there must be valid data at `scan.read - 1`.
+
+impl<'s, 'a, T: 'a> VecGrowRevScanItem<'s, 'a, T> {
+    fn into_inner_forget(self) -> &'s mut VecGrowRevScan<'a, T> {
+        // This is safe, as `VecGrowRevScanItem` is annotated with #[repr(transparent)], see
+        // `VecGrowScanItem::into_inner_forget`.
+        unsafe { mem::transmute(self) }
+    }
+
+    /// Removes and returns this item from the vector.
+    pub fn remove(self) -> T {
+        self.into_inner_forget().take_current()
+    }
+
+    /// Replaces this item with a new value, returns the old value.
+    pub fn replace(self, value: T) -> T {
+        let scan = self.into_inner_forget();
+        let result = scan.take_current();
+        scan.insert(value);
+        result
+    }
+
+    /// Replace this item with a sequence of items, given in the vector's order. Returns the
+    /// replaced item.
+    pub fn replace_with_many(self, values: impl IntoIterator<Item = T>) -> T {
+        let scan = self.into_inner_forget();
+        let result = scan.take_current();
+        scan.insert_many(values);
+        result
+    }
+
+    /// Insert an item before this item in the scan's order, i.e. behind it within the vector.
+    ///
+    /// The inserted item is placed in front of the previously inserted items.
+    pub fn insert_before(&mut self, value: T) {
+        self.scan.insert(value);
+    }
+
+    /// Insert an item after this item in the scan's order, i.e. in front of it within the vector,
+    /// keeping this item. The inserted item is not returned during iteration.
+    pub fn insert_after(self, value: T) {
+        let scan = self.into_inner_forget();
+        scan.keep_current();
+        scan.insert(value);
+    }
+}
+
+impl<'s, 'a, T: 'a> Deref for VecGrowRevScanItem<'s, 'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // The offset `scan.read - 1` contains valid data owned by the scan.
+        unsafe { &*self.scan.base.add(self.scan.read - 1) }
+    }
+}
+
+impl<'s, 'a, T: 'a> DerefMut for VecGrowRevScanItem<'s, 'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // The offset `scan.read - 1` contains valid data owned by the scan.
+        unsafe { &mut *self.scan.base.add(self.scan.read - 1) }
+    }
+}
+
+impl<'s, 'a, T: 'a> Drop for VecGrowRevScanItem<'s, 'a, T> {
+    fn drop(&mut self) {
+        self.scan.keep_current();
+    }
+}
+
+/// Backward scan over a vector with mutation and item removal.
+///
+/// This is the mirror image of [`VecMutScan`](crate::VecMutScan): items are visited from the last
+/// to the first one, e.g. to process the most recently pushed items first. Items are kept in order
+/// and every item is moved at most once, even when items are removed.
+///
+/// Dropping the `VecMutScanRev` mid-iteration keeps the remaining items in front of the visited
+/// items.
+///
+/// ```
+/// # use vec_mut_scan::VecMutScanRev;
+/// // Undo the most recent edits up to and including the last checkpoint.
+/// let mut history = vec!["open", "checkpoint", "type", "checkpoint", "type", "delete"];
+/// let mut scan = VecMutScanRev::new(&mut history);
+/// while let Some(entry) = scan.next() {
+///     if entry.remove() == "checkpoint" {
+///         break;
+///     }
+/// }
+/// drop(scan);
+///
+/// assert_eq!(history, ["open", "checkpoint", "type"]);
+/// ```
+pub struct VecMutScanRev<'a, T: 'a> {
+    vec: &'a mut Vec<T>,
+    base: *mut T,
+    read: usize,
+    write: usize,
+    end: usize,
+}
+
+// See `VecMutScan`.
+unsafe impl<'a, T: Send> Send for VecMutScanRev<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for VecMutScanRev<'a, T> {}
+
+// The vector's buffer consists of the remaining items `0..read`, the gap `read..write` and the
+// visited items `write..end`.
+
+impl<'a, T: 'a> VecMutScanRev<'a, T> {
+    /// Begin a backward scan over a vector with mutation and item removal.
+    pub fn new(vec: &'a mut Vec<T>) -> Self {
+        let base = vec.as_mut_ptr();
+        let end = vec.len();
+
+        // Leak amplification, see `VecMutScan::new`.
+        unsafe { vec.set_len(0) };
+
+        VecMutScanRev {
+            vec,
+            base,
+            read: end,
+            write: end,
+            end,
+        }
+    }
+
+    /// Advance to the previous item of the vector.
+    ///
+    /// This returns a reference wrapper that enables item removal (see [`VecMutScanRevItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<VecMutScanRevItem<'s, 'a, T>> {
+        if self.read != 0 {
+            Some(VecMutScanRevItem { scan: self })
+        } else {
+            None
+        }
+    }
+
+    /// Access the whole vector.
+    ///
+    /// This returns the items that haven't been visited yet and the visited items, in the
+    /// vector's order.
+    pub fn slices(&self) -> (&[T], &[T]) {
+        unsafe {
+            (
+                slice::from_raw_parts(self.base, self.read),
+                slice::from_raw_parts(self.base.add(self.write), self.end - self.write),
+            )
+        }
+    }
+
+    /// Access and mutate the whole vector.
+    ///
+    /// See [`slices`][VecMutScanRev::slices].
+    pub fn slices_mut(&mut self) -> (&mut [T], &mut [T]) {
+        unsafe {
+            (
+                slice::from_raw_parts_mut(self.base, self.read),
+                slice::from_raw_parts_mut(self.base.add(self.write), self.end - self.write),
+            )
+        }
+    }
+}
+
+impl<'a, T: 'a> Drop for VecMutScanRev<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            // Move the visited items to the front, closing the gap.
+            let visited = self.end - self.write;
+            ptr::copy(self.base.add(self.write), self.base.add(self.read), visited);
+            self.vec.set_len(self.read + visited);
+        }
+    }
+}
+
+/// Reference wrapper that enables item removal for [`VecMutScanRev`].
+///
+/// Dropping the wrapper keeps the item.
+#[repr(transparent)]
+pub struct VecMutScanRevItem<'s, 'a, T: 'a> {
+    scan: &'s mut VecMutScanRev<'a, T>,
+}
+
+// When a `VecMutScanRevItem` is created, there must be valid data at `scan.read - 1`.
+
+impl<'s, 'a, T: 'a> VecMutScanRevItem<'s, 'a, T> {
+    fn into_inner_forget(self) -> &'s mut VecMutScanRev<'a, T> {
+        // This is safe, as `VecMutScanRevItem` is annotated with #[repr(transparent)], see
+        // `VecGrowScanItem::into_inner_forget`.
+        unsafe { mem::transmute(self) }
+    }
+
+    /// Removes and returns this item from the vector.
+    pub fn remove(self) -> T {
+        let scan = self.into_inner_forget();
+        scan.read -= 1;
+        // The item at `read` is valid and becomes part of the gap.
+        unsafe { ptr::read(scan.base.add(scan.read)) }
+    }
+
+    /// Replaces this item with a new value, returns the old value.
+    ///
+    /// This is equivalent to assigning a new value or calling [`mem::replace`] on the mutable
+    /// reference obtained by using [`DerefMut`], but can avoid an intermediate move within the
+    /// vector's buffer.
+    pub fn replace(self, value: T) -> T {
+        let scan = self.into_inner_forget();
+        scan.read -= 1;
+        scan.write -= 1;
+        unsafe {
+            // The item at `read` is moved out and `value` is written directly to its destination,
+            // which is either the same slot or the last slot of the gap.
+            let result = ptr::read(scan.base.add(scan.read));
+            ptr::write(scan.base.add(scan.write), value);
+            result
+        }
+    }
+}
+
+impl<'s, 'a, T: 'a> Deref for VecMutScanRevItem<'s, 'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // The offset `scan.read - 1` contains valid data owned by the scan.
+        unsafe { &*self.scan.base.add(self.scan.read - 1) }
+    }
+}
+
+impl<'s, 'a, T: 'a> DerefMut for VecMutScanRevItem<'s, 'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // The offset `scan.read - 1` contains valid data owned by the scan.
+        unsafe { &mut *self.scan.base.add(self.scan.read - 1) }
+    }
+}
+
+impl<'s, 'a, T: 'a> Drop for VecMutScanRevItem<'s, 'a, T> {
+    fn drop(&mut self) {
+        let scan = &mut *self.scan;
+        scan.read -= 1;
+        scan.write -= 1;
+        // Move the item over the gap, the slots may be the same.
+        unsafe { ptr::copy(scan.base.add(scan.read), scan.base.add(scan.write), 1) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec};
+
+    #[test]
+    fn reverse_edits() {
+        let mut input: Vec<_> = (0..12).map(Rc::new).collect();
+        let input_copy = input.clone();
+        // The model is built back to front, so it is reversed at the end.
+        let mut expected = vec![];
+
+        let mut scan = VecGrowRevScan::new(&mut input);
+        let mut value = 100;
+        while let Some(mut item) = scan.next() {
+            let current = **item;
+            value += 1;
+            match current % 6 {
+                0 => {
+                    assert_eq!(*item.remove(), current);
+                }
+                1 => {
+                    item.insert_before(Rc::new(value));
+                    expected.push(value);
+                    expected.push(current);
+                    *item = Rc::new(current + 50);
+                    *expected.last_mut().unwrap() += 50;
+                }
+                2 => {
+                    item.insert_after(Rc::new(value));
+                    expected.push(current);
+                    expected.push(value);
+                }
+                3 => {
+                    assert_eq!(*item.replace(Rc::new(value)), current);
+                    expected.push(value);
+                }
+                4 => {
+                    item.replace_with_many(vec![Rc::new(value), Rc::new(value + 1)]);
+                    expected.push(value + 1);
+                    expected.push(value);
+                }
+                _ => {
+                    expected.push(current);
+                }
+            }
+            if current == 6 {
+                break;
+            }
+        }
+        scan.insert_many(vec![Rc::new(1000), Rc::new(1001)]);
+        expected.push(1001);
+        expected.push(1000);
+        drop(scan);
+        expected.extend((0..6).rev());
+        expected.reverse();
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, expected);
+        drop(input_copy);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    fn reverse_removal() {
+        let mut input: Vec<_> = (0..10).map(Rc::new).collect();
+        let input_copy = input.clone();
+
+        let mut scan = VecMutScanRev::new(&mut input);
+        let mut seen = vec![];
+        loop {
+            let mut item = match scan.next() {
+                Some(item) => item,
+                None => break,
+            };
+            let current = **item;
+            seen.push(current);
+            match current % 4 {
+                0 => assert_eq!(*item.remove(), current),
+                1 => assert_eq!(*item.replace(Rc::new(current + 50)), current),
+                2 => *item = Rc::new(current + 100),
+                _ => (),
+            }
+            if current == 3 {
+                break;
+            }
+        }
+        let (remaining, visited) = scan.slices();
+        assert_eq!(remaining.len(), 3);
+        assert_eq!(**visited.first().unwrap(), 3);
+        drop(scan);
+
+        assert_eq!(seen, [9, 8, 7, 6, 5, 4, 3]);
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [0, 1, 2, 3, 55, 106, 7, 59]);
+        drop(input_copy);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+}
@@ -0,0 +1,158 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{ScanError, VecGrowScan};
+
+/// A set of rewrite rules that are applied to a vector until no rule matches anymore.
+///
+/// Each rule consists of a predicate and a rewrite function. Rules are registered using
+/// [`rule`](Rewriter::rule) and are tried in the order of registration. The first rule whose
+/// predicate matches an item replaces that item with the items returned by its rewrite function,
+/// which may be empty.
+///
+/// The rules are applied in passes, each a single scan over the vector. Items produced during a
+/// pass are only visited by the following pass. Passes are repeated until a pass makes no changes.
+/// As a set of rules can keep rewriting items indefinitely, the number of passes is limited, see
+/// [`max_passes`](Rewriter::max_passes).
+///
+/// ```
+/// # use vec_mut_scan::Rewriter;
+/// let mut numbers = vec![3, 10, 1];
+///
+/// let passes = Rewriter::new()
+///     .rule(|&x| x > 3, |x| vec![x / 2, x - x / 2])
+///     .rule(|&x| x == 1, |_| vec![])
+///     .run(&mut numbers)
+///     .unwrap();
+///
+/// assert_eq!(numbers, [3, 2, 3, 2, 3]);
+/// assert_eq!(passes, 2);
+/// ```
+pub struct Rewriter<'f, T> {
+    rules: Vec<Rule<'f, T>>,
+    max_passes: usize,
+}
+
+struct Rule<'f, T> {
+    predicate: Box<dyn FnMut(&T) -> bool + 'f>,
+    rewrite: Box<dyn FnMut(T) -> Vec<T> + 'f>,
+}
+
+impl<'f, T> Default for Rewriter<'f, T> {
+    fn default() -> Self {
+        Rewriter {
+            rules: Vec::new(),
+            max_passes: 1000,
+        }
+    }
+}
+
+impl<'f, T> Rewriter<'f, T> {
+    /// Create a rewriter without any rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule that replaces each item matching `predicate` with the items returned by
+    /// `rewrite`.
+    pub fn rule(
+        mut self,
+        predicate: impl FnMut(&T) -> bool + 'f,
+        rewrite: impl FnMut(T) -> Vec<T> + 'f,
+    ) -> Self {
+        self.rules.push(Rule {
+            predicate: Box::new(predicate),
+            rewrite: Box::new(rewrite),
+        });
+        self
+    }
+
+    /// Limit the number of passes, defaults to 1000.
+    ///
+    /// This includes the final pass that makes no changes.
+    pub fn max_passes(mut self, max_passes: usize) -> Self {
+        self.max_passes = max_passes;
+        self
+    }
+
+    /// Apply the rules to a vector until no rule matches anymore.
+    ///
+    /// Returns the number of passes that made changes. If the rules still match after the maximal
+    /// number of passes, [`ScanError::BudgetExhausted`] is returned and the vector contains the
+    /// result of the last pass.
+    ///
+    /// The rewriter is not consumed and can be run on multiple vectors.
+    pub fn run(&mut self, vec: &mut Vec<T>) -> Result<usize, ScanError> {
+        for passes in 0..self.max_passes {
+            if !self.pass(vec) {
+                return Ok(passes);
+            }
+        }
+        Err(ScanError::BudgetExhausted)
+    }
+
+    /// Runs a single pass, returns whether any rule matched.
+    fn pass(&mut self, vec: &mut Vec<T>) -> bool {
+        let mut changed = false;
+        let mut scan = VecGrowScan::new(vec);
+        while let Some(item) = scan.next() {
+            for rule in self.rules.iter_mut() {
+                if (rule.predicate)(&item) {
+                    item.replace_with_many_with(&mut rule.rewrite);
+                    changed = true;
+                    break;
+                }
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn budget() {
+        let mut input = vec![1, 2];
+        let result = Rewriter::new()
+            .rule(|_| true, |x| vec![x])
+            .max_passes(3)
+            .run(&mut input);
+
+        assert_eq!(result, Err(ScanError::BudgetExhausted));
+        assert_eq!(input, [1, 2]);
+
+        let mut input = vec![1, 2];
+        let result = Rewriter::new()
+            .rule(|&x| x == 1, |x| vec![x + 1, x + 1])
+            .max_passes(2)
+            .run(&mut input);
+
+        assert_eq!(result, Ok(1));
+        assert_eq!(input, [2, 2, 2]);
+
+        let mut input = vec![1, 2];
+        let result = Rewriter::new()
+            .rule(|&x| x == 1, |x| vec![x + 1, x + 1])
+            .max_passes(1)
+            .run(&mut input);
+
+        assert_eq!(result, Err(ScanError::BudgetExhausted));
+        assert_eq!(input, [2, 2, 2]);
+    }
+
+    #[test]
+    fn rule_order() {
+        let mut input: Vec<i32> = (0..6).collect();
+        let passes = Rewriter::new()
+            .rule(|&x| x % 3 == 0 && x > 0, |x| vec![x - 1])
+            .rule(|&x| x % 2 == 0 && x > 0, |_| vec![])
+            .run(&mut input)
+            .unwrap();
+
+        assert_eq!(input, [0, 1, 5]);
+        assert_eq!(passes, 2);
+    }
+}
@@ -0,0 +1,100 @@
+use alloc::vec::Vec;
+use core::iter;
+
+use crate::{VecGrowScan, VecMutScan};
+
+/// Replaces each run of equal consecutive items by a single item, returning the length of each
+/// run.
+///
+/// This run-length encodes the vector in place. The vector keeps the first item of each run and
+/// the returned vector contains the corresponding run lengths. Use [`rle_expand`] to undo this.
+///
+/// ```
+/// # use vec_mut_scan::rle_compress;
+/// let mut pixels = vec![0, 0, 0, 1, 1, 0, 2, 2, 2, 2];
+/// let runs = rle_compress(&mut pixels);
+///
+/// assert_eq!(pixels, [0, 1, 0, 2]);
+/// assert_eq!(runs, [3, 2, 1, 4]);
+/// ```
+pub fn rle_compress<T: PartialEq>(vec: &mut Vec<T>) -> Vec<usize> {
+    let mut runs: Vec<usize> = Vec::new();
+    let mut scan = VecMutScan::new(vec);
+    while let Some(item) = scan.next() {
+        let repeated = match item.slices().0.last() {
+            Some(last) => *item == *last,
+            None => false,
+        };
+        if repeated {
+            *runs.last_mut().unwrap() += 1;
+            item.remove();
+        } else {
+            runs.push(1);
+        }
+    }
+    runs
+}
+
+/// Replaces each item by a run of clones of the item, with lengths given by `runs`.
+///
+/// This is the inverse of [`rle_compress`]. A run length of zero removes the item.
+///
+/// Panics if `runs` and the vector have different lengths.
+///
+/// ```
+/// # use vec_mut_scan::rle_expand;
+/// let mut pixels = vec![0, 1, 0, 2];
+/// rle_expand(&mut pixels, &[3, 2, 1, 4]);
+///
+/// assert_eq!(pixels, [0, 0, 0, 1, 1, 0, 2, 2, 2, 2]);
+/// ```
+pub fn rle_expand<T: Clone>(vec: &mut Vec<T>, runs: &[usize]) {
+    assert_eq!(vec.len(), runs.len(), "run count does not match vector");
+    let mut scan = VecGrowScan::new(vec);
+    for &run in runs {
+        let item = scan.next().unwrap();
+        match run {
+            0 => drop(item.remove()),
+            1 => (),
+            _ => item.replace_with_many_with(|value| {
+                iter::repeat(value.clone())
+                    .take(run - 1)
+                    .chain(iter::once(value))
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec};
+
+    #[test]
+    fn round_trip() {
+        let a = Rc::new(1);
+        let b = Rc::new(2);
+        let input = [&a, &a, &b, &a, &b, &b, &b];
+        let mut items: Vec<_> = input.iter().map(|&x| x.clone()).collect();
+
+        let runs = rle_compress(&mut items);
+        assert_eq!(runs, [2, 1, 1, 3]);
+        assert_eq!(items, [a.clone(), b.clone(), a.clone(), b.clone()]);
+        assert_eq!(Rc::strong_count(&a), 3);
+
+        rle_expand(&mut items, &runs);
+        let expected: Vec<_> = input.iter().map(|&x| x.clone()).collect();
+        assert_eq!(items, expected);
+        drop(expected);
+
+        rle_expand(&mut items, &[0, 1, 0, 2, 0, 0, 1]);
+        assert_eq!(items, [a.clone(), a.clone(), a.clone(), b.clone()]);
+        drop(items);
+        assert_eq!(Rc::strong_count(&a), 1);
+        assert_eq!(Rc::strong_count(&b), 1);
+
+        let mut empty: Vec<i32> = vec![];
+        assert!(rle_compress(&mut empty).is_empty());
+    }
+}
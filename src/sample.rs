@@ -0,0 +1,103 @@
+use alloc::vec::Vec;
+use rand::Rng;
+
+use crate::VecMutScan;
+
+/// Retain each item independently with the given probability.
+///
+/// Panics if `probability` is not within `0.0..=1.0`.
+///
+/// Requires the `rand` feature.
+///
+/// ```
+/// # use vec_mut_scan::retain_sample;
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let mut numbers: Vec<_> = (0..1000).collect();
+/// retain_sample(&mut numbers, 0.1, &mut rng);
+///
+/// assert!(numbers.len() < 200);
+/// assert!(numbers.windows(2).all(|pair| pair[0] < pair[1]));
+/// ```
+pub fn retain_sample<T, R: Rng + ?Sized>(vec: &mut Vec<T>, probability: f64, rng: &mut R) {
+    assert!(
+        (0.0..=1.0).contains(&probability),
+        "probability must be within 0.0..=1.0"
+    );
+
+    let mut scan = VecMutScan::new(vec);
+    while let Some(item) = scan.next() {
+        if !rng.gen_bool(probability) {
+            item.remove();
+        }
+    }
+}
+
+/// Retain a uniformly random selection of exactly `k` items, keeping their relative order.
+///
+/// If there are at most `k` items, all items are kept. This uses selection sampling, which decides
+/// for each item in turn whether to keep it, so no indices need to be collected beforehand.
+///
+/// Requires the `rand` feature.
+///
+/// ```
+/// # use vec_mut_scan::retain_sample_exact;
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let mut numbers: Vec<_> = (0..1000).collect();
+/// retain_sample_exact(&mut numbers, 10, &mut rng);
+///
+/// assert_eq!(numbers.len(), 10);
+/// assert!(numbers.windows(2).all(|pair| pair[0] < pair[1]));
+/// ```
+pub fn retain_sample_exact<T, R: Rng + ?Sized>(vec: &mut Vec<T>, k: usize, rng: &mut R) {
+    let mut remaining = vec.len();
+    let mut needed = k;
+
+    let mut scan = VecMutScan::new(vec);
+    while let Some(item) = scan.next() {
+        // Every selection of `needed` items out of the `remaining` items is equally likely.
+        if needed < remaining && rng.gen_range(0..remaining) >= needed {
+            item.remove();
+        } else {
+            needed -= 1;
+        }
+        remaining -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    #[test]
+    fn exact_sample() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let mut counts = [0; 10];
+
+        for _ in 0..1000 {
+            let mut input: Vec<_> = (0..10).collect();
+            retain_sample_exact(&mut input, 3, &mut rng);
+            assert_eq!(input.len(), 3);
+            for &x in &input {
+                counts[x] += 1;
+            }
+        }
+
+        // Each item is kept with probability 0.3.
+        assert!(counts.iter().all(|&count| count > 200 && count < 400));
+
+        let mut input: Vec<_> = (0..10).collect();
+        retain_sample_exact(&mut input, 20, &mut rng);
+        assert_eq!(input.len(), 10);
+
+        retain_sample(&mut input, 1.0, &mut rng);
+        assert_eq!(input.len(), 10);
+        retain_sample(&mut input, 0.0, &mut rng);
+        assert!(input.is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+use alloc::vec::Vec;
+
+use crate::{VecGrowScan, VecMutScan};
+
+/// A type wrapping a vector, which can be scanned directly.
+///
+/// This is implemented for [`Vec`] and, when the `derive` feature is enabled, can be derived using
+/// `#[derive(Scannable)]` for newtypes wrapping a vector or another `Scannable` type. Deriving
+/// requires the wrapper to have a single field or to mark the wrapped field with `#[scannable]`.
+///
+/// ```
+/// # use vec_mut_scan::Scannable;
+/// struct Instructions(Vec<u8>);
+///
+/// impl Scannable for Instructions {
+///     type Item = u8;
+///
+///     fn scannable_vec(&mut self) -> &mut Vec<u8> {
+///         &mut self.0
+///     }
+/// }
+///
+/// let mut code = Instructions(vec![1, 0, 2, 0]);
+/// let mut scan = code.scan();
+/// while let Some(insn) = scan.next() {
+///     if *insn == 0 {
+///         insn.remove();
+///     }
+/// }
+/// drop(scan);
+///
+/// assert_eq!(code.0, [1, 2]);
+/// ```
+pub trait Scannable {
+    /// The type of the wrapped vector's items.
+    type Item;
+
+    /// Mutable access to the wrapped vector.
+    fn scannable_vec(&mut self) -> &mut Vec<Self::Item>;
+
+    /// Begin a [`VecMutScan`] over the wrapped vector.
+    fn scan(&mut self) -> VecMutScan<'_, Self::Item> {
+        VecMutScan::new(self.scannable_vec())
+    }
+
+    /// Begin a [`VecGrowScan`] over the wrapped vector.
+    fn grow_scan(&mut self) -> VecGrowScan<'_, Self::Item> {
+        VecGrowScan::new(self.scannable_vec())
+    }
+}
+
+impl<T> Scannable for Vec<T> {
+    type Item = T;
+
+    fn scannable_vec(&mut self) -> &mut Vec<T> {
+        self
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    use crate::Scannable;
+
+    #[derive(Scannable)]
+    struct Instructions(Vec<u32>);
+
+    #[derive(Scannable)]
+    struct Program<'a, T> {
+        name: &'a str,
+        #[scannable]
+        code: T,
+    }
+
+    #[test]
+    fn derived() {
+        let mut code = Instructions(vec![1, 2, 3, 4]);
+        let mut scan = code.scan();
+        scan.next().unwrap().remove();
+        drop(scan);
+
+        let mut program = Program { name: "main", code };
+        let mut scan = program.grow_scan();
+        scan.next();
+        scan.insert(10);
+        drop(scan);
+
+        assert_eq!(program.name, "main");
+        assert_eq!(program.code.0, [2, 10, 3, 4]);
+    }
+}
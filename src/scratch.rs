@@ -0,0 +1,303 @@
+use alloc::vec::Vec;
+use core::{
+    mem::{self, MaybeUninit},
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+use crate::{close_gap, ScanError};
+
+/// Forward scan over a vector with mutation, item removal and bounded item insertion, which never
+/// allocates.
+///
+/// This is a variant of [`VecGrowScan`](crate::VecGrowScan) for environments where allocation has
+/// to be avoided. Inserted items are placed into the gap left by removed items. When there is no
+/// gap, they are queued in a caller-provided scratch buffer instead of a `VecDeque`. Insertion
+/// fails when the scratch buffer is full or when the vector's capacity would not suffice to hold
+/// all items once the scan is dropped. Thus neither the scan nor dropping it allocate.
+///
+/// ```
+/// # use vec_mut_scan::{ScanError, ScratchGrowScan};
+/// use std::mem::MaybeUninit;
+///
+/// let mut numbers = Vec::with_capacity(8);
+/// numbers.extend_from_slice(&[1, 2, 3]);
+/// let mut scratch: [MaybeUninit<i32>; 2] = unsafe { MaybeUninit::uninit().assume_init() };
+///
+/// let mut scan = ScratchGrowScan::new(&mut numbers, &mut scratch);
+/// scan.next();
+/// scan.insert(10).unwrap();
+/// scan.insert(11).unwrap();
+/// assert_eq!(scan.insert(12), Err(ScanError::CapacityExceeded));
+/// scan.next().unwrap().remove();
+/// scan.insert(13).unwrap();
+/// drop(scan);
+///
+/// assert_eq!(numbers, [1, 10, 11, 13, 3]);
+/// ```
+pub struct ScratchGrowScan<'a, T: 'a> {
+    vec: &'a mut Vec<T>,
+    base: *mut T,
+    write: usize,
+    read: usize,
+    end: usize,
+    scratch: &'a mut [MaybeUninit<T>],
+    head: usize,
+    queued: usize,
+}
+
+// Like `VecGrowScan`, this owns the items within the vector and the scratch buffer.
+unsafe impl<'a, T: Send> Send for ScratchGrowScan<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for ScratchGrowScan<'a, T> {}
+
+// invariant: as for `VecGrowScan`, if there's a gap in the vector, then the queue is empty. The
+// queue consists of the `queued` items of the scratch buffer starting at `head`, wrapping around.
+
+impl<'a, T: 'a> ScratchGrowScan<'a, T> {
+    /// Begin a scan over a vector, queuing insertions in the given scratch buffer.
+    pub fn new(vec: &'a mut Vec<T>, scratch: &'a mut [MaybeUninit<T>]) -> Self {
+        let base = vec.as_mut_ptr();
+        let end = vec.len();
+
+        // Leak amplification, see `VecMutScan::new`.
+        unsafe { vec.set_len(0) };
+
+        ScratchGrowScan {
+            vec,
+            base,
+            write: 0,
+            read: 0,
+            end,
+            scratch,
+            head: 0,
+            queued: 0,
+        }
+    }
+
+    /// Advance to the next item of the vector.
+    ///
+    /// This returns a reference wrapper that enables item removal (see [`ScratchGrowScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<ScratchGrowScanItem<'s, 'a, T>> {
+        if self.read != self.end {
+            Some(ScratchGrowScanItem { scan: self })
+        } else {
+            None
+        }
+    }
+
+    /// The number of items that can currently be inserted without failing.
+    ///
+    /// This grows with every removed item and shrinks with every inserted item.
+    pub fn insert_capacity(&self) -> usize {
+        let gap = self.read - self.write;
+        let free_scratch = self.scratch.len() - self.queued;
+        let len = self.end - gap + self.queued;
+        let free_vec = self.vec.capacity() - len;
+        gap + free_scratch.min(free_vec)
+    }
+
+    /// Insert an item between the items that have been visited, and the items that haven't been
+    /// visited yet. Inserted items are not returned during iteration.
+    ///
+    /// Returns a mutable reference to the inserted item. Returns
+    /// [`ScanError::CapacityExceeded`] and drops the item if there is no room for it, see
+    /// [`insert_capacity`][ScratchGrowScan::insert_capacity].
+    pub fn insert(&mut self, item: T) -> Result<&mut T, ScanError> {
+        if self.write < self.read {
+            // The queue is empty by invariant, so this is the right place.
+            unsafe {
+                ptr::write(self.base.add(self.write), item);
+                self.write += 1;
+                return Ok(&mut *self.base.add(self.write - 1));
+            }
+        }
+        if self.insert_capacity() == 0 {
+            return Err(ScanError::CapacityExceeded);
+        }
+        let slot = self.queue_slot(self.queued);
+        self.queued += 1;
+        unsafe {
+            ptr::write(slot, item);
+            Ok(&mut *slot)
+        }
+    }
+
+    /// Insert an item constructed by `f` between the items that have been visited, and the items
+    /// that haven't been visited yet.
+    ///
+    /// Like [`insert`][ScratchGrowScan::insert], but `f` is only called when there is room for the
+    /// item, so no item is constructed and dropped when insertion fails.
+    pub fn insert_with(&mut self, f: impl FnOnce() -> T) -> Result<&mut T, ScanError> {
+        if self.insert_capacity() == 0 {
+            return Err(ScanError::CapacityExceeded);
+        }
+        self.insert(f())
+    }
+
+    /// Pointer to the `index`-th slot of the queue.
+    fn queue_slot(&mut self, index: usize) -> *mut T {
+        let pos = (self.head + index) % self.scratch.len();
+        self.scratch[pos].as_mut_ptr()
+    }
+
+    /// Take the first item of the queue.
+    ///
+    /// Safety: the queue must not be empty.
+    unsafe fn pop_front(&mut self) -> T {
+        let item = ptr::read(self.queue_slot(0));
+        self.head = (self.head + 1) % self.scratch.len();
+        self.queued -= 1;
+        item
+    }
+}
+
+impl<'a, T: 'a> Drop for ScratchGrowScan<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            let suffix_len = self.end - self.read;
+            if self.queued == 0 {
+                // Close the gap, see `VecGrowScan`'s `Drop` impl.
+                close_gap(self.base, self.write, self.read, suffix_len);
+                self.vec.set_len(self.write + suffix_len);
+            } else {
+                // There is no gap and `insert` made sure that the vector's capacity suffices for
+                // the queued items, so make room for them and move them into place.
+                ptr::copy(
+                    self.base.add(self.read),
+                    self.base.add(self.read + self.queued),
+                    suffix_len,
+                );
+                let queued = self.queued;
+                for offset in 0..queued {
+                    let item = self.pop_front();
+                    ptr::write(self.base.add(self.write + offset), item);
+                }
+                self.vec.set_len(self.end + queued);
+            }
+        }
+    }
+}
+
+/// Reference wrapper that enables item removal for [`ScratchGrowScan`].
+#[repr(transparent)]
+pub struct ScratchGrowScanItem<'s, 'a, T: 'a> {
+    scan: &'s mut ScratchGrowScan<'a, T>,
+}
+
+// When a `ScratchGrowScanItem` is created, there must be valid data at `scan.read`.
+
+impl<'s, 'a, T: 'a> ScratchGrowScanItem<'s, 'a, T> {
+    fn into_inner_forget(self) -> &'s mut ScratchGrowScan<'a, T> {
+        // This is safe, as `ScratchGrowScanItem` is annotated with #[repr(transparent)], see
+        // `VecGrowScanItem::into_inner_forget`.
+        unsafe { mem::transmute(self) }
+    }
+
+    /// Removes and returns this item from the vector.
+    pub fn remove(self) -> T {
+        let scan = self.into_inner_forget();
+        unsafe {
+            let result = ptr::read(scan.base.add(scan.read));
+            scan.read += 1;
+            // Fill the new gap with a queued item, if any.
+            if scan.queued > 0 {
+                let dequeued = scan.pop_front();
+                ptr::write(scan.base.add(scan.write), dequeued);
+                scan.write += 1;
+            }
+            result
+        }
+    }
+}
+
+impl<'s, 'a, T: 'a> Deref for ScratchGrowScanItem<'s, 'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // The offset `scan.read` contains valid data owned by the scan.
+        unsafe { &*self.scan.base.add(self.scan.read) }
+    }
+}
+
+impl<'s, 'a, T: 'a> DerefMut for ScratchGrowScanItem<'s, 'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // The offset `scan.read` contains valid data owned by the scan.
+        unsafe { &mut *self.scan.base.add(self.scan.read) }
+    }
+}
+
+impl<'s, 'a, T: 'a> Drop for ScratchGrowScanItem<'s, 'a, T> {
+    fn drop(&mut self) {
+        let scan = &mut *self.scan;
+        unsafe {
+            if scan.write < scan.read {
+                // Move the item over the gap.
+                ptr::copy_nonoverlapping(scan.base.add(scan.read), scan.base.add(scan.write), 1);
+            } else if scan.queued > 0 {
+                // Move the item to the back of the queue, replacing it with the front of the
+                // queue.
+                let item = ptr::read(scan.base.add(scan.read));
+                let dequeued = scan.pop_front();
+                ptr::write(scan.queue_slot(scan.queued), item);
+                scan.queued += 1;
+                ptr::write(scan.base.add(scan.write), dequeued);
+            }
+            scan.read += 1;
+            scan.write += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::rc::Rc;
+
+    #[test]
+    fn bounded_insertion() {
+        let mut input: Vec<_> = Vec::with_capacity(10);
+        input.extend((0..6).map(Rc::new));
+        let input_copy = input.clone();
+        let mut scratch: [MaybeUninit<Rc<i32>>; 3] = unsafe { MaybeUninit::uninit().assume_init() };
+
+        let mut scan = ScratchGrowScan::new(&mut input, &mut scratch);
+        assert_eq!(scan.insert_capacity(), 3);
+        scan.next();
+        scan.insert(Rc::new(10)).unwrap();
+        scan.insert(Rc::new(11)).unwrap();
+        scan.next();
+        scan.next();
+        scan.insert(Rc::new(12)).unwrap();
+        assert_eq!(scan.insert(Rc::new(13)), Err(ScanError::CapacityExceeded));
+        scan.next().unwrap().remove();
+        scan.next().unwrap().remove();
+        scan.next().unwrap().remove();
+        assert_eq!(scan.insert_capacity(), 3);
+        *scan.insert(Rc::new(14)).unwrap() = Rc::new(20);
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [0, 10, 11, 1, 2, 12, 20]);
+        assert!(input.iter().all(|x| Rc::strong_count(x) <= 2));
+        drop(input_copy);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+
+        // The vector's capacity limits insertions, too.
+        let mut input: Vec<_> = Vec::with_capacity(3);
+        input.extend((0..2).map(Rc::new));
+        let mut scan = ScratchGrowScan::new(&mut input, &mut scratch);
+        scan.next();
+        assert_eq!(scan.insert_capacity(), 1);
+        scan.insert(Rc::new(10)).unwrap();
+        assert!(scan.insert(Rc::new(11)).is_err());
+        assert!(scan
+            .insert_with(|| unreachable!("constructed without room"))
+            .is_err());
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [0, 10, 1]);
+    }
+}
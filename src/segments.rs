@@ -0,0 +1,256 @@
+use alloc::vec::Vec;
+use core::{
+    mem,
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+use crate::VecGrowScan;
+
+/// Forward scan over the segments of a jagged vector, stored as a flat data vector and a vector of
+/// segment offsets, with mutation, removal and replacement of whole segments.
+///
+/// Segment `i` consists of the items `data[offsets[i]..offsets[i + 1]]`, so there is one more
+/// offset than there are segments, the first offset is zero and the last offset is the length of
+/// the data vector. Both vectors are updated in a single pass: the data vector like with a
+/// [`VecGrowScan`], and each kept segment's end offset is rewritten in place.
+///
+/// Dropping the `SegmentScan` mid-iteration keeps the remaining segments.
+///
+/// ```
+/// # use vec_mut_scan::SegmentScan;
+/// let mut data = vec![1, 2, 3, 4, 5, 6];
+/// let mut offsets = vec![0, 2, 2, 5, 6];
+///
+/// // Drop empty segments and expand segments of length one.
+/// let mut scan = SegmentScan::new(&mut data, &mut offsets);
+/// while let Some(mut segment) = scan.next() {
+///     match segment.len() {
+///         0 => drop(segment.remove()),
+///         1 => {
+///             let item = segment[0];
+///             segment.replace(vec![0, item]);
+///         }
+///         _ => segment[0] *= 10,
+///     }
+/// }
+/// drop(scan);
+///
+/// assert_eq!(data, [10, 2, 30, 4, 5, 0, 6]);
+/// assert_eq!(offsets, [0, 2, 5, 7]);
+/// ```
+pub struct SegmentScan<'a, T: 'a> {
+    data: VecGrowScan<'a, T>,
+    offsets: &'a mut Vec<usize>,
+    // Index of the next segment to visit.
+    read: usize,
+    // Number of kept segments, whose end offsets are stored at `offsets[1..=write]`.
+    write: usize,
+    // Original start offset of the next segment to visit.
+    start: usize,
+    // Number of items in the kept segments.
+    len: usize,
+}
+
+impl<'a, T: 'a> SegmentScan<'a, T> {
+    /// Begin a scan over the segments of a jagged vector with mutation, removal and replacement.
+    ///
+    /// Panics if `offsets` is empty, doesn't start with zero, doesn't end with the length of
+    /// `data` or is not sorted.
+    pub fn new(data: &'a mut Vec<T>, offsets: &'a mut Vec<usize>) -> Self {
+        assert!(
+            offsets.first() == Some(&0) && offsets.last() == Some(&data.len()),
+            "offsets must start with zero and end with the data length"
+        );
+        assert!(
+            offsets.windows(2).all(|pair| pair[0] <= pair[1]),
+            "offsets must be sorted"
+        );
+        SegmentScan {
+            data: VecGrowScan::new(data),
+            offsets,
+            read: 0,
+            write: 0,
+            start: 0,
+            len: 0,
+        }
+    }
+
+    /// Advance to the next segment.
+    ///
+    /// This returns a reference wrapper that dereferences to the segment's items and enables
+    /// removing or replacing the segment (see [`SegmentScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<SegmentScanItem<'s, 'a, T>> {
+        if self.read + 1 < self.offsets.len() {
+            Some(SegmentScanItem { scan: self })
+        } else {
+            None
+        }
+    }
+
+    /// The number of segments that were not visited yet.
+    pub fn remaining_segments(&self) -> usize {
+        self.offsets.len() - 1 - self.read
+    }
+
+    /// Length of the next segment to visit.
+    fn current_len(&self) -> usize {
+        // `offsets[read + 1]` was not overwritten, as `write <= read`.
+        self.offsets[self.read + 1] - self.start
+    }
+
+    /// Remove the items of the current segment from the data scan.
+    fn take_current(&mut self) -> Vec<T> {
+        let mut left = self.current_len();
+        self.data.take_matching_prefix(|_| {
+            if left > 0 {
+                left -= 1;
+                true
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Finish visiting the current segment, which now has `len` items in the data scan's visited
+    /// items, if it was kept.
+    fn finish_segment(&mut self, kept: Option<usize>) {
+        self.start = self.offsets[self.read + 1];
+        self.read += 1;
+        if let Some(len) = kept {
+            self.len += len;
+            self.write += 1;
+            self.offsets[self.write] = self.len;
+        }
+    }
+}
+
+impl<'a, T: 'a> Drop for SegmentScan<'a, T> {
+    fn drop(&mut self) {
+        // The remaining segments keep their lengths, so their end offsets are shifted by the change
+        // in length of the visited segments and moved to follow the kept segments.
+        let count = self.offsets.len() - 1 - self.read;
+        for i in 1..=count {
+            self.offsets[self.write + i] = self.offsets[self.read + i] - self.start + self.len;
+        }
+        self.offsets.truncate(self.write + count + 1);
+    }
+}
+
+/// Reference wrapper that enables removing or replacing a segment of a [`SegmentScan`].
+///
+/// Dereferences to the segment's items. Dropping the wrapper keeps the segment.
+pub struct SegmentScanItem<'s, 'a, T: 'a> {
+    scan: &'s mut SegmentScan<'a, T>,
+}
+
+// When a `SegmentScanItem` is created, the scan must have a remaining segment.
+
+impl<'s, 'a, T: 'a> SegmentScanItem<'s, 'a, T> {
+    fn into_inner_forget(self) -> &'s mut SegmentScan<'a, T> {
+        let item = mem::ManuallyDrop::new(self);
+        // The reference is moved out of `item`, which is never used or dropped afterwards.
+        unsafe { ptr::read(&item.scan) }
+    }
+
+    /// Removes this segment, returning its items.
+    pub fn remove(self) -> Vec<T> {
+        let scan = self.into_inner_forget();
+        let items = scan.take_current();
+        scan.finish_segment(None);
+        items
+    }
+
+    /// Replaces this segment's items with the items of an iterator, returning the old items.
+    ///
+    /// The new items may differ in number from the old items.
+    pub fn replace(self, items: impl IntoIterator<Item = T>) -> Vec<T> {
+        let scan = self.into_inner_forget();
+        let old = scan.take_current();
+        let items: Vec<T> = items.into_iter().collect();
+        let len = items.len();
+        scan.data.insert_vec(items);
+        scan.finish_segment(Some(len));
+        old
+    }
+}
+
+impl<'s, 'a, T: 'a> Deref for SegmentScanItem<'s, 'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.scan.data.slices().3[..self.scan.current_len()]
+    }
+}
+
+impl<'s, 'a, T: 'a> DerefMut for SegmentScanItem<'s, 'a, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        let len = self.scan.current_len();
+        &mut self.scan.data.slices_mut().3[..len]
+    }
+}
+
+impl<'s, 'a, T: 'a> Drop for SegmentScanItem<'s, 'a, T> {
+    fn drop(&mut self) {
+        let len = self.scan.current_len();
+        for _ in 0..len {
+            self.scan.data.next();
+        }
+        self.scan.finish_segment(Some(len));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec};
+
+    #[test]
+    fn segment_edits() {
+        let mut data: Vec<_> = (0..10).map(Rc::new).collect();
+        let mut offsets = vec![0, 3, 3, 4, 7, 9, 10];
+        let copies = data.clone();
+
+        let mut scan = SegmentScan::new(&mut data, &mut offsets);
+        let mut lens = vec![];
+        loop {
+            let mut segment = match scan.next() {
+                Some(segment) => segment,
+                None => break,
+            };
+            lens.push(segment.len());
+            match segment.first().map(|x| **x) {
+                Some(0) => drop(segment.replace(vec![Rc::new(20)])),
+                Some(3) => drop(segment.remove()),
+                Some(4) => {
+                    let old = segment.replace((30..34).map(Rc::new));
+                    assert_eq!(old.len(), 3);
+                }
+                Some(7) => {
+                    segment[1] = Rc::new(40);
+                    break;
+                }
+                _ => (),
+            }
+        }
+        assert_eq!(scan.remaining_segments(), 1);
+        drop(scan);
+
+        assert_eq!(lens, [3, 0, 1, 3, 2]);
+        let values: Vec<_> = data.iter().map(|x| **x).collect();
+        assert_eq!(values, [20, 30, 31, 32, 33, 7, 40, 9]);
+        assert_eq!(offsets, [0, 1, 1, 5, 7, 8]);
+        drop(copies);
+        assert!(data.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "offsets must be sorted")]
+    fn unsorted_offsets() {
+        let mut data = vec![1, 2];
+        let mut offsets = vec![0, 2, 1, 2];
+        SegmentScan::new(&mut data, &mut offsets);
+    }
+}
@@ -0,0 +1,298 @@
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{VecGrowScan, VecMutScan};
+
+/// A destination for items removed during a scan.
+///
+/// This is implemented for closures taking the item by value, for [`Vec`] and [`VecDeque`] (which
+/// append the item) and, with the `std` feature enabled, for the sending halves of
+/// `std::sync::mpsc` channels. The latter allows a consumer thread to process removed items
+/// while the scan is still in progress. Maps from keys to vectors, i.e. [`BTreeMap`] and, with the
+/// `std` feature enabled, `HashMap`, accept key-item pairs, appending the item to the vector of
+/// its key.
+pub trait Sink<T> {
+    /// Accept an item removed during a scan.
+    fn accept(&mut self, item: T);
+}
+
+impl<T, F: FnMut(T)> Sink<T> for F {
+    fn accept(&mut self, item: T) {
+        self(item)
+    }
+}
+
+impl<T> Sink<T> for Vec<T> {
+    fn accept(&mut self, item: T) {
+        self.push(item)
+    }
+}
+
+impl<T> Sink<T> for VecDeque<T> {
+    fn accept(&mut self, item: T) {
+        self.push_back(item)
+    }
+}
+
+impl<K: Ord, T> Sink<(K, T)> for BTreeMap<K, Vec<T>> {
+    fn accept(&mut self, (key, item): (K, T)) {
+        self.entry(key).or_default().push(item)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Hash + Eq, T, S: BuildHasher> Sink<(K, T)> for HashMap<K, Vec<T>, S> {
+    fn accept(&mut self, (key, item): (K, T)) {
+        self.entry(key).or_default().push(item)
+    }
+}
+
+/// Sends the item over the channel. If the receiving half was dropped, the item is dropped, too.
+#[cfg(feature = "std")]
+impl<T> Sink<T> for std::sync::mpsc::Sender<T> {
+    fn accept(&mut self, item: T) {
+        let _ = self.send(item);
+    }
+}
+
+/// Sends the item over the channel, blocking while the channel's buffer is full. If the receiving
+/// half was dropped, the item is dropped, too.
+#[cfg(feature = "std")]
+impl<T> Sink<T> for std::sync::mpsc::SyncSender<T> {
+    fn accept(&mut self, item: T) {
+        let _ = self.send(item);
+    }
+}
+
+/// Retain only the items for which `keep` returns `true`, passing all other items to `sink`.
+///
+/// Removed items are passed to the sink in order, as soon as they are removed.
+///
+/// ```
+/// # use vec_mut_scan::retain_into;
+/// let mut numbers = vec![1, 2, 3, 4, 5, 6];
+/// let (sender, receiver) = std::sync::mpsc::channel();
+///
+/// let consumer = std::thread::spawn(move || receiver.iter().sum::<i32>());
+///
+/// retain_into(&mut numbers, |x| *x % 3 != 0, &mut |x| sender.send(x).unwrap());
+/// drop(sender);
+///
+/// assert_eq!(numbers, [1, 2, 4, 5]);
+/// assert_eq!(consumer.join().unwrap(), 9);
+/// ```
+pub fn retain_into<T, S: Sink<T> + ?Sized>(
+    vec: &mut Vec<T>,
+    mut keep: impl FnMut(&mut T) -> bool,
+    sink: &mut S,
+) {
+    let mut scan = VecMutScan::new(vec);
+    while let Some(mut item) = scan.next() {
+        if !keep(&mut item) {
+            sink.accept(item.remove());
+        }
+    }
+}
+
+/// Remove all items for which `remove` returns `true`, passing them to `sink` grouped by `key`.
+///
+/// Each removed item is passed to the sink together with its key, in order. With a map from keys
+/// to vectors as sink, this splits the removed items into groups, while the kept items stay in
+/// place.
+///
+/// ```
+/// # use vec_mut_scan::group_into;
+/// use std::collections::BTreeMap;
+///
+/// let mut events = vec![(1, "spawn"), (2, "spawn"), (0, "tick"), (1, "move"), (2, "despawn")];
+/// let mut by_entity = BTreeMap::new();
+/// group_into(&mut events, |event| event.0 != 0, |event| event.0, &mut by_entity);
+///
+/// assert_eq!(events, [(0, "tick")]);
+/// assert_eq!(by_entity[&1], [(1, "spawn"), (1, "move")]);
+/// assert_eq!(by_entity[&2], [(2, "spawn"), (2, "despawn")]);
+/// ```
+pub fn group_into<T, K, S: Sink<(K, T)> + ?Sized>(
+    vec: &mut Vec<T>,
+    mut remove: impl FnMut(&T) -> bool,
+    mut key: impl FnMut(&T) -> K,
+    sink: &mut S,
+) {
+    let mut scan = VecMutScan::new(vec);
+    while let Some(item) = scan.next() {
+        if remove(&item) {
+            let item_key = key(&item);
+            sink.accept((item_key, item.remove()));
+        }
+    }
+}
+
+/// Retain only the items for which `keep` returns `true`, folding all other items into an
+/// accumulator.
+///
+/// Starting with `init`, every removed item is combined with the accumulator using `fold`, in order.
+/// Returns the final value of the accumulator. This avoids collecting removed items just to reduce
+/// them afterwards.
+///
+/// ```
+/// # use vec_mut_scan::retain_fold;
+/// let mut buffers = vec![vec![0u8; 10], vec![0u8; 200], vec![0u8; 30]];
+/// let freed = retain_fold(&mut buffers, 0, |b| b.len() < 100, |acc, b| acc + b.len());
+///
+/// assert_eq!(buffers.len(), 2);
+/// assert_eq!(freed, 200);
+/// ```
+pub fn retain_fold<T, B>(
+    vec: &mut Vec<T>,
+    init: B,
+    mut keep: impl FnMut(&mut T) -> bool,
+    mut fold: impl FnMut(B, T) -> B,
+) -> B {
+    let mut acc = init;
+    let mut scan = VecMutScan::new(vec);
+    while let Some(mut item) = scan.next() {
+        if !keep(&mut item) {
+            acc = fold(acc, item.remove());
+        }
+    }
+    acc
+}
+
+/// The result of the closure passed to [`partition_map`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartitionMap<T, U> {
+    /// Keep the given item in the vector.
+    Keep(T),
+    /// Remove the item, passing the given value to the sink.
+    Extract(U),
+}
+
+/// Remove items while converting them into a different type, passing the converted values to
+/// `sink`.
+///
+/// The closure takes ownership of each item and either returns it, possibly modified, to keep it,
+/// or converts it into a value for the sink. This avoids a second pass over the removed items to
+/// convert them.
+///
+/// ```
+/// # use vec_mut_scan::{partition_map, PartitionMap};
+/// let mut inputs = vec!["1", "x", "22", "", "3"];
+/// let mut numbers = vec![];
+/// partition_map(&mut inputs, &mut numbers, |s| match s.parse::<i32>() {
+///     Ok(n) => PartitionMap::Extract(n),
+///     Err(_) => PartitionMap::Keep(s),
+/// });
+///
+/// assert_eq!(inputs, ["x", ""]);
+/// assert_eq!(numbers, [1, 22, 3]);
+/// ```
+pub fn partition_map<T, U, S: Sink<U> + ?Sized>(
+    vec: &mut Vec<T>,
+    sink: &mut S,
+    mut f: impl FnMut(T) -> PartitionMap<T, U>,
+) {
+    let mut scan = VecGrowScan::new(vec);
+    loop {
+        let item = match scan.next() {
+            Some(item) => item.remove(),
+            None => break,
+        };
+        // Kept items are inserted into the gap left by the removal, so they are not moved again.
+        match f(item) {
+            PartitionMap::Keep(item) => {
+                scan.insert(item);
+            }
+            PartitionMap::Extract(value) => sink.accept(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{
+        string::{String, ToString},
+        vec,
+    };
+
+    #[test]
+    fn into_vec() {
+        let mut input: Vec<_> = (0..10).collect();
+        let mut removed = vec![];
+
+        retain_into(&mut input, |x| *x % 4 != 0, &mut removed);
+
+        assert_eq!(input, [1, 2, 3, 5, 6, 7, 9]);
+        assert_eq!(removed, [0, 4, 8]);
+    }
+
+    #[test]
+    fn fold_removed() {
+        let mut input: Vec<_> = (0..10).collect();
+
+        let max = retain_fold(&mut input, None, |x| *x % 4 != 0, |acc, x| acc.max(Some(x)));
+
+        assert_eq!(input, [1, 2, 3, 5, 6, 7, 9]);
+        assert_eq!(max, Some(8));
+    }
+
+    #[test]
+    fn grouped() {
+        let mut input: Vec<_> = (0..10).collect();
+        let mut groups = BTreeMap::new();
+
+        group_into(&mut input, |x| *x % 4 != 0, |x| *x % 3, &mut groups);
+
+        assert_eq!(input, [0, 4, 8]);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[&0], [3, 6, 9]);
+        assert_eq!(groups[&1], [1, 7]);
+        assert_eq!(groups[&2], [2, 5]);
+
+        #[cfg(feature = "std")]
+        {
+            let mut groups = HashMap::new();
+            group_into(&mut input, |x| *x != 4, |x| *x > 4, &mut groups);
+
+            assert_eq!(input, [4]);
+            assert_eq!(groups[&false], [0]);
+            assert_eq!(groups[&true], [8]);
+        }
+    }
+
+    #[test]
+    fn mapped() {
+        let mut input: Vec<_> = (0..10).collect();
+        let mut removed: Vec<String> = vec![];
+
+        partition_map(&mut input, &mut removed, |x| {
+            if x % 3 == 0 {
+                PartitionMap::Extract(x.to_string())
+            } else {
+                PartitionMap::Keep(x * 10)
+            }
+        });
+
+        assert_eq!(input, [10, 20, 40, 50, 70, 80]);
+        assert_eq!(removed, ["0", "3", "6", "9"]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn into_channel() {
+        let mut input: Vec<_> = (0..10).collect();
+        let (mut sender, receiver) = std::sync::mpsc::channel();
+
+        retain_into(&mut input, |x| *x % 4 != 0, &mut sender);
+        drop(sender);
+
+        assert_eq!(receiver.iter().collect::<Vec<_>>(), [0, 4, 8]);
+    }
+}
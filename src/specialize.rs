@@ -0,0 +1,88 @@
+//! Specialized implementations for `Copy` items, enabled by the `nightly` feature.
+use core::ptr;
+
+use crate::VecMutScan;
+
+pub(crate) trait SpecFoldRetain<T> {
+    fn spec_fold_retain<B, F: FnMut(B, &mut T) -> (B, bool)>(&mut self, init: B, f: F) -> B;
+}
+
+impl<'a, T: 'a> SpecFoldRetain<T> for VecMutScan<'a, T> {
+    default fn spec_fold_retain<B, F: FnMut(B, &mut T) -> (B, bool)>(
+        &mut self,
+        init: B,
+        f: F,
+    ) -> B {
+        self.fold_retain_generic(init, f)
+    }
+}
+
+impl<'a, T: Copy + 'a> SpecFoldRetain<T> for VecMutScan<'a, T> {
+    fn spec_fold_retain<B, F: FnMut(B, &mut T) -> (B, bool)>(&mut self, init: B, mut f: F) -> B {
+        // An invariant check has to run after every item.
+        #[cfg(debug_assertions)]
+        {
+            if self.check.is_some() {
+                return self.fold_retain_generic(init, f);
+            }
+        }
+
+        let base = self.base;
+        let end = self.end;
+        let mut positions = Positions {
+            write: self.write,
+            read: self.read,
+            scan: self,
+        };
+        let mut acc = init;
+
+        while positions.read != end {
+            let (next_acc, keep) = f(acc, unsafe { &mut *base.add(positions.read) });
+            acc = next_acc;
+            // Removed items have no drop glue and the destination is within the gap or the item
+            // itself, so every item can be copied, whether kept or not.
+            unsafe { ptr::copy(base.add(positions.read), base.add(positions.write), 1) };
+            positions.write += keep as usize;
+            positions.read += 1;
+        }
+
+        acc
+    }
+}
+
+/// Positions of a scan, which are stored back into the scan when dropped, also when unwinding.
+struct Positions<'s, 'a, T: 'a> {
+    scan: &'s mut VecMutScan<'a, T>,
+    write: usize,
+    read: usize,
+}
+
+impl<'s, 'a, T: 'a> Drop for Positions<'s, 'a, T> {
+    fn drop(&mut self) {
+        self.scan.write = self.write;
+        self.scan.read = self.read;
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[test]
+    fn copy_fold_retain_unwind() {
+        let mut input: Vec<u64> = (0..10).collect();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mut scan = VecMutScan::new(&mut input);
+            scan.retain_rest(|x| {
+                assert!(*x != 6);
+                *x % 2 == 0
+            });
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(input, [0, 2, 4, 6, 7, 8, 9]);
+    }
+}
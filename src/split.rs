@@ -0,0 +1,191 @@
+use alloc::vec::Vec;
+use core::ptr;
+
+use crate::VecMutScan;
+
+impl<'a, T: 'a> VecMutScan<'a, T> {
+    /// Split the remaining items into `n` independent scans, e.g. for processing them in parallel.
+    ///
+    /// The items that were not visited yet are divided into `n` consecutive parts of (almost) equal
+    /// length. The returned [`SplitScans`] provides a separate [`VecMutScan`] for each part (see
+    /// [`SplitScans::scans`]). As these scans can be sent to other threads, this allows parallel
+    /// processing using scoped threads. When the `SplitScans` is dropped, the items kept by the
+    /// individual scans are moved to close the gaps between the parts, each item being moved at
+    /// most once more.
+    ///
+    /// After the `SplitScans` is dropped, all items of this scan are considered visited.
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// let mut numbers: Vec<_> = (0..100).collect();
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    /// let mut split = scan.split_into_scans(4);
+    ///
+    /// std::thread::scope(|s| {
+    ///     for mut part in split.scans() {
+    ///         s.spawn(move || {
+    ///             while let Some(item) = part.next() {
+    ///                 if *item % 3 != 0 {
+    ///                     item.remove();
+    ///                 }
+    ///             }
+    ///         });
+    ///     }
+    /// });
+    ///
+    /// drop(split);
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, (0..100).step_by(3).collect::<Vec<_>>());
+    /// ```
+    pub fn split_into_scans(&mut self, n: usize) -> SplitScans<'_, 'a, T> {
+        assert!(n > 0, "cannot split into zero scans");
+
+        let start = self.read;
+        let len = self.end - self.read;
+
+        // Make sure `self` is in a consistent state should the `SplitScans` be leaked, by handing
+        // the ownership of the remaining items over to it.
+        self.read = self.end;
+
+        let mut parts = Vec::with_capacity(n);
+        let mut part_start = start;
+        for i in 0..n {
+            let part_len = len / n + (i < len % n) as usize;
+            parts.push(Part {
+                start: part_start,
+                len: part_len,
+            });
+            part_start += part_len;
+        }
+
+        SplitScans { scan: self, parts }
+    }
+}
+
+/// Independent scans over consecutive parts of a [`VecMutScan`]'s remaining items.
+///
+/// See [`VecMutScan::split_into_scans`].
+pub struct SplitScans<'s, 'a, T: 'a> {
+    scan: &'s mut VecMutScan<'a, T>,
+    parts: Vec<Part>,
+}
+
+struct Part {
+    start: usize,
+    len: usize,
+}
+
+// invariant: each part's `start..start + len` contains valid data owned by the part and the parts
+// are ordered by `start` and lie between `scan.write` and `scan.end`.
+
+impl<'s, 'a, T: 'a> SplitScans<'s, 'a, T> {
+    /// Begin one scan per part.
+    ///
+    /// Dropping these scans mid-iteration keeps the remaining items of the corresponding part.
+    /// This can be called multiple times, each time starting new scans over the items kept so far.
+    pub fn scans(&mut self) -> Vec<VecMutScan<'_, T>> {
+        let base = self.scan.base;
+        self.parts
+            .iter_mut()
+            .map(|part| unsafe {
+                let end = part.len;
+                // By invariant the part owns the valid items `start..start + len`. The new scan
+                // takes over this ownership and stores the number of kept items back into `len`.
+                VecMutScan::from_raw_parts(&mut part.len, base.add(part.start), 0, end)
+            })
+            .collect()
+    }
+
+    /// The number of items currently contained in each part.
+    pub fn part_lens(&self) -> impl Iterator<Item = usize> + '_ {
+        self.parts.iter().map(|part| part.len)
+    }
+}
+
+impl<'s, 'a, T: 'a> Drop for SplitScans<'s, 'a, T> {
+    fn drop(&mut self) {
+        // Move the items of all parts to the start of the gap of the scan we split, one part after
+        // another.
+        let base = self.scan.base;
+        let mut write = self.scan.write;
+        for part in self.parts.iter() {
+            unsafe {
+                // As the parts are ordered and lie after `scan.write`, `write` never exceeds
+                // `part.start`, making this copy stay within the buffer. This is required to handle
+                // overlapping copies.
+                ptr::copy(base.add(part.start), base.add(write), part.len);
+            }
+            write += part.len;
+        }
+        // The scan's `read` was set to `end` when splitting, and `0..write` now contains valid
+        // data owned by the scan.
+        self.scan.write = write;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec};
+
+    #[test]
+    fn split_after_prefix() {
+        let mut input: Vec<_> = (0..10).map(Rc::new).collect();
+        let input_copy = input.clone();
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next().unwrap().remove();
+        scan.next();
+
+        let mut split = scan.split_into_scans(3);
+        assert_eq!(split.part_lens().collect::<Vec<_>>(), [3, 3, 2]);
+
+        for (i, mut part) in split.scans().into_iter().enumerate() {
+            let mut first = true;
+            while let Some(item) = part.next() {
+                if first || i == 2 {
+                    item.remove();
+                }
+                first = false;
+            }
+        }
+        assert_eq!(split.part_lens().collect::<Vec<_>>(), [2, 2, 0]);
+
+        for mut part in split.scans() {
+            if let Some(item) = part.next() {
+                item.replace(Rc::new(100));
+            }
+        }
+
+        drop(split);
+        assert!(scan.next().is_none());
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|rc| **rc).collect();
+        assert_eq!(values, [1, 100, 4, 100, 7]);
+
+        let ref_counts: Vec<_> = input_copy.iter().map(Rc::strong_count).collect();
+        assert_eq!(ref_counts, [1, 2, 1, 1, 2, 1, 1, 2, 1, 1]);
+    }
+
+    #[test]
+    fn more_parts_than_items() {
+        let mut input = vec![1, 2];
+
+        let mut scan = VecMutScan::new(&mut input);
+        let mut split = scan.split_into_scans(4);
+        for mut part in split.scans() {
+            if let Some(mut item) = part.next() {
+                *item *= 10;
+            }
+        }
+        drop(split);
+        drop(scan);
+
+        assert_eq!(input, [10, 20]);
+    }
+}
@@ -0,0 +1,228 @@
+use alloc::string::String;
+use core::{mem::ManuallyDrop, ops::Deref, ptr, str};
+
+use crate::{InsertStrategy, VecGrowScan};
+
+/// Forward scan over the chars of a string with removal, replacement and insertion.
+///
+/// The string is edited in place and is valid UTF-8 again when the scan is dropped. Text is
+/// inserted before the chars that haven't been visited yet. Inserted text that doesn't fit into
+/// the room left by removed chars makes the scan move the remaining text back at once, instead of
+/// moving it a byte at a time (see [`InsertStrategy::Auto`]), and runs of kept text are moved over
+/// that room at once by [`keep_until`][StringGrowScan::keep_until].
+///
+/// ```
+/// # use vec_mut_scan::StringGrowScan;
+/// // Expand `{{name}}` placeholders.
+/// let mut text = String::from("Hello, {{name}}! Bye, {{name}}.");
+/// let mut scan = StringGrowScan::new(&mut text);
+///
+/// while scan.keep_until("{{") {
+///     let mut name = String::new();
+///     while let Some(c) = scan.next() {
+///         name.push(c.remove());
+///         if name.ends_with("}}") {
+///             break;
+///         }
+///     }
+///     assert_eq!(name, "{{name}}");
+///     scan.insert_str("Ferris");
+/// }
+/// drop(scan);
+///
+/// assert_eq!(text, "Hello, Ferris! Bye, Ferris.");
+/// ```
+pub struct StringGrowScan<'a> {
+    scan: VecGrowScan<'a, u8>,
+}
+
+impl<'a> StringGrowScan<'a> {
+    /// Begin a scan over the chars of a string with removal, replacement and insertion.
+    pub fn new(string: &'a mut String) -> Self {
+        // The scan only ever removes complete chars and inserts complete strings, so the bytes are
+        // valid UTF-8 whenever the scan is dropped. If the scan is leaked, the string is left
+        // empty.
+        let mut scan = VecGrowScan::new(unsafe { string.as_mut_vec() });
+        scan.set_insert_strategy(InsertStrategy::Auto);
+        StringGrowScan { scan }
+    }
+
+    /// Advance to the next char of the string.
+    ///
+    /// This returns a reference wrapper that enables removal and replacement (see
+    /// [`StringGrowScanItem`]).
+    #[allow(clippy::should_implement_trait)] // can't be an iterator due to lifetimes
+    pub fn next<'s>(&'s mut self) -> Option<StringGrowScanItem<'s, 'a>> {
+        let c = self.remaining().chars().next()?;
+        Some(StringGrowScanItem { scan: self, c })
+    }
+
+    /// The part of the string that was not visited yet.
+    pub fn remaining(&self) -> &str {
+        // The remaining bytes were not modified and start at a char boundary.
+        unsafe { str::from_utf8_unchecked(self.scan.slices().3) }
+    }
+
+    /// Insert a char between the visited text and the text that wasn't visited yet. Inserted text
+    /// is not returned during iteration.
+    pub fn insert_char(&mut self, c: char) {
+        self.insert_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Insert text between the visited text and the text that wasn't visited yet. Inserted text is
+    /// not returned during iteration.
+    pub fn insert_str(&mut self, text: &str) {
+        self.scan.insert_many(text.bytes());
+    }
+
+    /// Keep all text up to the next occurrence of `pattern`, so that the following
+    /// [`next`][StringGrowScan::next] call returns the pattern's first char.
+    ///
+    /// Returns `false` and keeps all remaining text if the pattern does not occur.
+    pub fn keep_until(&mut self, pattern: &str) -> bool {
+        let remaining = self.remaining();
+        let (len, found) = match remaining.find(pattern) {
+            Some(index) => (index, true),
+            None => (remaining.len(), false),
+        };
+        self.keep_bytes(len);
+        found
+    }
+
+    /// Keep the next `len` bytes, moving them at once if possible.
+    fn keep_bytes(&mut self, len: usize) {
+        let scan = &mut self.scan;
+        if scan.queue.is_empty() {
+            let start = scan.read;
+            unsafe {
+                // Without queued items, the bytes only need to be moved over the gap, if any.
+                if scan.write < scan.read {
+                    ptr::copy(scan.base.add(scan.read), scan.base.add(scan.write), len);
+                }
+            }
+            scan.read += len;
+            scan.write += len;
+            scan.run_check("keeping items", start);
+        } else {
+            // Each kept byte makes room for a queued byte, which has to be done bytewise.
+            for _ in 0..len {
+                scan.next();
+            }
+        }
+    }
+
+    /// Moves past the next `len` bytes, removing them.
+    fn remove_bytes(&mut self, len: usize) {
+        for _ in 0..len {
+            self.scan.next().unwrap().remove();
+        }
+    }
+}
+
+/// Reference wrapper that enables removal and replacement of chars for [`StringGrowScan`].
+///
+/// Dereferences to the char. Dropping the wrapper keeps the char.
+pub struct StringGrowScanItem<'s, 'a> {
+    scan: &'s mut StringGrowScan<'a>,
+    c: char,
+}
+
+impl<'s, 'a> StringGrowScanItem<'s, 'a> {
+    fn into_inner_forget(self) -> (&'s mut StringGrowScan<'a>, char) {
+        let item = ManuallyDrop::new(self);
+        // The reference is moved out of `item`, which is never used or dropped afterwards.
+        (unsafe { ptr::read(&item.scan) }, item.c)
+    }
+
+    /// Removes and returns this char from the string.
+    pub fn remove(self) -> char {
+        let (scan, c) = self.into_inner_forget();
+        scan.remove_bytes(c.len_utf8());
+        c
+    }
+
+    /// Replaces this char with another char, returns the old char.
+    pub fn replace(self, value: char) -> char {
+        self.replace_with_str(value.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Replaces this char with a string, returns the old char.
+    ///
+    /// The new string is not visited by the scan.
+    pub fn replace_with_str(self, value: &str) -> char {
+        let (scan, c) = self.into_inner_forget();
+        scan.remove_bytes(c.len_utf8());
+        scan.insert_str(value);
+        c
+    }
+}
+
+impl<'s, 'a> Deref for StringGrowScanItem<'s, 'a> {
+    type Target = char;
+
+    fn deref(&self) -> &char {
+        &self.c
+    }
+}
+
+impl<'s, 'a> Drop for StringGrowScanItem<'s, 'a> {
+    fn drop(&mut self) {
+        self.scan.keep_bytes(self.c.len_utf8());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    #[test]
+    fn char_edits() {
+        let mut text = String::from("aé🦀b-c-d-e");
+        let mut seen = Vec::new();
+
+        let mut scan = StringGrowScan::new(&mut text);
+        scan.insert_char('>');
+        loop {
+            let c = match scan.next() {
+                Some(c) => c,
+                None => break,
+            };
+            seen.push(*c);
+            match *c {
+                'a' => assert_eq!(c.replace('ä'), 'a'),
+                'é' => assert_eq!(c.remove(), 'é'),
+                '🦀' => assert_eq!(c.replace_with_str("crab"), '🦀'),
+                'b' => {
+                    drop(c);
+                    assert!(scan.keep_until("d"));
+                    assert_eq!(scan.remaining(), "d-e");
+                    scan.insert_str("ü");
+                }
+                _ => (),
+            }
+        }
+        assert!(!scan.keep_until("x"));
+        drop(scan);
+
+        assert_eq!(seen, ['a', 'é', '🦀', 'b', 'd', '-', 'e']);
+        assert_eq!(text, ">äcrabb-c-üd-e");
+    }
+
+    #[test]
+    fn keep_with_queue() {
+        let mut text = String::from("ab{x}cdefgh");
+
+        let mut scan = StringGrowScan::new(&mut text);
+        scan.insert_str("12");
+        assert!(scan.keep_until("{"));
+        scan.next().unwrap().remove();
+        scan.next().unwrap().replace_with_str("long value");
+        scan.next().unwrap().remove();
+        assert!(!scan.keep_until("{"));
+        drop(scan);
+
+        assert_eq!(text, "12ablong valuecdefgh");
+    }
+}
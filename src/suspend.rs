@@ -0,0 +1,235 @@
+use alloc::vec::Vec;
+
+use crate::{ScanError, VecGrowScan, VecMutScan};
+
+/// The state of a suspended scan, which can be resumed later on the same vector.
+///
+/// Suspending a scan releases the borrow of the vector, so that the scan can be continued across
+/// the boundary of e.g. an event loop iteration. While suspended, the vector contains the visited
+/// and inserted items, followed by the remaining items, followed by the items appended or parked
+/// during the scan, just like after dropping the scan. It can be read as usual, and the items can be mutated
+/// in place, but it must not be resized before resuming the scan.
+///
+/// When resuming, the vector's length is checked against the length at the time of suspension. If
+/// it differs, [`ScanError::InvalidPosition`] is returned.
+///
+/// ```
+/// # use vec_mut_scan::{SuspendedScan, VecGrowScan};
+/// let mut numbers = vec![1, 2, 3, 4];
+///
+/// let mut scan = VecGrowScan::new(&mut numbers);
+/// scan.next().unwrap().replace_with_many(vec![10, 11]);
+/// scan.append(40);
+/// let suspended = scan.suspend();
+///
+/// assert_eq!(numbers, [10, 11, 2, 3, 4, 40]);
+///
+/// let mut scan = suspended.resume(&mut numbers).unwrap();
+/// assert_eq!(*scan.next().unwrap(), 2);
+/// drop(scan);
+/// ```
+#[derive(Debug)]
+pub struct SuspendedScan {
+    position: usize,
+    len: usize,
+    appended: usize,
+}
+
+impl SuspendedScan {
+    /// The index of the next item to visit.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Resume the scan as a [`VecGrowScan`].
+    ///
+    /// Fails if the vector's length changed since the scan was suspended.
+    pub fn resume<T>(self, vec: &mut Vec<T>) -> Result<VecGrowScan<'_, T>, ScanError> {
+        self.check_len(vec.len())?;
+        let tail = vec.split_off(self.len - self.appended);
+        let mut scan = VecGrowScan::start_at(vec, self.position);
+        scan.tail = tail;
+        Ok(scan)
+    }
+
+    /// Resume the scan as a [`VecMutScan`].
+    ///
+    /// Fails if the vector's length changed since the scan was suspended. Returns
+    /// [`ScanError::AppendedItems`] if items were appended to or parked by the suspended scan, as a
+    /// `VecMutScan` cannot keep them at the end of the vector.
+    pub fn resume_mut<T>(self, vec: &mut Vec<T>) -> Result<VecMutScan<'_, T>, ScanError> {
+        self.check_len(vec.len())?;
+        if self.appended != 0 {
+            return Err(ScanError::AppendedItems);
+        }
+        Ok(VecMutScan::start_at(vec, self.position))
+    }
+
+    fn check_len(&self, len: usize) -> Result<(), ScanError> {
+        if len == self.len {
+            Ok(())
+        } else {
+            Err(ScanError::InvalidPosition)
+        }
+    }
+}
+
+impl<'a, T: 'a> VecMutScan<'a, T> {
+    /// Suspend the scan, releasing the vector.
+    ///
+    /// See [`SuspendedScan`]. Kept items visited using [`next_back`][VecMutScan::next_back] follow
+    /// the remaining items in the vector, so a resumed scan visits them again.
+    pub fn suspend(self) -> SuspendedScan {
+        let suspended = SuspendedScan {
+            position: self.write,
+            len: self.write + (self.len - self.read),
+            appended: 0,
+        };
+        drop(self);
+        suspended
+    }
+}
+
+impl<'a, T: 'a> VecGrowScan<'a, T> {
+    /// Suspend the scan, releasing the vector.
+    ///
+    /// See [`SuspendedScan`].
+    pub fn suspend(mut self) -> SuspendedScan {
+        // Items of the insert source are inserted when the scan is dropped, so they precede the
+        // next item to visit.
+        if let Some(source) = self.source.take() {
+            self.insert_many_inner(source);
+        }
+        let position = self.write + self.queue.len();
+        // Parked items are placed after the appended items and are kept at the end of the vector
+        // the same way.
+        let appended = self.tail.len() + self.parked.len();
+        let suspended = SuspendedScan {
+            position,
+            len: position + (self.end - self.read) + appended,
+            appended,
+        };
+        drop(self);
+        suspended
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn suspend_resume() {
+        let mut input: Vec<_> = (0..6).collect();
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next().unwrap().remove();
+        scan.next();
+        let suspended = scan.suspend();
+        assert_eq!(suspended.position(), 1);
+        assert_eq!(input, [1, 2, 3, 4, 5]);
+
+        input[1] = 20;
+        let mut scan = suspended.resume(&mut input).unwrap();
+        scan.next().unwrap().remove();
+        scan.insert(21);
+        scan.append(30);
+        let suspended = scan.suspend();
+        assert_eq!(suspended.position(), 2);
+        assert_eq!(input, [1, 21, 3, 4, 5, 30]);
+
+        let mut scan = suspended.resume(&mut input).unwrap();
+        assert_eq!(*scan.next().unwrap(), 3);
+        scan.insert(31);
+        let suspended = scan.suspend();
+        assert_eq!(suspended.position(), 4);
+        assert_eq!(input, [1, 21, 3, 31, 4, 5, 30]);
+
+        let mut scan = suspended.resume(&mut input).unwrap();
+        while let Some(item) = scan.next() {
+            item.remove();
+        }
+        drop(scan);
+        assert_eq!(input, [1, 21, 3, 31, 30]);
+    }
+
+    #[test]
+    fn resized() {
+        let mut input = vec![1, 2, 3];
+        let suspended = VecMutScan::new(&mut input).suspend();
+        input.push(4);
+        assert!(suspended.resume_mut(&mut input).is_err());
+    }
+
+    #[test]
+    fn resume_mut_appended() {
+        let mut input = vec![1, 2, 3];
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.append(4);
+        let suspended = scan.suspend();
+
+        assert_eq!(
+            suspended.resume_mut(&mut input).err(),
+            Some(ScanError::AppendedItems)
+        );
+        assert_eq!(input, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn suspend_parked() {
+        let mut input: Vec<_> = (1..5).collect();
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next().unwrap().park();
+        scan.append(10);
+        let suspended = scan.suspend();
+        assert_eq!(suspended.position(), 0);
+        assert_eq!(input, [2, 3, 4, 10, 1]);
+
+        let mut scan = suspended.resume(&mut input).unwrap();
+        assert_eq!(*scan.next().unwrap(), 2);
+        scan.next().unwrap().remove();
+        assert!(scan.logical_eq(&[2, 4, 10, 1]));
+        drop(scan);
+        assert_eq!(input, [2, 4, 10, 1]);
+    }
+
+    #[test]
+    fn suspend_with_insert_source() {
+        let mut input: Vec<_> = (1..5).collect();
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next();
+        scan.set_insert_source(10..12);
+        let suspended = scan.suspend();
+        assert_eq!(suspended.position(), 3);
+        assert_eq!(input, [1, 10, 11, 2, 3, 4]);
+
+        let mut scan = suspended.resume(&mut input).unwrap();
+        assert_eq!(*scan.next().unwrap(), 2);
+        drop(scan);
+        assert_eq!(input, [1, 10, 11, 2, 3, 4]);
+    }
+
+    #[test]
+    fn suspend_after_next_back() {
+        let mut input: Vec<_> = (1..7).collect();
+
+        let mut scan = VecMutScan::new(&mut input);
+        scan.next();
+        scan.next_back().unwrap().remove();
+        scan.next_back();
+        let suspended = scan.suspend();
+        assert_eq!(suspended.position(), 1);
+        assert_eq!(input, [1, 2, 3, 4, 5]);
+
+        let mut scan = suspended.resume_mut(&mut input).unwrap();
+        let mut seen = vec![];
+        while let Some(item) = scan.next() {
+            seen.push(*item);
+        }
+        assert_eq!(seen, [2, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,120 @@
+use alloc::vec::Vec;
+
+use crate::VecGrowScan;
+
+/// Update a vector in place to be equal to `target`, reusing matching items.
+///
+/// This is [`sync_to_with`] for vectors of cloneable items, cloning the items of `target` that
+/// need to be inserted.
+///
+/// ```
+/// # use vec_mut_scan::sync_to;
+/// let mut rows = vec!["a", "b", "c", "d"];
+/// sync_to(&mut rows, &["a", "c", "x", "d", "e"], |x, y| x == y);
+///
+/// assert_eq!(rows, ["a", "c", "x", "d", "e"]);
+/// ```
+pub fn sync_to<T: Clone>(vec: &mut Vec<T>, target: &[T], eq: impl FnMut(&T, &T) -> bool) {
+    sync_to_with(vec, target, eq, T::clone)
+}
+
+/// Update a vector in place to match `target`, reusing matching items and creating missing ones
+/// using `create`.
+///
+/// Both sequences are walked in a single pass. Items of the vector that match (according to `eq`)
+/// a not yet matched item of `target` are kept, while the target items skipped over are inserted
+/// in front of them. Items that match no later target item are replaced, when the next target
+/// item matches none of the remaining items, and removed otherwise. Kept items are never
+/// reconstructed, so this is suitable for items that are expensive to create or own resources,
+/// as in a retained mode UI.
+///
+/// Matches are chosen greedily, so the number of operations is not always minimal. Finding a
+/// match may search all remaining items of both sequences, which is quadratic in the worst case
+/// but linear when the sequences only differ locally.
+///
+/// ```
+/// # use vec_mut_scan::sync_to_with;
+/// struct Widget {
+///     id: u32,
+///     generation: u32,
+/// }
+///
+/// let mut widgets: Vec<_> = (0..4).map(|id| Widget { id, generation: 0 }).collect();
+/// let create = |&id: &u32| Widget { id, generation: 1 };
+/// sync_to_with(&mut widgets, &[0, 2, 5, 3], |w, &id| w.id == id, create);
+///
+/// let state: Vec<_> = widgets.iter().map(|w| (w.id, w.generation)).collect();
+/// assert_eq!(state, [(0, 0), (2, 0), (5, 1), (3, 0)]);
+/// ```
+pub fn sync_to_with<T, U>(
+    vec: &mut Vec<T>,
+    target: &[U],
+    mut eq: impl FnMut(&T, &U) -> bool,
+    mut create: impl FnMut(&U) -> T,
+) {
+    let mut next = 0;
+    let mut scan = VecGrowScan::new(vec);
+    while let Some(mut item) = scan.next() {
+        let pending = &target[next..];
+        if let Some(offset) = pending.iter().position(|value| eq(&*item, value)) {
+            item.insert_many_before(pending[..offset].iter().map(&mut create));
+            next += offset + 1;
+        } else if !pending.is_empty()
+            && !item.slices().3[1..]
+                .iter()
+                .any(|remaining| eq(remaining, &pending[0]))
+        {
+            item.replace(create(&pending[0]));
+            next += 1;
+        } else {
+            item.remove();
+        }
+    }
+    scan.insert_many(target[next..].iter().map(create));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec};
+
+    #[test]
+    fn reuse_items() {
+        let targets: &[&[i32]] = &[
+            &[],
+            &[1, 2, 3, 4, 5],
+            &[5, 4, 3, 2, 1],
+            &[0, 1, 9, 3, 9, 5, 6],
+            &[2, 2, 2],
+        ];
+
+        for &start in targets {
+            for &target in targets {
+                let mut items: Vec<_> = start.iter().map(|&x| Rc::new(x)).collect();
+                let originals = items.clone();
+
+                sync_to_with(&mut items, target, |x, y| **x == *y, |&y| Rc::new(y));
+
+                let values: Vec<_> = items.iter().map(|x| **x).collect();
+                assert_eq!(values, target);
+                assert!(originals.iter().all(|x| Rc::strong_count(x) <= 2));
+            }
+        }
+
+        let originals: Vec<_> = (1..6).map(Rc::new).collect();
+        let mut items = originals.clone();
+        let target: Vec<_> = [0, 1, 9, 3, 9, 5, 6].iter().map(|&x| Rc::new(x)).collect();
+        sync_to(&mut items, &target, |x, y| x == y);
+        let reused: Vec<_> = items
+            .iter()
+            .filter(|x| originals.iter().any(|y| Rc::ptr_eq(x, y)))
+            .map(|x| **x)
+            .collect();
+        assert_eq!(reused, [1, 3, 5]);
+
+        let mut input = vec![1, 2, 3, 4];
+        sync_to(&mut input, &[1, 3, 5], |x, y| x == y);
+        assert_eq!(input, [1, 3, 5]);
+    }
+}
@@ -0,0 +1,170 @@
+//! Utilities for testing code built on top of this crate.
+//!
+//! This module provides [`ScanOp`], a single operation of a scan using [`VecGrowScan`], which
+//! implements [`Arbitrary`] for use with fuzzers. A sequence of operations can be replayed using
+//! the real scan ([`replay`]) or using a straightforward reference model ([`replay_model`]), and
+//! [`check`] asserts that both agree. Code wrapping the scans can use the same operations to
+//! compare itself against the model.
+//!
+//! Requires the `test-util` feature.
+//!
+//! ```
+//! # use vec_mut_scan::testing::{check, ScanOp};
+//! let ops = [ScanOp::Remove, ScanOp::Insert(9), ScanOp::InsertAfter(7), ScanOp::Append(5)];
+//! let result = check(&[1, 2, 3], &ops);
+//!
+//! assert_eq!(result, [9, 2, 7, 3, 5]);
+//! ```
+use alloc::{collections::VecDeque, vec::Vec};
+use arbitrary::{Arbitrary, Unstructured};
+use core::fmt::Debug;
+
+use crate::VecGrowScan;
+
+/// A single operation of a scan.
+///
+/// Operations that act on an item first advance to the next item. If there are no remaining items,
+/// they do nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanOp<T> {
+    /// Advance to the next item and keep it.
+    Keep,
+    /// Advance to the next item and remove it.
+    Remove,
+    /// Advance to the next item and replace it.
+    Replace(T),
+    /// Advance to the next item, insert a new item before it and keep it.
+    InsertBefore(T),
+    /// Advance to the next item, keep it and insert a new item after it.
+    InsertAfter(T),
+    /// Insert a new item before the remaining items, see [`VecGrowScan::insert`].
+    Insert(T),
+    /// Insert a new item after the remaining items, see [`VecGrowScan::append`].
+    Append(T),
+    /// End the scan, keeping all remaining items. Following operations are ignored.
+    Stop,
+}
+
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for ScanOp<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=7u8)? {
+            0 => ScanOp::Keep,
+            1 => ScanOp::Remove,
+            2 => ScanOp::Replace(T::arbitrary(u)?),
+            3 => ScanOp::InsertBefore(T::arbitrary(u)?),
+            4 => ScanOp::InsertAfter(T::arbitrary(u)?),
+            5 => ScanOp::Insert(T::arbitrary(u)?),
+            6 => ScanOp::Append(T::arbitrary(u)?),
+            _ => ScanOp::Stop,
+        })
+    }
+}
+
+/// Replay a sequence of operations on `vec` using a [`VecGrowScan`].
+pub fn replay<T: Clone>(vec: &mut Vec<T>, ops: &[ScanOp<T>]) {
+    let mut scan = VecGrowScan::new(vec);
+    for op in ops {
+        match op {
+            ScanOp::Insert(value) => {
+                scan.insert(value.clone());
+            }
+            ScanOp::Append(value) => {
+                scan.append(value.clone());
+            }
+            ScanOp::Stop => break,
+            _ => {
+                let mut item = match scan.next() {
+                    Some(item) => item,
+                    None => continue,
+                };
+                match op {
+                    ScanOp::Remove => drop(item.remove()),
+                    ScanOp::Replace(value) => drop(item.replace(value.clone())),
+                    ScanOp::InsertBefore(value) => {
+                        item.insert_before(value.clone());
+                    }
+                    ScanOp::InsertAfter(value) => {
+                        item.insert_after(value.clone());
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+/// Replay a sequence of operations on `vec` using a reference model of [`VecGrowScan`].
+///
+/// The model keeps the visited items, the remaining items and the appended items in separate
+/// collections, which are concatenated at the end.
+pub fn replay_model<T: Clone>(vec: &mut Vec<T>, ops: &[ScanOp<T>]) {
+    let mut visited = Vec::new();
+    let mut remaining: VecDeque<T> = vec.drain(..).collect();
+    let mut appended = Vec::new();
+
+    for op in ops {
+        match op {
+            ScanOp::Insert(value) => visited.push(value.clone()),
+            ScanOp::Append(value) => appended.push(value.clone()),
+            ScanOp::Stop => break,
+            _ => {
+                let item = match remaining.pop_front() {
+                    Some(item) => item,
+                    None => continue,
+                };
+                match op {
+                    ScanOp::Remove => (),
+                    ScanOp::Replace(value) => visited.push(value.clone()),
+                    ScanOp::InsertBefore(value) => {
+                        visited.push(value.clone());
+                        visited.push(item);
+                    }
+                    ScanOp::InsertAfter(value) => {
+                        visited.push(item);
+                        visited.push(value.clone());
+                    }
+                    _ => visited.push(item),
+                }
+            }
+        }
+    }
+
+    vec.extend(visited);
+    vec.extend(remaining);
+    vec.extend(appended);
+}
+
+/// Replay a sequence of operations on `items` using both [`replay`] and [`replay_model`].
+///
+/// Panics if the results differ, otherwise returns the result.
+pub fn check<T: Clone + PartialEq + Debug>(items: &[T], ops: &[ScanOp<T>]) -> Vec<T> {
+    let mut actual = items.to_vec();
+    replay(&mut actual, ops);
+    let mut expected = items.to_vec();
+    replay_model(&mut expected, ops);
+    assert_eq!(actual, expected, "scan and model disagree");
+    actual
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arbitrary_ops() {
+        let mut state = 1u32;
+        let data: Vec<u8> = (0..100_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (state >> 16) as u8
+            })
+            .collect();
+        let mut u = Unstructured::new(&data);
+
+        while !u.is_empty() {
+            let items = Vec::<u8>::arbitrary(&mut u).unwrap();
+            let ops = Vec::<ScanOp<u8>>::arbitrary(&mut u).unwrap();
+            check(&items, &ops);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use alloc::vec::Vec;
+use bytemuck::TransparentWrapper;
+
+use crate::VecMutScan;
+
+impl<'a, T: 'a> VecMutScan<'a, T> {
+    /// Begin a scan over a vector of transparent wrappers around `T`, visiting the wrapped items.
+    ///
+    /// This allows using scan based code written for `T` on vectors of newtypes implementing
+    /// [`TransparentWrapper<T>`]. Removed items are returned unwrapped, so the wrapper's `Drop`
+    /// impl, if any, is not run for them.
+    ///
+    /// Requires the `bytemuck` feature.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// use bytemuck::TransparentWrapper;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// #[repr(transparent)]
+    /// struct Meters(f64);
+    ///
+    /// unsafe impl TransparentWrapper<f64> for Meters {}
+    ///
+    /// fn drop_negative(scan: &mut VecMutScan<f64>) {
+    ///     while let Some(item) = scan.next() {
+    ///         if *item < 0.0 {
+    ///             item.remove();
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut distances = vec![Meters(1.5), Meters(-2.0), Meters(3.0)];
+    /// drop_negative(&mut VecMutScan::new_peeled(&mut distances));
+    ///
+    /// assert_eq!(distances, [Meters(1.5), Meters(3.0)]);
+    /// ```
+    pub fn new_peeled<W: TransparentWrapper<T>>(vec: &'a mut Vec<W>) -> Self {
+        let base = vec.as_mut_ptr() as *mut T;
+        let end = vec.len();
+
+        // `W` has the same layout as `T` and wrapping or unwrapping values is safe, so the items
+        // `0..end` are valid as `T` and can be owned by the vector of `W` again afterwards.
+        unsafe { VecMutScan::from_raw_parts(vec, base, 0, end) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec};
+
+    #[repr(transparent)]
+    struct Wrapper(Rc<i32>);
+
+    unsafe impl TransparentWrapper<Rc<i32>> for Wrapper {}
+
+    #[test]
+    fn peeled_items() {
+        let item = Rc::new(1);
+        let mut input = vec![
+            Wrapper(item.clone()),
+            Wrapper(Rc::new(2)),
+            Wrapper(item.clone()),
+        ];
+
+        let mut scan = VecMutScan::new_peeled(&mut input);
+        scan.next();
+        assert_eq!(*scan.next().unwrap().remove(), 2);
+        *scan.next().unwrap() = Rc::new(3);
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| *x.0).collect();
+        assert_eq!(values, [1, 3]);
+        assert_eq!(Rc::strong_count(&item), 2);
+    }
+}
@@ -0,0 +1,143 @@
+use core::{cmp, ptr};
+
+use crate::{ScanError, VecGrowScan};
+
+impl<'a, T: 'a> VecGrowScan<'a, T> {
+    /// Reserve room for inserting at least `additional` items before the remaining items, returning
+    /// an error if allocation fails.
+    ///
+    /// Returns [`ScanError::CapacityExceeded`] if the vector's length would overflow and
+    /// [`ScanError::AllocationFailed`] if the allocator fails.
+    ///
+    /// Afterwards, inserting up to `additional` items using [`insert`][VecGrowScan::insert] or the
+    /// other methods that insert items before the remaining items does not allocate, neither does
+    /// dropping the scan. Appended items are not covered by this. For scans over a `BytesMut`,
+    /// allocation failure still aborts.
+    ///
+    /// Requires the `try-reserve` feature, which needs Rust 1.57 or later.
+    ///
+    /// ```
+    /// # use vec_mut_scan::{ScanError, VecGrowScan};
+    /// let mut numbers = vec![1, 2, 3];
+    /// let mut scan = VecGrowScan::new(&mut numbers);
+    /// scan.next();
+    ///
+    /// assert_eq!(scan.try_reserve(usize::MAX), Err(ScanError::CapacityExceeded));
+    /// scan.try_reserve(2).unwrap();
+    /// scan.insert(4);
+    /// scan.insert(5);
+    /// drop(scan);
+    ///
+    /// assert_eq!(numbers, [1, 4, 5, 2, 3]);
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), ScanError> {
+        let gap = self.read - self.write;
+        if gap >= additional {
+            return Ok(());
+        }
+        if gap > 0 {
+            // Close the gap, so that the items `0..end` are valid. By invariant, the queue is
+            // empty.
+            unsafe {
+                ptr::copy(
+                    self.base.add(self.read),
+                    self.base.add(self.write),
+                    self.end - self.read,
+                );
+            }
+            self.end -= gap;
+            self.read = self.write;
+        }
+        let len = self.end.checked_add(self.queue.len());
+        if len.and_then(|len| len.checked_add(additional)).is_none() {
+            return Err(ScanError::CapacityExceeded);
+        }
+        let shift = self.queue.len() + additional;
+        unsafe {
+            // Without a gap, the items `0..end` are valid.
+            let base = self
+                .vec
+                .try_reserve_items(self.end, shift)
+                .map_err(|_| ScanError::AllocationFailed)?;
+            self.open_reserved_gap(base, additional);
+        }
+        Ok(())
+    }
+
+    /// Insert an item before the remaining items, returning an error if allocation fails.
+    ///
+    /// See [`insert`][VecGrowScan::insert] and [`try_reserve`][VecGrowScan::try_reserve].
+    ///
+    /// Requires the `try-reserve` feature.
+    pub fn try_insert(&mut self, item: T) -> Result<&mut T, ScanError> {
+        if self.write == self.read {
+            let remaining = self.end - self.read;
+            self.try_reserve(cmp::max(remaining, 1))?;
+        }
+        Ok(self.insert(item))
+    }
+
+    /// Insert an item constructed by `f` before the remaining items, returning an error if
+    /// allocation fails.
+    ///
+    /// Unlike [`try_insert`][VecGrowScan::try_insert], this only constructs the item once room for
+    /// it is reserved, so no item is constructed when allocation fails.
+    ///
+    /// Requires the `try-reserve` feature.
+    pub fn try_insert_with(&mut self, f: impl FnOnce() -> T) -> Result<&mut T, ScanError> {
+        if self.write == self.read {
+            let remaining = self.end - self.read;
+            self.try_reserve(cmp::max(remaining, 1))?;
+        }
+        Ok(self.insert(f()))
+    }
+
+    /// Insert a sequence of items before the remaining items, returning an error if allocation
+    /// fails.
+    ///
+    /// When allocation fails, the items that were already inserted are kept and the other items
+    /// are dropped. See [`insert_many`][VecGrowScan::insert_many] and
+    /// [`try_reserve`][VecGrowScan::try_reserve].
+    ///
+    /// Requires the `try-reserve` feature.
+    pub fn try_insert_many(&mut self, iter: impl IntoIterator<Item = T>) -> Result<(), ScanError> {
+        let iter = iter.into_iter();
+        self.try_reserve(iter.size_hint().0)?;
+        for item in iter {
+            self.try_insert(item)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec, vec::Vec};
+
+    #[test]
+    fn fallible_insertion() {
+        let mut input: Vec<_> = (0..6).map(Rc::new).collect();
+        let input_copy = input.clone();
+
+        let mut scan = VecGrowScan::new(&mut input);
+        scan.next().unwrap().remove();
+        scan.next().unwrap().remove();
+        scan.try_insert(Rc::new(10)).unwrap();
+        scan.try_insert_many((11..14).map(Rc::new)).unwrap();
+        scan.next().unwrap().insert_before(Rc::new(20));
+        assert!(scan.try_reserve(usize::MAX / 2).is_err());
+        scan.try_insert_many(vec![Rc::new(30)]).unwrap();
+        scan.next().unwrap().remove();
+        scan.next().unwrap().remove();
+        scan.try_insert(Rc::new(40)).unwrap();
+        scan.try_insert_with(|| Rc::new(41)).unwrap();
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [10, 11, 12, 13, 20, 2, 30, 40, 41, 5]);
+        drop(input);
+        assert!(input_copy.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+}
@@ -0,0 +1,160 @@
+use core::{mem::MaybeUninit, slice};
+
+use crate::{VecGrowScan, VecMutScan};
+
+impl<'a, T: 'a> VecMutScan<'a, T> {
+    /// Begin a scan over the first `len` items of a fixed capacity buffer with mutation and item
+    /// removal.
+    ///
+    /// This is for buffers that are managed manually, e.g. in environments without an allocator.
+    /// The items are kept at the start of the buffer and `len` is updated when the scan is
+    /// dropped. Should the scan be leaked, `len` is left at zero and the items are leaked.
+    ///
+    /// Panics if `len` is larger than the buffer's capacity.
+    ///
+    /// # Safety
+    ///
+    /// The first `len` items of `buf` must be initialized.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut buf: [MaybeUninit<u32>; 8] = unsafe { MaybeUninit::uninit().assume_init() };
+    /// let mut len = 0;
+    /// for i in 0..5 {
+    ///     buf[len] = MaybeUninit::new(i);
+    ///     len += 1;
+    /// }
+    ///
+    /// let mut scan = unsafe { VecMutScan::from_uninit(&mut buf, &mut len) };
+    /// while let Some(item) = scan.next() {
+    ///     if *item % 2 == 1 {
+    ///         item.remove();
+    ///     }
+    /// }
+    /// drop(scan);
+    ///
+    /// assert_eq!(len, 3);
+    /// ```
+    pub unsafe fn from_uninit(
+        buf: &'a mut [MaybeUninit<T>],
+        len: &'a mut usize,
+    ) -> VecMutScan<'a, T> {
+        assert!(*len <= buf.len(), "length exceeds the buffer's capacity");
+        let end = *len;
+        // `MaybeUninit<T>` has the same layout as `T` and the caller guarantees that the items
+        // `0..end` are valid.
+        VecMutScan::from_raw_parts(len, buf.as_mut_ptr() as *mut T, 0, end)
+    }
+
+    /// The gap left by removed items, for use as scratch space.
+    ///
+    /// The gap is located between the visited and the remaining items and has one slot per
+    /// removed item. Items removed using [`next_back`][VecMutScan::next_back] don't leave a gap,
+    /// as the kept items visited from the back are moved into their place. It is uninitialized and the scan treats it as such: values written to it are
+    /// never dropped and can't be read back once the returned borrow ends, as any scan operation
+    /// may overwrite or move the gap. This allows algorithms to stage items temporarily without
+    /// allocating.
+    ///
+    /// ```
+    /// # use vec_mut_scan::VecMutScan;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut numbers = vec![1, 2, 3, 4, 5];
+    /// let mut scan = VecMutScan::new(&mut numbers);
+    /// scan.next().unwrap().remove();
+    /// scan.next().unwrap().remove();
+    ///
+    /// let scratch = scan.gap_mut();
+    /// assert_eq!(scratch.len(), 2);
+    /// scratch[0] = MaybeUninit::new(10);
+    /// ```
+    pub fn gap_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        // The gap `write..read` is within the buffer and not aliased by any other slice.
+        unsafe {
+            slice::from_raw_parts_mut(
+                self.base.add(self.write) as *mut MaybeUninit<T>,
+                self.read - self.write,
+            )
+        }
+    }
+}
+
+impl<'a, T: 'a> VecGrowScan<'a, T> {
+    /// The gap left by removed items, for use as scratch space.
+    ///
+    /// The gap only exists while no inserted items are queued, see
+    /// [`VecMutScan::gap_mut`] for how it may be used.
+    pub fn gap_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        // As the queue is empty when there is a gap, `write..read` is exactly the gap.
+        unsafe {
+            slice::from_raw_parts_mut(
+                self.base.add(self.write) as *mut MaybeUninit<T>,
+                self.read - self.write,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{rc::Rc, vec::Vec};
+
+    #[test]
+    fn uninit_buffer() {
+        let item = Rc::new(());
+        let mut buf: [MaybeUninit<Rc<()>>; 6] = unsafe { MaybeUninit::uninit().assume_init() };
+        for slot in buf[..4].iter_mut() {
+            *slot = MaybeUninit::new(item.clone());
+        }
+        let mut len = 4;
+
+        let mut scan = unsafe { VecMutScan::from_uninit(&mut buf, &mut len) };
+        scan.next().unwrap().remove();
+        scan.next();
+        scan.next().unwrap().remove();
+        drop(scan);
+
+        assert_eq!(len, 2);
+        assert_eq!(Rc::strong_count(&item), 3);
+
+        for slot in buf[..len].iter_mut() {
+            unsafe { slot.as_mut_ptr().drop_in_place() };
+        }
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+
+    #[test]
+    fn gap_scratch() {
+        let mut input: Vec<_> = (0..8).map(Rc::new).collect();
+        let mut scan = VecGrowScan::new(&mut input);
+
+        assert!(scan.gap_mut().is_empty());
+        scan.next();
+        for _ in 0..3 {
+            scan.next().unwrap().remove();
+        }
+
+        // Reverse the next three items, staging them in the gap.
+        let scratch = scan.gap_mut();
+        assert_eq!(scratch.len(), 3);
+        for slot in 0..3 {
+            // Removing widens the gap at its end, so the staged items stay in place.
+            let value = scan.next().unwrap().remove();
+            scan.gap_mut()[slot] = MaybeUninit::new(value);
+        }
+        let staged: Vec<_> = scan.gap_mut()[..3]
+            .iter()
+            .map(|slot| unsafe { slot.as_ptr().read() })
+            .collect();
+        scan.insert_many(staged.into_iter().rev());
+        drop(scan);
+
+        let values: Vec<_> = input.iter().map(|x| **x).collect();
+        assert_eq!(values, [0, 6, 5, 4, 7]);
+        assert!(input.iter().all(|x| Rc::strong_count(x) == 1));
+    }
+}
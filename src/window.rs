@@ -0,0 +1,139 @@
+use alloc::vec::Vec;
+
+/// The action to take for the center item of a window visited by [`for_each_window`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowAction<T> {
+    /// Keep the center item.
+    Keep,
+    /// Remove the center item.
+    Remove,
+    /// Replace the center item with a new value.
+    Replace(T),
+}
+
+/// Visit every item together with its neighbors, removing or replacing it as requested by `f`.
+///
+/// For every item, `f` is passed a window consisting of up to `radius` kept items before it, the
+/// item itself and up to `radius` remaining items after it, as well as the item's index within
+/// that window. Windows are truncated at the ends of the vector. Later windows contain replacement
+/// values and no removed items, so each window reflects all previous edits, which is what filters
+/// like despiking need.
+///
+/// The items of the window can be mutated as well. Removing an item moves at most `radius` items,
+/// while the removed items are dropped at the end in one go. Should `f` panic, the removed items
+/// are still dropped and all other items are kept.
+///
+/// ```
+/// # use vec_mut_scan::{for_each_window, WindowAction};
+/// // Remove samples that differ from both neighbors by more than 10.
+/// let mut samples = vec![1i32, 2, 50, 3, 4, 60, 61, 5];
+/// for_each_window(&mut samples, 1, |window, center| {
+///     let value = window[center];
+///     let spike = window.len() == 3
+///         && (value - window[0]).abs() > 10
+///         && (value - window[2]).abs() > 10;
+///     if spike {
+///         WindowAction::Remove
+///     } else {
+///         WindowAction::Keep
+///     }
+/// });
+///
+/// assert_eq!(samples, [1, 2, 3, 4, 60, 61, 5]);
+/// ```
+pub fn for_each_window<T>(
+    vec: &mut Vec<T>,
+    radius: usize,
+    mut f: impl FnMut(&mut [T], usize) -> WindowAction<T>,
+) {
+    // The vector is split into the kept items `..removed_start`, the removed items
+    // `removed_start..removed_end`, at most `radius` recently kept items `removed_end..read`, the
+    // current item and the remaining items. This keeps each window contiguous.
+    let mut state = DrainRemovedOnDrop {
+        vec,
+        removed_start: 0,
+        removed_end: 0,
+    };
+
+    let mut read = 0;
+    while read < state.vec.len() {
+        let before = read - state.removed_end;
+        let window_end = state
+            .vec
+            .len()
+            .min(read.saturating_add(radius).saturating_add(1));
+        let action = f(&mut state.vec[read - before..window_end], before);
+
+        match action {
+            WindowAction::Keep => (),
+            WindowAction::Remove => {
+                // Move the removed item in front of the recently kept items.
+                state.vec[state.removed_end..=read].rotate_right(1);
+                state.removed_end += 1;
+                read += 1;
+                continue;
+            }
+            WindowAction::Replace(value) => state.vec[read] = value,
+        }
+
+        read += 1;
+        if read - state.removed_end > radius {
+            // The oldest recently kept item is no longer part of any window.
+            state.vec.swap(state.removed_start, state.removed_end);
+            state.removed_start += 1;
+            state.removed_end += 1;
+        }
+    }
+}
+
+/// Drops the removed items when dropped, also when unwinding.
+struct DrainRemovedOnDrop<'a, T> {
+    vec: &'a mut Vec<T>,
+    removed_start: usize,
+    removed_end: usize,
+}
+
+impl<'a, T> Drop for DrainRemovedOnDrop<'a, T> {
+    fn drop(&mut self) {
+        self.vec.drain(self.removed_start..self.removed_end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn windows_reflect_edits() {
+        for radius in 0..4 {
+            let mut input: Vec<_> = (0..20).collect();
+            let mut kept = vec![];
+
+            for_each_window(&mut input, radius, |window, center| {
+                let before = &window[..center];
+                assert_eq!(before, &kept[kept.len() - before.len()..]);
+                assert!(before.len() == radius || before.len() == kept.len());
+
+                let value = window[center];
+                assert!(window[center..]
+                    .iter()
+                    .copied()
+                    .eq(value..value + window.len() as i32 - center as i32));
+
+                if value % 3 == 0 {
+                    WindowAction::Remove
+                } else if value % 3 == 1 {
+                    kept.push(value * 10);
+                    WindowAction::Replace(value * 10)
+                } else {
+                    kept.push(value);
+                    WindowAction::Keep
+                }
+            });
+
+            assert_eq!(input, kept);
+        }
+    }
+}
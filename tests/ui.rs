@@ -0,0 +1,8 @@
+// Misuse of the API that must not compile. The expected compiler output can be updated with
+// `TRYBUILD=overwrite cargo test --test ui`.
+#[test]
+#[cfg_attr(miri, ignore)]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+use vec_mut_scan::VecGrowScan;
+
+fn main() {
+    let mut numbers = vec![1, 2, 3];
+    let mut scan = VecGrowScan::new(&mut numbers);
+    let first = scan.next().unwrap().keep();
+    scan.insert(0);
+    *first = 10;
+}
@@ -0,0 +1,10 @@
+use vec_mut_scan::VecMutScan;
+
+fn main() {
+    let mut numbers = vec![1, 2, 3];
+    let mut scan = VecMutScan::new(&mut numbers);
+    let item = scan.next().unwrap();
+    let value: &i32 = &item;
+    item.remove();
+    println!("{}", value);
+}
@@ -0,0 +1,9 @@
+use vec_mut_scan::VecMutScan;
+
+fn main() {
+    let mut numbers = vec![1, 2, 3];
+    let mut scan = VecMutScan::new(&mut numbers);
+    let first = scan.next().unwrap();
+    let second = scan.next().unwrap();
+    drop((first, second));
+}
@@ -0,0 +1,8 @@
+use vec_mut_scan::VecMutScan;
+
+fn main() {
+    let mut numbers = vec![1, 2, 3];
+    let mut scan = VecMutScan::new(&mut numbers);
+    numbers.push(4);
+    scan.next();
+}